@@ -1,36 +1,30 @@
 use hc_zome_attachments_integrity::*;
-use hdk::prelude::holo_hash::DnaHash;
 use hdk::prelude::*;
-
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Hrl {
-    dna_hash: DnaHash,
-    resource_hash: AnyDhtHash,
-}
-
-#[derive(Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
-pub struct HrlWithContext {
-    hrl: Hrl,
-    context: SerializedBytes,
-}
+pub use we_types::{Hrl, HrlWithContext};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AddAttachmentInput {
-    hash: AnyDhtHash,
-    hrl_with_context: HrlWithContext,
+    pub hash: AnyDhtHash,
+    pub hrl_with_context: HrlWithContext,
 }
 
+/// Links the hash to the attached HRL and back. Both links are created in the same
+/// zome call, so either both or none of them are committed.
 #[hdk_extern]
 pub fn add_attachment(input: AddAttachmentInput) -> ExternResult<()> {
+    let resource_hash = input.hrl_with_context.hrl.resource_hash.clone();
+    let tag = SerializedBytes::try_from(input.hrl_with_context)
+        .map_err(|err| wasm_error!(err))?
+        .bytes()
+        .clone();
+
     create_link(
-        input.hash,
-        input.hrl_with_context.hrl.resource_hash.clone(),
+        input.hash.clone(),
+        resource_hash.clone(),
         LinkTypes::Attachment,
-        SerializedBytes::try_from(input.hrl_with_context)
-            .map_err(|err| wasm_error!(err))?
-            .bytes()
-            .clone(),
+        tag.clone(),
     )?;
+    create_link(resource_hash, input.hash, LinkTypes::AttachedTo, tag)?;
 
     Ok(())
 }
@@ -50,13 +44,13 @@ pub fn get_attachments(hash: AnyDhtHash) -> ExternResult<Vec<HrlWithContext>> {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RemoveAttachmentInput {
-    hash: AnyDhtHash,
-    hrl_with_context: HrlWithContext,
+    pub hash: AnyDhtHash,
+    pub hrl_with_context: HrlWithContext,
 }
 
 #[hdk_extern]
 pub fn remove_attachment(input: RemoveAttachmentInput) -> ExternResult<()> {
-    let links = get_links(input.hash, LinkTypes::Attachment, None)?;
+    let links = get_links(input.hash.clone(), LinkTypes::Attachment, None)?;
 
     let attachment_links: Vec<Link> = links
         .into_iter()
@@ -73,5 +67,50 @@ pub fn remove_attachment(input: RemoveAttachmentInput) -> ExternResult<()> {
         delete_link(attachment_link.create_link_hash)?;
     }
 
+    let attached_to_links = get_links(
+        input.hrl_with_context.hrl.resource_hash.clone(),
+        LinkTypes::AttachedTo,
+        None,
+    )?;
+    let hash = AnyLinkableHash::from(input.hash);
+    for link in attached_to_links {
+        if link.target.ne(&hash) {
+            continue;
+        }
+        let sb = SerializedBytes::from(UnsafeBytes::from(link.tag.0.clone()));
+        if let Ok(hrl_with_context) = HrlWithContext::try_from(sb) {
+            if hrl_with_context.eq(&input.hrl_with_context) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+
     Ok(())
 }
+
+/// Gets the hashes that the given HRL has been attached to, i.e. the reverse of
+/// get_attachments. Attachments added before add_attachment created the AttachedTo
+/// link have no backlink and are not found here. Links with malformed tags are skipped.
+#[hdk_extern]
+pub fn get_attached_to(hrl: Hrl) -> ExternResult<Vec<AnyDhtHash>> {
+    let links = get_links(hrl.resource_hash.clone(), LinkTypes::AttachedTo, None)?;
+
+    let mut hashes: Vec<AnyDhtHash> = Vec::new();
+    for link in links {
+        let sb = SerializedBytes::from(UnsafeBytes::from(link.tag.0));
+        let Ok(hrl_with_context) = HrlWithContext::try_from(sb) else {
+            continue;
+        };
+        if hrl_with_context.hrl.ne(&hrl) {
+            continue;
+        }
+        let Some(hash) = link.target.into_any_dht_hash() else {
+            continue;
+        };
+        if !hashes.contains(&hash) {
+            hashes.push(hash);
+        }
+    }
+
+    Ok(hashes)
+}
@@ -0,0 +1,148 @@
+use hc_zome_attachments_coordinator::{
+    AddAttachmentInput, Hrl, HrlWithContext, RemoveAttachmentInput,
+};
+use hdk::prelude::*;
+use holochain::prelude::{
+    CoordinatorZome, CoordinatorZomeDef, DnaFile, DnaWasm, InlineCoordinatorZome,
+    IntegrityZomeDef,
+};
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+/// Builds a DNA with the attachments zomes, plus an inline zome that can create
+/// AttachedTo links with arbitrary tags, like buggy or older versions of the zome could
+async fn attachments_dna() -> DnaFile {
+    let wasm_dir = std::env::current_dir()
+        .unwrap()
+        .join("../../target/wasm32-unknown-unknown/release");
+    let integrity_wasm = DnaWasm::from(
+        std::fs::read(wasm_dir.join("hc_zome_attachments_integrity.wasm")).unwrap(),
+    );
+    let coordinator_wasm = DnaWasm::from(
+        std::fs::read(wasm_dir.join("hc_zome_attachments_coordinator.wasm")).unwrap(),
+    );
+
+    let integrity_zome = (
+        ZomeName::from("attachments_integrity"),
+        IntegrityZomeDef::from_hash(WasmHash::with_data(&integrity_wasm).await),
+    );
+    let mut coordinator_zome: CoordinatorZome = (
+        ZomeName::from("attachments"),
+        CoordinatorZomeDef::from_hash(WasmHash::with_data(&coordinator_wasm).await),
+    )
+        .into();
+    coordinator_zome.set_dependency("attachments_integrity");
+
+    let malformed_links = InlineCoordinatorZome::new_unique().function(
+        "create_attached_to_link",
+        |api, (base, target, tag): (AnyLinkableHash, AnyLinkableHash, Vec<u8>)| {
+            // AttachedTo is the second link type of the first integrity zome
+            let hash = api.create_link(CreateLinkInput::new(
+                base,
+                target,
+                ZomeIndex(0),
+                LinkType(1),
+                LinkTag::new(tag),
+                ChainTopOrdering::default(),
+            ))?;
+            Ok(hash)
+        },
+    );
+    let mut malformed_links_zome: CoordinatorZome = (
+        ZomeName::from("malformed_links"),
+        CoordinatorZomeDef::from(malformed_links),
+    )
+        .into();
+    malformed_links_zome.set_dependency("attachments_integrity");
+
+    let (dna, _, _) = SweetDnaFile::unique_from_zomes(
+        vec![integrity_zome],
+        vec![coordinator_zome, malformed_links_zome],
+        vec![integrity_wasm, coordinator_wasm],
+    )
+    .await;
+    dna
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn add_get_and_remove_attachments() {
+    let dna = attachments_dna().await;
+
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("attachments", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bob,)) = apps.into_tuples();
+    let alice_zome = alice.zome("attachments");
+    let bob_zome = bob.zome("attachments");
+
+    let hash = AnyDhtHash::from(EntryHash::from_raw_32(vec![1; 32]));
+    let hrl = Hrl {
+        dna_hash: DnaHash::from_raw_32(vec![2; 32]),
+        resource_hash: AnyDhtHash::from(EntryHash::from_raw_32(vec![3; 32])),
+    };
+    let hrl_with_context = HrlWithContext {
+        hrl: hrl.clone(),
+        context: SerializedBytes::try_from(()).unwrap(),
+    };
+
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "add_attachment",
+            AddAttachmentInput {
+                hash: hash.clone(),
+                hrl_with_context: hrl_with_context.clone(),
+            },
+        )
+        .await;
+
+    // An AttachedTo link whose tag is not an HrlWithContext
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice.zome("malformed_links"),
+            "create_attached_to_link",
+            (
+                AnyLinkableHash::from(hrl.resource_hash.clone()),
+                AnyLinkableHash::from(EntryHash::from_raw_32(vec![4; 32])),
+                vec![0xc1_u8, 0x00, 0xff],
+            ),
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let attachments: Vec<HrlWithContext> = conductors[1]
+        .call(&bob_zome, "get_attachments", hash.clone())
+        .await;
+    assert_eq!(attachments, vec![hrl_with_context.clone()]);
+
+    // The link with the malformed tag is skipped instead of failing the call
+    let attached_to: Vec<AnyDhtHash> = conductors[1]
+        .call(&bob_zome, "get_attached_to", hrl.clone())
+        .await;
+    assert_eq!(attached_to, vec![hash.clone()]);
+
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "remove_attachment",
+            RemoveAttachmentInput {
+                hash: hash.clone(),
+                hrl_with_context,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let attachments: Vec<HrlWithContext> = conductors[1]
+        .call(&bob_zome, "get_attachments", hash)
+        .await;
+    assert!(attachments.is_empty());
+
+    let attached_to: Vec<AnyDhtHash> = conductors[1]
+        .call(&bob_zome, "get_attached_to", hrl)
+        .await;
+    assert!(attached_to.is_empty());
+}
@@ -3,4 +3,7 @@ use hdi::prelude::*;
 #[hdk_link_types]
 pub enum LinkTypes {
     Attachment,
+    /// The reverse of an Attachment link, from the resource hash of the attached HRL
+    /// to the hash that it is attached to, with the same tag
+    AttachedTo,
 }
@@ -1,8 +1,22 @@
 use std::collections::BTreeMap;
 
-use hdk::prelude::holo_hash::DnaHash;
+use hdk::prelude::holo_hash::{AgentPubKeyB64, DnaHash};
 use hdk::prelude::*;
 
+/// Name of the integrity zome of the group DNA that defines Applets and their links
+pub const GROUP_INTEGRITY_ZOME_NAME: &str = "group_integrity";
+
+/// The anchor from which the group integrity zome links the Applets of the group
+pub const GROUP_APPLETS_ANCHOR: &str = "group_applets";
+
+/// Index of the AnchorToApplet link type among the link types of the group integrity
+/// zome, so that other zomes of the group DNA can recognize these links
+pub const ANCHOR_TO_APPLET_LINK_TYPE: u8 = 0;
+
+/// Larger blobs need to be split into multiple chunks, so that no single entry gets
+/// close to the maximum entry size
+pub const MAX_BLOB_CHUNK_SIZE: usize = 1_048_576;
+
 /// An applet instance
 #[hdk_entry_helper]
 #[derive(Clone)]
@@ -23,9 +37,74 @@ pub struct Applet {
     pub properties: BTreeMap<String, SerializedBytes>, // Segmented by RoleName
 }
 
+/// The fields added after the first release default to empty values, so that
+/// GroupProfiles created before they existed still deserialize
 #[hdk_entry_helper]
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct GroupProfile {
     pub name: String,
     pub logo_src: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub banner_src: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Hash of a Blob in the blobs zome holding the logo. If set, logo_src may be empty.
+    #[serde(default)]
+    pub logo_blob_hash: Option<EntryHash>,
+}
+
+/// The DNA properties of a group DNA. A group without a progenitor is
+/// not governed by stewards, i.e. every member has the same permissions.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Default)]
+pub struct GroupDnaProperties {
+    pub progenitor: Option<AgentPubKeyB64>,
+    /// If true, agents can only join the group with a GroupInvitation as membrane proof.
+    /// Requires a progenitor.
+    #[serde(default)]
+    pub invite_only: bool,
+}
+
+/// An invitation for a specific agent to join an invite-only group, issued by the
+/// progenitor of the group or by one of its stewards
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct GroupInvitation {
+    pub group_dna_hash: DnaHash,
+    pub invitee: AgentPubKey,
+    pub inviter: AgentPubKey,
+    /// The StewardGrant of the inviter, None if the inviter is the progenitor
+    pub steward_grant_hash: Option<ActionHash>,
+    /// The StewardAttestation of the progenitor for the StewardGrant of the inviter:
+    /// its chain top, until when the invitation can be used and its signature
+    #[serde(default)]
+    pub progenitor_chain_top: Option<ActionHash>,
+    #[serde(default)]
+    pub valid_until: Option<Timestamp>,
+    #[serde(default)]
+    pub progenitor_signature: Option<Signature>,
+    pub expiry: Option<Timestamp>,
+}
+
+/// A GroupInvitation signed by the inviter, to be passed as the membrane proof
+/// of the group DNA
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct SignedGroupInvitation {
+    pub invitation: GroupInvitation,
+    pub signature: Signature,
+}
+
+/// Holochain Resource Locator: points to a resource, e.g. an entry or action, in the
+/// DNA with the given hash
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Hrl {
+    pub dna_hash: DnaHash,
+    pub resource_hash: AnyDhtHash,
+}
+
+/// An Hrl together with applet specific context, e.g. the position within a document
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct HrlWithContext {
+    pub hrl: Hrl,
+    pub context: SerializedBytes,
 }
@@ -0,0 +1,67 @@
+use blobs_integrity::*;
+use hdk::prelude::*;
+
+/// Stores a chunk of a Blob. Returns the hash of the chunk, without creating it again if
+/// the same chunk has already been stored by anyone in the group.
+#[hdk_extern]
+pub fn create_blob_chunk(blob_chunk: BlobChunk) -> ExternResult<EntryHash> {
+    let chunk_hash = hash_entry(&blob_chunk)?;
+    if get(chunk_hash.clone(), GetOptions::default())?.is_none() {
+        create_entry(EntryTypes::BlobChunk(blob_chunk))?;
+    }
+    Ok(chunk_hash)
+}
+
+/// Stores a Blob whose chunks have been stored before with `create_blob_chunk`. Returns
+/// the hash by which the Blob can be referenced.
+#[hdk_extern]
+pub fn create_blob(blob: Blob) -> ExternResult<EntryHash> {
+    let blob_hash = hash_entry(&blob)?;
+    if get(blob_hash.clone(), GetOptions::default())?.is_none() {
+        create_entry(EntryTypes::Blob(blob))?;
+    }
+    Ok(blob_hash)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StoreBlobInput {
+    pub mime_type: String,
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+/// Splits the given bytes into chunks and stores them together with the Blob in one
+/// go. Meant for small blobs like logos, larger blobs should be stored chunk by chunk.
+#[hdk_extern]
+pub fn store_blob(input: StoreBlobInput) -> ExternResult<EntryHash> {
+    let mut chunk_hashes: Vec<EntryHash> = Vec::new();
+    for chunk in input.bytes.chunks(MAX_BLOB_CHUNK_SIZE) {
+        chunk_hashes.push(create_blob_chunk(BlobChunk {
+            bytes: chunk.to_vec(),
+        })?);
+    }
+    create_blob(Blob {
+        mime_type: input.mime_type,
+        size: input.bytes.len(),
+        chunk_hashes,
+    })
+}
+
+#[hdk_extern]
+pub fn get_blob(blob_hash: EntryHash) -> ExternResult<Option<Blob>> {
+    let Some(record) = get(blob_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    record.entry().to_app_option::<Blob>().map_err(|e| wasm_error!(e))
+}
+
+#[hdk_extern]
+pub fn get_blob_chunk(chunk_hash: EntryHash) -> ExternResult<Option<BlobChunk>> {
+    let Some(record) = get(chunk_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    record
+        .entry()
+        .to_app_option::<BlobChunk>()
+        .map_err(|e| wasm_error!(e))
+}
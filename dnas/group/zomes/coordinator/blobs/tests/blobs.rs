@@ -0,0 +1,108 @@
+use blobs_coordinator::StoreBlobInput;
+use blobs_integrity::{Blob, BlobChunk, MAX_BLOB_CHUNK_SIZE};
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blobs_are_chunked_and_reassembled() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("blobs");
+    let bob_zome = bobbo.zome("blobs");
+
+    let bytes: Vec<u8> = (0..(MAX_BLOB_CHUNK_SIZE + 10))
+        .map(|i| (i % 256) as u8)
+        .collect();
+
+    let blob_hash: EntryHash = conductors[0]
+        .call(
+            &alice_zome,
+            "store_blob",
+            StoreBlobInput {
+                mime_type: String::from("image/png"),
+                bytes: bytes.clone(),
+            },
+        )
+        .await;
+
+    // Storing the same contents again results in the same hash
+    let same_blob_hash: EntryHash = conductors[0]
+        .call(
+            &alice_zome,
+            "store_blob",
+            StoreBlobInput {
+                mime_type: String::from("image/png"),
+                bytes: bytes.clone(),
+            },
+        )
+        .await;
+    assert_eq!(blob_hash, same_blob_hash);
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let blob: Option<Blob> = conductors[1]
+        .call(&bob_zome, "get_blob", blob_hash)
+        .await;
+    let blob = blob.unwrap();
+    assert_eq!(blob.mime_type, "image/png");
+    assert_eq!(blob.size, bytes.len());
+    assert_eq!(blob.chunk_hashes.len(), 2);
+
+    let mut reassembled: Vec<u8> = Vec::new();
+    for chunk_hash in blob.chunk_hashes {
+        let chunk: Option<BlobChunk> = conductors[1]
+            .call(&bob_zome, "get_blob_chunk", chunk_hash)
+            .await;
+        reassembled.extend(chunk.unwrap().bytes);
+    }
+    assert_eq!(reassembled, bytes);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blob_size_must_match_its_chunks() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    let mut conductor = SweetConductor::from_config(ConductorConfig::default()).await;
+    let app = conductor.setup_app("we", &[dna]).await.unwrap();
+    let (alice,) = app.into_tuple();
+    let zome = alice.zome("blobs");
+
+    let chunk_hash: EntryHash = conductor
+        .call(
+            &zome,
+            "create_blob_chunk",
+            BlobChunk {
+                bytes: vec![1, 2, 3],
+            },
+        )
+        .await;
+
+    let result: Result<EntryHash, _> = conductor
+        .call_fallible(
+            &zome,
+            "create_blob",
+            Blob {
+                mime_type: String::from("image/png"),
+                size: 4,
+                chunk_hashes: vec![chunk_hash],
+            },
+        )
+        .await;
+    assert!(result.is_err());
+}
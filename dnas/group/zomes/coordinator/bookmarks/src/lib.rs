@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use bookmarks_integrity::*;
+use hdk::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PinHrlInput {
+    pub hrl_with_context: HrlWithContext,
+    pub title: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetBookmarksInput {
+    #[serde(default)]
+    pub offset: usize,
+    /// Returns all bookmarks from the offset onwards if None
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// A pinned Bookmark together with who pinned it and when
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BookmarkInfo {
+    pub bookmark_hash: ActionHash,
+    pub bookmark: Bookmark,
+    pub author: AgentPubKey,
+    pub pinned_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BookmarksPage {
+    pub bookmarks: Vec<BookmarkInfo>,
+    /// The number of bookmarks across all pages
+    pub total: usize,
+}
+
+/// Pins an HRL to the group home
+#[hdk_extern]
+pub fn pin_hrl(input: PinHrlInput) -> ExternResult<ActionHash> {
+    let bookmark_hash = create_entry(EntryTypes::Bookmark(Bookmark {
+        hrl_with_context: input.hrl_with_context,
+        title: input.title,
+    }))?;
+    create_link(
+        bookmarks_path().path_entry_hash()?,
+        bookmark_hash.clone(),
+        LinkTypes::AnchorToBookmark,
+        (),
+    )?;
+    Ok(bookmark_hash)
+}
+
+/// Removes a bookmark from the group home. Only its author can do so.
+#[hdk_extern]
+pub fn unpin_bookmark(bookmark_hash: ActionHash) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let record = get(bookmark_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Bookmark not found."))
+    ))?;
+    if record.action().author().ne(&my_pub_key) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Bookmarks can only be unpinned by their author."
+        ))));
+    }
+
+    let links = get_links(
+        bookmarks_path().path_entry_hash()?,
+        LinkTypes::AnchorToBookmark,
+        None,
+    )?;
+    for link in links {
+        if link.target.into_action_hash().eq(&Some(bookmark_hash.clone())) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    delete_entry(bookmark_hash)?;
+
+    Ok(())
+}
+
+/// Sets the order in which the bookmarks of the group are shown
+#[hdk_extern]
+pub fn reorder_bookmarks(bookmark_hashes: Vec<ActionHash>) -> ExternResult<ActionHash> {
+    let bookmark_order_hash =
+        create_entry(EntryTypes::BookmarkOrder(BookmarkOrder { bookmark_hashes }))?;
+    create_link(
+        bookmark_order_path().path_entry_hash()?,
+        bookmark_order_hash.clone(),
+        LinkTypes::AnchorToBookmarkOrder,
+        (),
+    )?;
+    Ok(bookmark_order_hash)
+}
+
+/// Gets a page of the bookmarks of the group, in the latest order set with
+/// `reorder_bookmarks`. Bookmarks that are not part of that order come last, the
+/// earliest pinned first.
+#[hdk_extern]
+pub fn get_bookmarks(input: GetBookmarksInput) -> ExternResult<BookmarksPage> {
+    let mut links = get_links(
+        bookmarks_path().path_entry_hash()?,
+        LinkTypes::AnchorToBookmark,
+        None,
+    )?;
+    links.sort_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+    let mut bookmark_hashes: Vec<ActionHash> = links
+        .into_iter()
+        .filter_map(|link| link.target.into_action_hash())
+        .collect();
+
+    let positions: HashMap<ActionHash, usize> = get_latest_bookmark_order()?
+        .map(|bookmark_order| bookmark_order.bookmark_hashes)
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(position, bookmark_hash)| (bookmark_hash, position))
+        .collect();
+    // Stable sort, so unordered bookmarks keep their pinning order
+    bookmark_hashes.sort_by_key(|bookmark_hash| {
+        positions.get(bookmark_hash).copied().unwrap_or(usize::MAX)
+    });
+
+    let total = bookmark_hashes.len();
+    let start = input.offset.min(total);
+    let end = match input.limit {
+        Some(limit) => start.saturating_add(limit).min(total),
+        None => total,
+    };
+
+    let get_input: Vec<GetInput> = bookmark_hashes
+        .drain(start..end)
+        .map(|bookmark_hash| GetInput::new(bookmark_hash.into(), GetOptions::default()))
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+
+    let mut bookmarks: Vec<BookmarkInfo> = Vec::new();
+    for record in records.into_iter().flatten() {
+        let Some(bookmark) = record
+            .entry()
+            .to_app_option::<Bookmark>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        bookmarks.push(BookmarkInfo {
+            bookmark_hash: record.action_address().clone(),
+            bookmark,
+            author: record.action().author().clone(),
+            pinned_at: record.action().timestamp(),
+        });
+    }
+
+    Ok(BookmarksPage { bookmarks, total })
+}
+
+/// Links with the same timestamp are ordered by their action hash, so that every agent
+/// arrives at the same order
+fn get_latest_bookmark_order() -> ExternResult<Option<BookmarkOrder>> {
+    let links = get_links(
+        bookmark_order_path().path_entry_hash()?,
+        LinkTypes::AnchorToBookmarkOrder,
+        None,
+    )?;
+    let latest_link = links.into_iter().max_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+    let Some(bookmark_order_hash) = latest_link.and_then(|link| link.target.into_action_hash())
+    else {
+        return Ok(None);
+    };
+    let Some(record) = get(bookmark_order_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    record
+        .entry()
+        .to_app_option::<BookmarkOrder>()
+        .map_err(|e| wasm_error!(e))
+}
@@ -0,0 +1,113 @@
+use ::fixt::prelude::fixt;
+
+use bookmarks_coordinator::{BookmarksPage, GetBookmarksInput, PinHrlInput};
+use bookmarks_integrity::{Hrl, HrlWithContext};
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+fn sample_pin(title: &str) -> PinHrlInput {
+    PinHrlInput {
+        hrl_with_context: HrlWithContext {
+            hrl: Hrl {
+                dna_hash: fixt!(DnaHash),
+                resource_hash: fixt!(ActionHash).into(),
+            },
+            context: SerializedBytes::try_from(()).unwrap(),
+        },
+        title: String::from(title),
+    }
+}
+
+fn titles(page: &BookmarksPage) -> Vec<String> {
+    page.bookmarks
+        .iter()
+        .map(|info| info.bookmark.title.clone())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pin_reorder_and_unpin_bookmarks() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("bookmarks");
+    let bob_zome = bobbo.zome("bookmarks");
+
+    let first_hash: ActionHash = conductors[0]
+        .call(&alice_zome, "pin_hrl", sample_pin("first"))
+        .await;
+    let second_hash: ActionHash = conductors[0]
+        .call(&alice_zome, "pin_hrl", sample_pin("second"))
+        .await;
+    let third_hash: ActionHash = conductors[1]
+        .call(&bob_zome, "pin_hrl", sample_pin("third"))
+        .await;
+
+    // Titles must not be empty
+    let result: Result<ActionHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "pin_hrl", sample_pin(" "))
+        .await;
+    assert!(result.is_err());
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let page: BookmarksPage = conductors[1]
+        .call(&bob_zome, "get_bookmarks", GetBookmarksInput::default())
+        .await;
+    assert_eq!(page.total, 3);
+    assert_eq!(titles(&page), vec!["first", "second", "third"]);
+
+    // Any member can reorder, unordered bookmarks come last
+    let _: ActionHash = conductors[1]
+        .call(
+            &bob_zome,
+            "reorder_bookmarks",
+            vec![third_hash.clone(), first_hash.clone()],
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let page: BookmarksPage = conductors[0]
+        .call(
+            &alice_zome,
+            "get_bookmarks",
+            GetBookmarksInput {
+                offset: 1,
+                limit: Some(1),
+            },
+        )
+        .await;
+    assert_eq!(page.total, 3);
+    assert_eq!(titles(&page), vec!["first"]);
+
+    // Only the author can unpin
+    let result: Result<(), _> = conductors[1]
+        .call_fallible(&bob_zome, "unpin_bookmark", second_hash.clone())
+        .await;
+    assert!(result.is_err());
+
+    let _: () = conductors[0]
+        .call(&alice_zome, "unpin_bookmark", second_hash)
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let page: BookmarksPage = conductors[1]
+        .call(&bob_zome, "get_bookmarks", GetBookmarksInput::default())
+        .await;
+    assert_eq!(page.total, 2);
+    assert_eq!(titles(&page), vec!["third", "first"]);
+}
@@ -2,7 +2,7 @@ use custom_views_integrity::*;
 use hdk::prelude::*;
 #[hdk_extern]
 pub fn get_all_custom_views(_: ()) -> ExternResult<Vec<Record>> {
-    let path = Path::from("all_custom_views");
+    let path = all_custom_views_path();
     let links = get_links(path.path_entry_hash()?, LinkTypes::AllCustomViews, None)?;
     let get_input: Vec<GetInput> = links
         .into_iter()
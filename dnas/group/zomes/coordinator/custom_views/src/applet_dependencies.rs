@@ -0,0 +1,206 @@
+use std::collections::{BTreeSet, HashSet};
+
+use custom_views_integrity::*;
+use hdk::prelude::*;
+
+use crate::custom_view::get_latest_custom_view;
+
+/// A CustomView that depends on Applets that have been archived in the group
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BrokenCustomView {
+    pub custom_view_hash: ActionHash,
+    pub archived_applets: Vec<EntryHash>,
+}
+
+/// Links the Applets to the original create action of the CustomView, with the version
+/// of the view that declares the dependency in the tag
+pub(crate) fn link_applet_dependencies(
+    original_custom_view_hash: ActionHash,
+    custom_view_version_hash: ActionHash,
+    applet_hashes: Vec<EntryHash>,
+) -> ExternResult<()> {
+    for applet_hash in applet_hashes {
+        create_link(
+            applet_hash,
+            original_custom_view_hash.clone(),
+            LinkTypes::AppletToCustomViews,
+            CustomViewDependencyTag {
+                custom_view_version_hash: custom_view_version_hash.clone(),
+                archive_hash: None,
+            }
+            .to_tag()?,
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn unlink_applet_dependencies(
+    original_custom_view_hash: ActionHash,
+    applet_hashes: Vec<EntryHash>,
+) -> ExternResult<()> {
+    for applet_hash in applet_hashes {
+        let links = get_links(applet_hash, LinkTypes::AppletToCustomViews, None)?;
+        for link in links {
+            if link.target.into_action_hash().eq(&Some(original_custom_view_hash.clone())) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Gets the latest version of all CustomViews that currently depend on the given Applet
+#[hdk_extern]
+pub fn get_custom_views_for_applet(applet_hash: EntryHash) -> ExternResult<Vec<Record>> {
+    Ok(get_dependent_custom_views(applet_hash)?
+        .into_iter()
+        .map(|(_original_custom_view_hash, record)| record)
+        .collect())
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FlagCustomViewsBrokenInput {
+    pub applet_hash: EntryHash,
+    /// The deletion of the AnchorToApplet link that archived the Applet
+    pub archive_hash: ActionHash,
+}
+
+/// Flags all CustomViews that depend on the given Applet as broken. Called by the group
+/// zome when the Applet gets archived.
+#[hdk_extern]
+pub fn flag_custom_views_broken(input: FlagCustomViewsBrokenInput) -> ExternResult<()> {
+    let applet_hash = input.applet_hash;
+    for (original_custom_view_hash, record) in get_dependent_custom_views(applet_hash.clone())? {
+        if get_archived_applets(original_custom_view_hash.clone())?.contains(&applet_hash) {
+            continue;
+        }
+        create_link(
+            original_custom_view_hash,
+            applet_hash.clone(),
+            LinkTypes::CustomViewToArchivedApplet,
+            CustomViewDependencyTag {
+                custom_view_version_hash: record.action_address().clone(),
+                archive_hash: Some(input.archive_hash.clone()),
+            }
+            .to_tag()?,
+        )?;
+    }
+    Ok(())
+}
+
+/// Removes the broken flags that the given Applet caused. Called by the group zome
+/// when the Applet gets unarchived. Validation only allows MAX_UNFLAGGED_CUSTOM_VIEWS
+/// flags to be removed at once, the remaining ones stay until their authors remove them.
+#[hdk_extern]
+pub fn unflag_custom_views_broken(applet_hash: EntryHash) -> ExternResult<()> {
+    let mut unflagged: u32 = 0;
+    let links = get_links(applet_hash.clone(), LinkTypes::AppletToCustomViews, None)?;
+    let original_custom_view_hashes: HashSet<ActionHash> = links
+        .into_iter()
+        .filter_map(|link| link.target.into_action_hash())
+        .collect();
+    for original_custom_view_hash in original_custom_view_hashes {
+        let links = get_links(
+            original_custom_view_hash,
+            LinkTypes::CustomViewToArchivedApplet,
+            None,
+        )?;
+        for link in links {
+            if link.target.into_entry_hash().eq(&Some(applet_hash.clone())) {
+                if unflagged >= MAX_UNFLAGGED_CUSTOM_VIEWS {
+                    return Ok(());
+                }
+                delete_link(link.create_link_hash)?;
+                unflagged += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Gets all CustomViews whose latest version depends on an archived Applet
+#[hdk_extern]
+pub fn get_broken_custom_views(_: ()) -> ExternResult<Vec<BrokenCustomView>> {
+    let links = get_links(
+        all_custom_views_path().path_entry_hash()?,
+        LinkTypes::AllCustomViews,
+        None,
+    )?;
+    let mut broken_custom_views: Vec<BrokenCustomView> = Vec::new();
+    for custom_view_hash in links.into_iter().filter_map(|link| link.target.into_action_hash()) {
+        let archived_applets = get_archived_applets(custom_view_hash.clone())?;
+        if archived_applets.is_empty() {
+            continue;
+        }
+        let Some(custom_view) = get_latest_custom_view_entry(custom_view_hash.clone())? else {
+            continue;
+        };
+        // The view may have dropped the dependency since it was flagged
+        let archived_applets: Vec<EntryHash> = archived_applets
+            .into_iter()
+            .filter(|applet_hash| custom_view.applet_dependencies.contains(applet_hash))
+            .collect();
+        if !archived_applets.is_empty() {
+            broken_custom_views.push(BrokenCustomView {
+                custom_view_hash,
+                archived_applets,
+            });
+        }
+    }
+    Ok(broken_custom_views)
+}
+
+/// The original create action hash and latest version of all CustomViews whose latest
+/// version depends on the given Applet
+fn get_dependent_custom_views(applet_hash: EntryHash) -> ExternResult<Vec<(ActionHash, Record)>> {
+    let mut links = get_links(applet_hash.clone(), LinkTypes::AppletToCustomViews, None)?;
+    links.sort_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+
+    let mut seen: HashSet<ActionHash> = HashSet::new();
+    let mut custom_views: Vec<(ActionHash, Record)> = Vec::new();
+    for link in links {
+        let Some(original_custom_view_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        if !seen.insert(original_custom_view_hash.clone()) {
+            continue;
+        }
+        let Some(record) = get_latest_custom_view(original_custom_view_hash.clone())? else {
+            continue;
+        };
+        let custom_view: Option<CustomView> =
+            record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+        if let Some(custom_view) = custom_view {
+            if custom_view.applet_dependencies.contains(&applet_hash) {
+                custom_views.push((original_custom_view_hash, record));
+            }
+        }
+    }
+    Ok(custom_views)
+}
+
+fn get_archived_applets(original_custom_view_hash: ActionHash) -> ExternResult<Vec<EntryHash>> {
+    let archived_applets: BTreeSet<EntryHash> = get_links(
+        original_custom_view_hash,
+        LinkTypes::CustomViewToArchivedApplet,
+        None,
+    )?
+    .into_iter()
+    .filter_map(|link| link.target.into_entry_hash())
+    .collect();
+    Ok(archived_applets.into_iter().collect())
+}
+
+fn get_latest_custom_view_entry(
+    original_custom_view_hash: ActionHash,
+) -> ExternResult<Option<CustomView>> {
+    let Some(record) = get_latest_custom_view(original_custom_view_hash)? else {
+        return Ok(None);
+    };
+    record.entry().to_app_option().map_err(|e| wasm_error!(e))
+}
@@ -1,5 +1,7 @@
 use hdk::prelude::*;
 use custom_views_integrity::*;
+
+use crate::applet_dependencies::{link_applet_dependencies, unlink_applet_dependencies};
 #[hdk_extern]
 pub fn create_custom_view(custom_view: CustomView) -> ExternResult<Record> {
     let custom_view_hash = create_entry(&EntryTypes::CustomView(custom_view.clone()))?;
@@ -9,13 +11,18 @@ pub fn create_custom_view(custom_view: CustomView) -> ExternResult<Record> {
                 WasmErrorInner::Guest(String::from("Could not find the newly created CustomView"))
             ),
         )?;
-    let path = Path::from("all_custom_views");
+    let path = all_custom_views_path();
     create_link(
         path.path_entry_hash()?,
         custom_view_hash.clone(),
         LinkTypes::AllCustomViews,
         (),
     )?;
+    link_applet_dependencies(
+        custom_view_hash.clone(),
+        custom_view_hash,
+        custom_view.applet_dependencies,
+    )?;
     Ok(record)
 }
 #[hdk_extern]
@@ -24,7 +31,7 @@ pub fn get_custom_view(
 ) -> ExternResult<Option<Record>> {
     get_latest_custom_view(original_custom_view_hash)
 }
-fn get_latest_custom_view(custom_view_hash: ActionHash) -> ExternResult<Option<Record>> {
+pub(crate) fn get_latest_custom_view(custom_view_hash: ActionHash) -> ExternResult<Option<Record>> {
     let details = get_details(custom_view_hash, GetOptions::default())?
         .ok_or(wasm_error!(WasmErrorInner::Guest("CustomView not found".into())))?;
     let record_details = match details {
@@ -48,10 +55,49 @@ pub struct UpdateCustomViewInput {
 }
 #[hdk_extern]
 pub fn update_custom_view(input: UpdateCustomViewInput) -> ExternResult<Record> {
+    let previous_record = get(input.previous_custom_view_hash.clone(), GetOptions::default())?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Could not find the previous CustomView"))
+            ),
+        )?;
+    let previous_custom_view: CustomView = previous_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("The previous record is not a CustomView"))
+            ),
+        )?;
+    let original_custom_view_hash = original_custom_view_hash(
+        input.previous_custom_view_hash.clone(),
+    )?;
     let updated_custom_view_hash = update_entry(
         input.previous_custom_view_hash,
         &input.updated_custom_view,
     )?;
+    let removed_dependencies: Vec<EntryHash> = previous_custom_view
+        .applet_dependencies
+        .iter()
+        .filter(|applet_hash| {
+            !input.updated_custom_view.applet_dependencies.contains(applet_hash)
+        })
+        .cloned()
+        .collect();
+    unlink_applet_dependencies(original_custom_view_hash.clone(), removed_dependencies)?;
+    let added_dependencies: Vec<EntryHash> = input
+        .updated_custom_view
+        .applet_dependencies
+        .iter()
+        .filter(|applet_hash| !previous_custom_view.applet_dependencies.contains(applet_hash))
+        .cloned()
+        .collect();
+    link_applet_dependencies(
+        original_custom_view_hash,
+        updated_custom_view_hash.clone(),
+        added_dependencies,
+    )?;
     let record = get(updated_custom_view_hash.clone(), GetOptions::default())?
         .ok_or(
             wasm_error!(
@@ -64,5 +110,17 @@ pub fn update_custom_view(input: UpdateCustomViewInput) -> ExternResult<Record>
 pub fn delete_custom_view(
     original_custom_view_hash: ActionHash,
 ) -> ExternResult<ActionHash> {
+    if let Some(record) = get_latest_custom_view(original_custom_view_hash.clone())? {
+        let custom_view: Option<CustomView> = record
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?;
+        if let Some(custom_view) = custom_view {
+            unlink_applet_dependencies(
+                original_custom_view_hash.clone(),
+                custom_view.applet_dependencies,
+            )?;
+        }
+    }
     delete_entry(original_custom_view_hash)
 }
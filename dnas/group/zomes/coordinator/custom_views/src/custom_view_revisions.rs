@@ -0,0 +1,106 @@
+use std::collections::{HashSet, VecDeque};
+
+use custom_views_integrity::*;
+use hdk::prelude::*;
+
+use crate::custom_view::{get_latest_custom_view, update_custom_view, UpdateCustomViewInput};
+
+/// One version of a CustomView, either its original create or one of its updates
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomViewRevision {
+    pub revision_hash: ActionHash,
+    pub custom_view: CustomView,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RevertCustomViewInput {
+    pub original_custom_view_hash: ActionHash,
+    pub revision_hash: ActionHash,
+}
+
+/// Gets all revisions of a CustomView sorted by their timestamp, the original first.
+/// Includes concurrent updates of the same revision, so the last revision is not
+/// necessarily the one that `get_custom_view` returns.
+#[hdk_extern]
+pub fn get_custom_view_revisions(
+    original_custom_view_hash: ActionHash,
+) -> ExternResult<Vec<CustomViewRevision>> {
+    let mut revisions: Vec<CustomViewRevision> = Vec::new();
+    let mut visited: HashSet<ActionHash> = HashSet::new();
+    let mut queue: VecDeque<ActionHash> = VecDeque::from([original_custom_view_hash]);
+    while let Some(revision_hash) = queue.pop_front() {
+        if !visited.insert(revision_hash.clone()) {
+            continue;
+        }
+        let Some(Details::Record(record_details)) =
+            get_details(revision_hash, GetOptions::default())?
+        else {
+            continue;
+        };
+        let record = record_details.record;
+        let Some(custom_view) = record
+            .entry()
+            .to_app_option::<CustomView>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+                "The given record is not a CustomView"
+            ))));
+        };
+        revisions.push(CustomViewRevision {
+            revision_hash: record.action_address().clone(),
+            custom_view,
+            author: record.action().author().clone(),
+            timestamp: record.action().timestamp(),
+        });
+        queue.extend(
+            record_details
+                .updates
+                .into_iter()
+                .map(|update| update.action_address().clone()),
+        );
+    }
+    revisions.sort_by(|revision_a, revision_b| {
+        revision_a
+            .timestamp
+            .cmp(&revision_b.timestamp)
+            .then_with(|| revision_a.revision_hash.cmp(&revision_b.revision_hash))
+    });
+    Ok(revisions)
+}
+
+/// Restores an older revision of a CustomView by creating a new update with its
+/// contents on top of the latest one, so that no revision is lost. Like any update,
+/// only the author of the CustomView can do this.
+#[hdk_extern]
+pub fn revert_custom_view(input: RevertCustomViewInput) -> ExternResult<Record> {
+    if original_custom_view_hash(input.revision_hash.clone())?
+        .ne(&input.original_custom_view_hash)
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given revision is not a revision of the given CustomView"
+        ))));
+    }
+    let revision_record = get(input.revision_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the CustomView revision"))
+    ))?;
+    let custom_view: CustomView = revision_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given revision is not a CustomView"
+        ))))?;
+    let latest_record = get_latest_custom_view(input.original_custom_view_hash)?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from(
+            "Deleted CustomViews cannot be reverted"
+        ))),
+    )?;
+
+    update_custom_view(UpdateCustomViewInput {
+        previous_custom_view_hash: latest_record.action_address().clone(),
+        updated_custom_view: custom_view,
+    })
+}
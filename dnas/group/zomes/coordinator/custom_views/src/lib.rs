@@ -1,5 +1,7 @@
 pub mod all_custom_views;
+pub mod applet_dependencies;
 pub mod custom_view;
+pub mod custom_view_revisions;
 use hdk::prelude::*;
 use custom_views_integrity::*;
 #[hdk_extern]
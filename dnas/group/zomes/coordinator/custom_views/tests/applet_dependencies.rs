@@ -0,0 +1,152 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+use custom_views_integrity::*;
+
+use custom_views::applet_dependencies::{BrokenCustomView, FlagCustomViewsBrokenInput};
+use custom_views::custom_view::UpdateCustomViewInput;
+
+mod common;
+use common::{create_custom_view, sample_applet, sample_custom_view_1, sample_custom_view_2};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn custom_views_are_found_by_their_applet_dependencies() {
+    // Use prebuilt dna file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("custom_views");
+    let bob_zome = bobbo.zome("custom_views");
+
+    let applet_1 = EntryHash::from_raw_36(vec![1; 36]);
+    let applet_2 = EntryHash::from_raw_36(vec![2; 36]);
+
+    let mut sample = sample_custom_view_1(&conductors[0], &alice_zome).await;
+    sample.applet_dependencies = vec![applet_1.clone()];
+
+    // Alice creates a CustomView that depends on the first Applet
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample.clone()).await;
+    let original_action_hash = record.signed_action.hashed.hash.clone();
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let custom_views: Vec<Record> = conductors[1]
+        .call(&bob_zome, "get_custom_views_for_applet", applet_1.clone())
+        .await;
+    assert_eq!(custom_views, vec![record]);
+
+    // Alice moves the dependency to the second Applet
+    let mut sample_2 = sample_custom_view_2(&conductors[0], &alice_zome).await;
+    sample_2.applet_dependencies = vec![applet_2.clone()];
+    let update_record: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_custom_view",
+            UpdateCustomViewInput {
+                previous_custom_view_hash: original_action_hash.clone(),
+                updated_custom_view: sample_2,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let custom_views: Vec<Record> = conductors[1]
+        .call(&bob_zome, "get_custom_views_for_applet", applet_1)
+        .await;
+    assert!(custom_views.is_empty());
+    let custom_views: Vec<Record> = conductors[1]
+        .call(&bob_zome, "get_custom_views_for_applet", applet_2)
+        .await;
+    assert_eq!(custom_views, vec![update_record]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn archiving_an_applet_flags_dependent_custom_views_as_broken() {
+    // Use prebuilt dna file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("custom_views");
+    let bob_zome = bobbo.zome("custom_views");
+
+    let applet_hash: EntryHash = conductors[1]
+        .call(&bobbo.zome("group"), "register_applet", sample_applet("applet"))
+        .await;
+
+    let mut sample = sample_custom_view_1(&conductors[0], &alice_zome).await;
+    sample.applet_dependencies = vec![applet_hash.clone()];
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample).await;
+    let independent_record: Record = create_custom_view(
+        &conductors[0],
+        &alice_zome,
+        sample_custom_view_2(&conductors[0], &alice_zome).await,
+    )
+    .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Views can only be flagged with the archiving of the Applet
+    let result: Result<(), _> = conductors[1]
+        .call_fallible(
+            &bob_zome,
+            "flag_custom_views_broken",
+            FlagCustomViewsBrokenInput {
+                applet_hash: applet_hash.clone(),
+                archive_hash: record.signed_action.hashed.hash.clone(),
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    let _: () = conductors[1]
+        .call(&bobbo.zome("group"), "archive_applet", applet_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let broken_custom_views: Vec<BrokenCustomView> = conductors[0]
+        .call(&alice_zome, "get_broken_custom_views", ())
+        .await;
+    assert_eq!(broken_custom_views.len(), 1);
+    assert_eq!(
+        broken_custom_views[0].custom_view_hash,
+        record.signed_action.hashed.hash
+    );
+    assert_eq!(broken_custom_views[0].archived_applets, vec![applet_hash.clone()]);
+
+    // Unarchiving the Applet removes the flag again
+    let _: () = conductors[1]
+        .call(&bobbo.zome("group"), "unarchive_applet", applet_hash)
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let broken_custom_views: Vec<BrokenCustomView> = conductors[0]
+        .call(&alice_zome, "get_broken_custom_views", ())
+        .await;
+    assert!(broken_custom_views.is_empty());
+}
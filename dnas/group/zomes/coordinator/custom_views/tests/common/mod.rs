@@ -1,7 +1,46 @@
+use ::fixt::prelude::fixt;
+use std::collections::BTreeMap;
+
+use hdk::prelude::holo_hash::*;
 use hdk::prelude::*;
-use holochain::sweettest::*;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
 
 use custom_views_integrity::*;
+use we_types::Applet;
+
+/// Installs the group DNA for two agents on separate conductors that know each other
+pub async fn setup_two_agents() -> (SweetConductorBatch, SweetCell, SweetCell) {
+    // Use prebuilt dna file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+    (conductors, alice, bobbo)
+}
+
+/// An Applet to register with the group zome
+pub fn sample_applet(custom_name: &str) -> Applet {
+    Applet {
+        custom_name: String::from(custom_name),
+        description: String::from("description"),
+        appstore_app_hash: fixt!(ActionHash),
+
+        devhub_dna_hash: fixt!(DnaHash),
+        devhub_happ_entry_action_hash: fixt!(ActionHash),
+        devhub_happ_release_hash: fixt!(ActionHash),
+        initial_devhub_gui_release_hash: Some(fixt!(ActionHash)),
+
+        network_seed: None,
+        properties: BTreeMap::new(),
+    }
+}
 
 
 
@@ -12,6 +51,8 @@ pub async fn sample_custom_view_1(conductor: &SweetConductor, zome: &SweetZome)
 	  html: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".to_string(),
 	  js: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".to_string(),
 	  css: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".to_string(),
+      logo_blob_hash: None,
+      applet_dependencies: vec![],
     }
 }
 
@@ -22,6 +63,8 @@ pub async fn sample_custom_view_2(conductor: &SweetConductor, zome: &SweetZome)
 	  html: "Lorem ipsum 2".to_string(),
 	  js: "Lorem ipsum 2".to_string(),
 	  css: "Lorem ipsum 2".to_string(),
+      logo_blob_hash: None,
+      applet_dependencies: vec![],
     }
 }
 
@@ -0,0 +1,154 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+use custom_views_integrity::*;
+
+use custom_views::custom_view::UpdateCustomViewInput;
+use custom_views::custom_view_revisions::{CustomViewRevision, RevertCustomViewInput};
+
+mod common;
+use common::{create_custom_view, sample_custom_view_1, sample_custom_view_2};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn revisions_of_a_custom_view_can_be_listed_and_reverted() {
+    // Use prebuilt dna file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("custom_views");
+    let bob_zome = bobbo.zome("custom_views");
+
+    let sample_1 = sample_custom_view_1(&conductors[0], &alice_zome).await;
+    let sample_2 = sample_custom_view_2(&conductors[0], &alice_zome).await;
+
+    // Alice creates a CustomView and updates it
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample_1.clone()).await;
+    let original_action_hash = record.signed_action.hashed.hash.clone();
+    let update_record: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_custom_view",
+            UpdateCustomViewInput {
+                previous_custom_view_hash: original_action_hash.clone(),
+                updated_custom_view: sample_2.clone(),
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let revisions: Vec<CustomViewRevision> = conductors[1]
+        .call(&bob_zome, "get_custom_view_revisions", original_action_hash.clone())
+        .await;
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].revision_hash, original_action_hash);
+    assert_eq!(revisions[0].custom_view, sample_1);
+    assert_eq!(revisions[0].author, alice.agent_pubkey().clone());
+    assert_eq!(revisions[1].revision_hash, update_record.signed_action.hashed.hash);
+    assert_eq!(revisions[1].custom_view, sample_2);
+
+    // Alice reverts to the original revision
+    let revert_record: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "revert_custom_view",
+            RevertCustomViewInput {
+                original_custom_view_hash: original_action_hash.clone(),
+                revision_hash: original_action_hash.clone(),
+            },
+        )
+        .await;
+    let entry: CustomView = revert_record.entry().to_app_option().unwrap().unwrap();
+    assert_eq!(entry, sample_1);
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let revisions: Vec<CustomViewRevision> = conductors[1]
+        .call(&bob_zome, "get_custom_view_revisions", original_action_hash.clone())
+        .await;
+    assert_eq!(revisions.len(), 3);
+    assert_eq!(revisions[2].revision_hash, revert_record.signed_action.hashed.hash);
+
+    let latest: Option<Record> = conductors[1]
+        .call(&bob_zome, "get_custom_view", original_action_hash)
+        .await;
+    assert_eq!(latest.unwrap(), revert_record);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_updates_of_a_revision_are_all_listed() {
+    // Use prebuilt dna file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("custom_views");
+    let bob_zome = bobbo.zome("custom_views");
+
+    let sample_1 = sample_custom_view_1(&conductors[0], &alice_zome).await;
+    let sample_2 = sample_custom_view_2(&conductors[0], &alice_zome).await;
+
+    // Alice updates the original revision twice, branching its update chain
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample_1.clone()).await;
+    let original_action_hash = record.signed_action.hashed.hash.clone();
+    let first_update: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_custom_view",
+            UpdateCustomViewInput {
+                previous_custom_view_hash: original_action_hash.clone(),
+                updated_custom_view: sample_2.clone(),
+            },
+        )
+        .await;
+    let second_update: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_custom_view",
+            UpdateCustomViewInput {
+                previous_custom_view_hash: original_action_hash.clone(),
+                updated_custom_view: sample_1.clone(),
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let revisions: Vec<CustomViewRevision> = conductors[1]
+        .call(&bob_zome, "get_custom_view_revisions", original_action_hash.clone())
+        .await;
+    let revision_hashes: Vec<ActionHash> = revisions
+        .into_iter()
+        .map(|revision| revision.revision_hash)
+        .collect();
+    assert_eq!(
+        revision_hashes,
+        vec![
+            original_action_hash,
+            first_update.signed_action.hashed.hash,
+            second_update.signed_action.hashed.hash,
+        ]
+    );
+}
@@ -0,0 +1,197 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::prelude::SignedActionHashedExt;
+use holochain::sweettest::*;
+
+use custom_views_integrity::*;
+
+use custom_views::custom_view::UpdateCustomViewInput;
+use custom_views::custom_view_revisions::CustomViewRevision;
+
+mod common;
+use common::{create_custom_view, sample_custom_view_1, sample_custom_view_2, setup_two_agents};
+
+
+#[tokio::test(flavor = "multi_thread")]
+async fn only_author_can_update_custom_view() {
+    let (conductors, alice, bobbo) = setup_two_agents().await;
+
+    let alice_zome = alice.zome("custom_views");
+    let bob_zome = bobbo.zome("custom_views");
+
+    let sample_1 = sample_custom_view_1(&conductors[0], &alice_zome).await;
+
+    // Alice creates a CustomView
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample_1.clone()).await;
+    let original_action_hash = record.signed_action.hashed.hash.clone();
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let sample_2 = sample_custom_view_2(&conductors[1], &bob_zome).await;
+    let input = UpdateCustomViewInput {
+      previous_custom_view_hash: original_action_hash.clone(),
+      updated_custom_view: sample_2.clone(),
+    };
+
+    // Bob tries to update Alice's CustomView
+    let result: Result<Record, _> = conductors[1]
+        .call_fallible(&bob_zome, "update_custom_view", input)
+        .await;
+    assert!(result.is_err());
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let get_record: Option<Record> = conductors[0]
+        .call(&alice_zome, "get_custom_view", original_action_hash.clone())
+        .await;
+
+    assert_eq!(record, get_record.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn only_author_can_delete_custom_view() {
+    let (conductors, alice, bobbo) = setup_two_agents().await;
+
+    let alice_zome = alice.zome("custom_views");
+    let bob_zome = bobbo.zome("custom_views");
+
+    let sample_1 = sample_custom_view_1(&conductors[0], &alice_zome).await;
+
+    // Alice creates a CustomView
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample_1.clone()).await;
+    let original_action_hash = record.signed_action.hashed.hash.clone();
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Bob tries to delete Alice's CustomView
+    let result: Result<ActionHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "delete_custom_view", original_action_hash.clone())
+        .await;
+    assert!(result.is_err());
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let get_record: Option<Record> = conductors[0]
+        .call(&alice_zome, "get_custom_view", original_action_hash.clone())
+        .await;
+
+    assert_eq!(record, get_record.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn oversized_custom_view_is_rejected() {
+    let (conductors, alice, bobbo) = setup_two_agents().await;
+
+    let alice_zome = alice.zome("custom_views");
+    let bob_zome = bobbo.zome("custom_views");
+
+    let mut oversized = sample_custom_view_1(&conductors[0], &alice_zome).await;
+    oversized.html = "a".repeat(MAX_CUSTOM_VIEW_HTML_SIZE + 1);
+
+    let result: Result<Record, _> = conductors[0]
+        .call_fallible(&alice_zome, "create_custom_view", oversized)
+        .await;
+    assert!(result.is_err());
+
+    // An update must not be able to sneak oversized content in either
+    let sample_1 = sample_custom_view_1(&conductors[0], &alice_zome).await;
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample_1.clone()).await;
+
+    let mut oversized = sample_1.clone();
+    oversized.logo = "a".repeat(MAX_CUSTOM_VIEW_LOGO_SIZE + 1);
+    let input = UpdateCustomViewInput {
+      previous_custom_view_hash: record.signed_action.hashed.hash.clone(),
+      updated_custom_view: oversized,
+    };
+    let result: Result<Record, _> = conductors[0]
+        .call_fallible(&alice_zome, "update_custom_view", input)
+        .await;
+    assert!(result.is_err());
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let get_records: Vec<Record> = conductors[1]
+        .call(&bob_zome, "get_all_custom_views", ())
+        .await;
+
+    assert_eq!(get_records.len(), 1);
+    assert_eq!(get_records[0], record);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn peer_rejects_update_by_other_agent() {
+    let (conductors, alice, bobbo) = setup_two_agents().await;
+
+    let alice_zome = alice.zome("custom_views");
+
+    let sample_1 = sample_custom_view_1(&conductors[0], &alice_zome).await;
+
+    // Alice creates a CustomView
+    let record: Record = create_custom_view(&conductors[0], &alice_zome, sample_1.clone()).await;
+    let original_action_hash = record.signed_action.hashed.hash.clone();
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Bob's own conductor refuses to commit an update of Alice's CustomView, so he
+    // grafts it onto his source chain with validation turned off
+    let bob_chain = conductors[1]
+        .raw_handle()
+        .dump_full_cell_state(bobbo.cell_id(), None)
+        .await
+        .unwrap()
+        .source_chain_dump
+        .records;
+    let bob_chain_top = bob_chain.last().unwrap();
+
+    let sample_2 = sample_custom_view_2(&conductors[1], &bobbo.zome("custom_views")).await;
+    let entry = Entry::app(SerializedBytes::try_from(sample_2).unwrap()).unwrap();
+    let update = Update {
+        author: bobbo.agent_pubkey().clone(),
+        timestamp: Timestamp::now(),
+        action_seq: bob_chain_top.action.action_seq() + 1,
+        prev_action: bob_chain_top.action_address.clone(),
+        original_action_address: original_action_hash.clone(),
+        original_entry_address: record.action().entry_hash().unwrap().clone(),
+        entry_type: record.action().entry_type().unwrap().clone(),
+        entry_hash: EntryHash::with_data_sync(&entry),
+        weight: Default::default(),
+    };
+    let signed_action = SignedActionHashed::sign(
+        &conductors[1].keystore(),
+        ActionHashed::from_content_sync(Action::Update(update)),
+    )
+    .await
+    .unwrap();
+    conductors[1]
+        .raw_handle()
+        .graft_records_onto_source_chain(
+            bobbo.cell_id().clone(),
+            false,
+            vec![Record::new(signed_action, Some(entry))],
+        )
+        .await
+        .unwrap();
+
+    // Committing anything triggers the publishing of Bob's chain to Alice
+    let sample_1 = sample_custom_view_1(&conductors[1], &bobbo.zome("custom_views")).await;
+    create_custom_view(&conductors[1], &bobbo.zome("custom_views"), sample_1).await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Alice validated the update as one of its authorities and rejected it
+    let revision_hashes: Vec<ActionHash> = conductors[0]
+        .call::<_, Vec<CustomViewRevision>>(
+            &alice_zome,
+            "get_custom_view_revisions",
+            original_action_hash.clone(),
+        )
+        .await
+        .into_iter()
+        .map(|revision| revision.revision_hash)
+        .collect();
+    assert_eq!(revision_hashes, vec![original_action_hash]);
+}
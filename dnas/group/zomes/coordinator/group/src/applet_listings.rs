@@ -0,0 +1,241 @@
+use std::collections::{HashMap, HashSet};
+
+use group_integrity::*;
+use hdk::prelude::*;
+
+use crate::applets::get_my_applets;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum AppletSortKey {
+    #[default]
+    RegisteredAt,
+    Name,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ListAppletsInput {
+    #[serde(default)]
+    pub sort_by: AppletSortKey,
+    #[serde(default)]
+    pub descending: bool,
+    #[serde(default)]
+    pub offset: usize,
+    /// Returns all Applets from the offset onwards if None
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// An Applet of the group together with who registered it in the group and when
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppletListing {
+    pub applet_hash: EntryHash,
+    pub record: Record,
+    pub applet: Applet,
+    pub registered_by: AgentPubKey,
+    pub registered_at: Timestamp,
+    pub archived: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppletListingPage {
+    pub applets: Vec<AppletListing>,
+    /// The number of Applets across all pages
+    pub total: usize,
+}
+
+/// What the AnchorToApplet links tell about an Applet, before its entry is fetched
+#[derive(Debug, Clone)]
+struct AppletRegistration {
+    applet_hash: EntryHash,
+    registered_by: AgentPubKey,
+    registered_at: Timestamp,
+    archived: bool,
+}
+
+/// Gets the registrations of all Applets that have ever been linked from the group_applets
+/// anchor. The earliest link of an Applet counts as its registration, and an Applet is
+/// archived if all of its links have been deleted.
+fn get_applet_registrations() -> ExternResult<Vec<AppletRegistration>> {
+    let path = group_applets_path();
+    let links_details = get_link_details(path.path_entry_hash()?, LinkTypes::AnchorToApplet, None)?;
+
+    let mut registrations: HashMap<EntryHash, AppletRegistration> = HashMap::new();
+    for (create_link, deletes) in links_details.into_inner() {
+        let Action::CreateLink(create_link) = create_link.action() else {
+            continue;
+        };
+        let Some(applet_hash) = create_link.target_address.clone().into_entry_hash() else {
+            continue;
+        };
+        let registration = registrations
+            .entry(applet_hash.clone())
+            .or_insert(AppletRegistration {
+                applet_hash,
+                registered_by: create_link.author.clone(),
+                registered_at: create_link.timestamp,
+                archived: true,
+            });
+        if create_link.timestamp < registration.registered_at {
+            registration.registered_by = create_link.author.clone();
+            registration.registered_at = create_link.timestamp;
+        }
+        if deletes.is_empty() {
+            registration.archived = false;
+        }
+    }
+
+    Ok(registrations.into_values().collect())
+}
+
+/// Fetches the Applet records in one batch get. Applets that can't be fetched, e.g.
+/// because we haven't synced up yet, are skipped.
+fn get_applet_listings(registrations: Vec<AppletRegistration>) -> ExternResult<Vec<AppletListing>> {
+    let get_input: Vec<GetInput> = registrations
+        .iter()
+        .map(|registration| {
+            GetInput::new(
+                registration.applet_hash.clone().into(),
+                GetOptions::default(),
+            )
+        })
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+
+    let mut listings: Vec<AppletListing> = Vec::new();
+    for (registration, maybe_record) in registrations.into_iter().zip(records) {
+        let Some(record) = maybe_record else {
+            continue;
+        };
+        let Some(applet) = record
+            .entry()
+            .to_app_option::<Applet>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        listings.push(AppletListing {
+            applet_hash: registration.applet_hash,
+            record,
+            applet,
+            registered_by: registration.registered_by,
+            registered_at: registration.registered_at,
+            archived: registration.archived,
+        });
+    }
+    Ok(listings)
+}
+
+fn compare_registrations(a: &AppletRegistration, b: &AppletRegistration) -> std::cmp::Ordering {
+    a.registered_at
+        .cmp(&b.registered_at)
+        .then_with(|| a.applet_hash.cmp(&b.applet_hash))
+}
+
+fn page_bounds(input: &ListAppletsInput, total: usize) -> (usize, usize) {
+    let start = input.offset.min(total);
+    let end = match input.limit {
+        Some(limit) => start.saturating_add(limit).min(total),
+        None => total,
+    };
+    (start, end)
+}
+
+/// Sorts the registrations and fetches the Applets of the requested page. When sorting
+/// by registration time only the Applets of the page are fetched, sorting by name needs
+/// all of them.
+fn list_applets(
+    mut registrations: Vec<AppletRegistration>,
+    input: ListAppletsInput,
+) -> ExternResult<AppletListingPage> {
+    let total = registrations.len();
+    let (start, end) = page_bounds(&input, total);
+
+    let applets = match input.sort_by {
+        AppletSortKey::RegisteredAt => {
+            registrations.sort_by(compare_registrations);
+            if input.descending {
+                registrations.reverse();
+            }
+            get_applet_listings(registrations.drain(start..end).collect())?
+        }
+        AppletSortKey::Name => {
+            let mut listings = get_applet_listings(registrations)?;
+            listings.sort_by(|a, b| {
+                a.applet
+                    .custom_name
+                    .to_lowercase()
+                    .cmp(&b.applet.custom_name.to_lowercase())
+                    .then_with(|| a.applet_hash.cmp(&b.applet_hash))
+            });
+            if input.descending {
+                listings.reverse();
+            }
+            let (start, end) = page_bounds(&input, listings.len());
+            listings.drain(start..end).collect()
+        }
+    };
+
+    Ok(AppletListingPage { applets, total })
+}
+
+/// All Applets of the group that are not archived, the earliest registered first
+pub(crate) fn get_active_applet_listings() -> ExternResult<Vec<AppletListing>> {
+    let mut registrations: Vec<AppletRegistration> = get_applet_registrations()?
+        .into_iter()
+        .filter(|registration| !registration.archived)
+        .collect();
+    registrations.sort_by(compare_registrations);
+    get_applet_listings(registrations)
+}
+
+/// Like `get_group_applets`, but with the Applet entries and their registration
+#[hdk_extern]
+pub fn list_group_applets(input: ListAppletsInput) -> ExternResult<AppletListingPage> {
+    let registrations = get_applet_registrations()?
+        .into_iter()
+        .filter(|registration| !registration.archived)
+        .collect();
+    list_applets(registrations, input)
+}
+
+/// Like `get_unjoined_applets`, but with the Applet entries and their registration
+#[hdk_extern]
+pub fn list_unjoined_applets(input: ListAppletsInput) -> ExternResult<AppletListingPage> {
+    let my_applets: HashSet<EntryHash> = get_my_applets(())?.into_iter().collect();
+    let registrations = get_applet_registrations()?
+        .into_iter()
+        .filter(|registration| {
+            !registration.archived && !my_applets.contains(&registration.applet_hash)
+        })
+        .collect();
+    list_applets(registrations, input)
+}
+
+/// Like `get_archived_applets`, but with the Applet entries and their registration
+#[hdk_extern]
+pub fn list_archived_applets(input: ListAppletsInput) -> ExternResult<AppletListingPage> {
+    let registrations = get_applet_registrations()?
+        .into_iter()
+        .filter(|registration| registration.archived)
+        .collect();
+    list_applets(registrations, input)
+}
+
+/// Like `get_federated_applets`, but with the Applet entries and their registration
+#[hdk_extern]
+pub fn list_federated_applets(input: ListAppletsInput) -> ExternResult<AppletListingPage> {
+    let federated_applets: HashSet<EntryHash> = get_links(
+        federated_applets_path().path_entry_hash()?,
+        LinkTypes::AnchorToFederatedApplet,
+        None,
+    )?
+    .into_iter()
+    .filter_map(|link| link.target.into_entry_hash())
+    .collect();
+
+    let registrations = get_applet_registrations()?
+        .into_iter()
+        .filter(|registration| federated_applets.contains(&registration.applet_hash))
+        .collect();
+    list_applets(registrations, input)
+}
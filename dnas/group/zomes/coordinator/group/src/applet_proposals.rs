@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use group_integrity::*;
+use hdk::prelude::*;
+
+use crate::group_settings::{
+    get_group_settings, group_settings_reference, latest_group_settings_hash,
+};
+use crate::stewards::{agent_chain_top, optional_steward_permission};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AppletProposalStatus {
+    /// Members can still vote on the proposal, or it has reached its quorum but nobody
+    /// has accepted it yet
+    Open,
+    Accepted,
+    /// The voting period ended without the proposal reaching its quorum
+    Rejected,
+}
+
+/// An AppletProposal together with the current tally of its votes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppletProposalWithTally {
+    pub proposal_hash: ActionHash,
+    pub proposal: AppletProposal,
+    pub applet: Applet,
+    pub proposed_by: AgentPubKey,
+    pub proposed_at: Timestamp,
+    pub voting_closes_at: Timestamp,
+    pub quorum: u32,
+    pub approvals: u32,
+    pub rejections: u32,
+    /// Our own latest vote, if we have voted
+    pub my_vote: Option<bool>,
+    pub status: AppletProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VoteOnAppletProposalInput {
+    pub proposal_hash: ActionHash,
+    pub approve: bool,
+}
+
+/// Proposes an Applet to the group. Only possible if the GroupSettings require Applets
+/// to be voted on, otherwise use `register_applet`.
+#[hdk_extern]
+pub fn propose_applet(applet: Applet) -> ExternResult<ActionHash> {
+    let group_settings_hash = latest_group_settings_hash()?;
+    if get_group_settings(())?.applet_proposal_rules.is_none() {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Applet proposals are not enabled in this group, register the Applet instead."
+        ))));
+    }
+
+    let applet_hash = hash_entry(&applet)?;
+    if get(applet_hash.clone(), GetOptions::default())?.is_none() {
+        create_entry(EntryTypes::Applet(applet))?;
+    }
+
+    let proposal_hash = create_entry(EntryTypes::AppletProposal(AppletProposal {
+        applet_hash,
+        group_settings_hash,
+    }))?;
+    create_link(
+        applet_proposals_path().path_entry_hash()?,
+        proposal_hash.clone(),
+        LinkTypes::AnchorToAppletProposal,
+        (),
+    )?;
+
+    Ok(proposal_hash)
+}
+
+/// Votes on an AppletProposal. Voting again replaces our previous vote.
+#[hdk_extern]
+pub fn vote_on_applet_proposal(input: VoteOnAppletProposalInput) -> ExternResult<ActionHash> {
+    let vote_hash = create_entry(EntryTypes::AppletVote(AppletVote {
+        proposal_hash: input.proposal_hash.clone(),
+        approve: input.approve,
+    }))?;
+    create_link(
+        input.proposal_hash,
+        vote_hash.clone(),
+        LinkTypes::AppletProposalToVote,
+        (),
+    )?;
+    Ok(vote_hash)
+}
+
+/// Gets the proposals that can still be voted on or accepted, the oldest first
+#[hdk_extern]
+pub fn get_open_applet_proposals(_: ()) -> ExternResult<Vec<AppletProposalWithTally>> {
+    let mut links = get_links(
+        applet_proposals_path().path_entry_hash()?,
+        LinkTypes::AnchorToAppletProposal,
+        None,
+    )?;
+    links.sort_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+
+    let mut proposals: Vec<AppletProposalWithTally> = Vec::new();
+    for link in links {
+        let Some(proposal_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        if let Some(proposal) = get_applet_proposal(proposal_hash)? {
+            if proposal.status == AppletProposalStatus::Open {
+                proposals.push(proposal);
+            }
+        }
+    }
+    Ok(proposals)
+}
+
+/// Gets an AppletProposal with its current tally. Returns None if we haven't synced
+/// the proposal, its Applet or its GroupSettings yet.
+#[hdk_extern]
+pub fn get_applet_proposal(
+    proposal_hash: ActionHash,
+) -> ExternResult<Option<AppletProposalWithTally>> {
+    let Some(record) = get(proposal_hash.clone(), GetOptions::default())? else {
+        return Ok(None);
+    };
+    let Some(proposal) = record
+        .entry()
+        .to_app_option::<AppletProposal>()
+        .map_err(|e| wasm_error!(e))?
+    else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given hash is not an AppletProposal."
+        ))));
+    };
+    let Some(applet) = get_applet_entry(proposal.applet_hash.clone())? else {
+        return Ok(None);
+    };
+    let Some(rules) = get_applet_proposal_rules(proposal.group_settings_hash.clone())? else {
+        return Ok(None);
+    };
+
+    let proposed_at = record.action().timestamp();
+    let voting_closes_at = voting_closes_at(proposed_at, &rules);
+    let votes = get_latest_votes(proposal_hash.clone())?;
+    let approvals = votes.values().filter(|(_, vote)| vote.approve).count() as u32;
+    let rejections = votes.len() as u32 - approvals;
+    let my_vote = votes
+        .get(&agent_info()?.agent_initial_pubkey)
+        .map(|(_, vote)| vote.approve);
+
+    let accepted =
+        !get_links(proposal_hash.clone(), LinkTypes::AppletProposalToAcceptance, None)?
+            .is_empty();
+    let status = if accepted {
+        AppletProposalStatus::Accepted
+    } else if sys_time()? <= voting_closes_at || approvals >= rules.quorum {
+        AppletProposalStatus::Open
+    } else {
+        AppletProposalStatus::Rejected
+    };
+
+    Ok(Some(AppletProposalWithTally {
+        proposal_hash,
+        proposal,
+        applet,
+        proposed_by: record.action().author().clone(),
+        proposed_at,
+        voting_closes_at,
+        quorum: rules.quorum,
+        approvals,
+        rejections,
+        my_vote,
+        status,
+    }))
+}
+
+/// Accepts an AppletProposal that has reached its quorum once its voting period has
+/// closed, and adds its Applet to the group. Whoever accepts it must be allowed to
+/// register Applets in the group. The votes of other agents only count once they have
+/// been active after the voting period closed, see `ApprovingVote`.
+#[hdk_extern]
+pub fn accept_applet_proposal(proposal_hash: ActionHash) -> ExternResult<EntryHash> {
+    let proposal = get_applet_proposal(proposal_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("AppletProposal not found."))
+    ))?;
+    if proposal.status == AppletProposalStatus::Accepted {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The AppletProposal has already been accepted."
+        ))));
+    }
+    if sys_time()? <= proposal.voting_closes_at {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The voting period of the AppletProposal has not closed yet."
+        ))));
+    }
+    if proposal.approvals < proposal.quorum {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "The AppletProposal needs {} approving votes to be accepted but only has {}.",
+            proposal.quorum, proposal.approvals
+        ))));
+    }
+
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let mut approving_votes: Vec<ApprovingVote> = Vec::new();
+    for (voter, (vote_hash, vote)) in get_latest_votes(proposal_hash.clone())? {
+        if !vote.approve {
+            continue;
+        }
+        if voter.eq(&my_pub_key) {
+            approving_votes.push(ApprovingVote {
+                vote_hash,
+                voter_chain_top: None,
+            });
+        } else if let Some(voter_chain_top) =
+            chain_top_after(voter, proposal.voting_closes_at)?
+        {
+            approving_votes.push(ApprovingVote {
+                vote_hash,
+                voter_chain_top: Some(voter_chain_top),
+            });
+        }
+    }
+    if (approving_votes.len() as u32) < proposal.quorum {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Only {} of the approving voters have been active since the voting period closed but {} are needed, try again later.",
+            approving_votes.len(),
+            proposal.quorum
+        ))));
+    }
+    let acceptance_hash = create_entry(EntryTypes::AppletProposalAcceptance(
+        AppletProposalAcceptance {
+            proposal_hash: proposal_hash.clone(),
+            approving_votes,
+        },
+    ))?;
+    create_link(
+        proposal_hash,
+        acceptance_hash.clone(),
+        LinkTypes::AppletProposalToAcceptance,
+        (),
+    )?;
+
+    let applet_hash = proposal.proposal.applet_hash;
+    let (group_settings_hash, progenitor_chain_top) = group_settings_reference()?;
+    let permission = optional_steward_permission();
+    create_link(
+        group_applets_path().path_entry_hash()?,
+        applet_hash.clone(),
+        LinkTypes::AnchorToApplet,
+        AnchorToAppletTag {
+            permission_hash: permission.permission_hash,
+            progenitor_chain_top: permission.progenitor_chain_top.or(progenitor_chain_top),
+            valid_until: permission.valid_until,
+            progenitor_signature: permission.progenitor_signature,
+            group_settings_hash,
+            proposal_acceptance_hash: Some(acceptance_hash),
+            archived_link_hash: None,
+        }
+        .to_tag()?,
+    )?;
+
+    Ok(applet_hash)
+}
+
+/// The acceptance of the first accepted proposal for the given Applet, if any. Needed
+/// to link the Applet from the group_applets anchor again after it has been archived.
+pub fn get_applet_proposal_acceptance_hash(
+    applet_hash: EntryHash,
+) -> ExternResult<Option<ActionHash>> {
+    let links = get_links(
+        applet_proposals_path().path_entry_hash()?,
+        LinkTypes::AnchorToAppletProposal,
+        None,
+    )?;
+    for link in links {
+        let Some(proposal_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        let Some(record) = get(proposal_hash.clone(), GetOptions::default())? else {
+            continue;
+        };
+        let Ok(Some(proposal)) = record.entry().to_app_option::<AppletProposal>() else {
+            continue;
+        };
+        if proposal.applet_hash.ne(&applet_hash) {
+            continue;
+        }
+        let acceptance_hash =
+            get_links(proposal_hash, LinkTypes::AppletProposalToAcceptance, None)?
+                .into_iter()
+                .find_map(|link| link.target.into_action_hash());
+        if acceptance_hash.is_some() {
+            return Ok(acceptance_hash);
+        }
+    }
+    Ok(None)
+}
+
+/// The latest action of the given agent, if it was made after the given point in time
+fn chain_top_after(agent: AgentPubKey, after: Timestamp) -> ExternResult<Option<ActionHash>> {
+    let chain_top = agent_chain_top(agent)?;
+    let Some(record) = get(chain_top.clone(), GetOptions::default())? else {
+        return Ok(None);
+    };
+    if record.action().timestamp() <= after {
+        return Ok(None);
+    }
+    Ok(Some(chain_top))
+}
+
+fn get_applet_entry(applet_hash: EntryHash) -> ExternResult<Option<Applet>> {
+    let Some(record) = get(applet_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    record
+        .entry()
+        .to_app_option::<Applet>()
+        .map_err(|e| wasm_error!(e))
+}
+
+/// The AppletProposalRules of the GroupSettings that a proposal was made under
+fn get_applet_proposal_rules(
+    group_settings_hash: Option<ActionHash>,
+) -> ExternResult<Option<AppletProposalRules>> {
+    let Some(group_settings_hash) = group_settings_hash else {
+        return Ok(None);
+    };
+    let Some(record) = get(group_settings_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    Ok(record
+        .entry()
+        .to_app_option::<GroupSettings>()
+        .map_err(|e| wasm_error!(e))?
+        .and_then(|group_settings| group_settings.applet_proposal_rules))
+}
+
+/// Gets the latest vote of each agent that has voted on the given proposal, together
+/// with the action hash of that vote
+fn get_latest_votes(
+    proposal_hash: ActionHash,
+) -> ExternResult<HashMap<AgentPubKey, (ActionHash, AppletVote)>> {
+    let mut links = get_links(proposal_hash, LinkTypes::AppletProposalToVote, None)?;
+    links.sort_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+
+    let get_input: Vec<GetInput> = links
+        .into_iter()
+        .filter_map(|link| link.target.into_action_hash())
+        .map(|vote_hash| GetInput::new(vote_hash.into(), GetOptions::default()))
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+
+    let mut votes: HashMap<AgentPubKey, (ActionHash, AppletVote)> = HashMap::new();
+    for record in records.into_iter().flatten() {
+        if let Ok(Some(vote)) = record.entry().to_app_option::<AppletVote>() {
+            votes.insert(
+                record.action().author().clone(),
+                (record.action_address().clone(), vote),
+            );
+        }
+    }
+    Ok(votes)
+}
@@ -0,0 +1,187 @@
+use std::collections::{BTreeSet, HashSet};
+
+use group_integrity::*;
+use hdk::prelude::*;
+
+use crate::applet_listings::{get_active_applet_listings, AppletListing};
+
+/// The tags and categories that members have given an Applet
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct AppletTags {
+    pub tags: Vec<String>,
+    pub categories: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SetAppletTagsInput {
+    pub applet_hash: EntryHash,
+    pub tags: Vec<String>,
+    pub categories: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SearchAppletsInput {
+    /// Only Applets with this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Only Applets in this category
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Only Applets whose name, description or tags contain this text, ignoring case
+    #[serde(default)]
+    pub query: Option<String>,
+}
+
+/// Sets the tags and categories that we have given an Applet. Tags and categories are
+/// links next to the Applet entry, so the Applet hash stays the same. Tags given by
+/// other members are kept, as links can only be removed by their author.
+#[hdk_extern]
+pub fn set_applet_tags(input: SetAppletTagsInput) -> ExternResult<()> {
+    set_my_applet_labels(
+        input.applet_hash.clone(),
+        input.tags,
+        LinkTypes::AppletToTag,
+        LinkTypes::TagToApplet,
+        applet_tag_path,
+    )?;
+    set_my_applet_labels(
+        input.applet_hash,
+        input.categories,
+        LinkTypes::AppletToCategory,
+        LinkTypes::CategoryToApplet,
+        applet_category_path,
+    )
+}
+
+/// Gets the tags and categories that any member has given the Applet
+#[hdk_extern]
+pub fn get_applet_tags(applet_hash: EntryHash) -> ExternResult<AppletTags> {
+    Ok(AppletTags {
+        tags: get_applet_labels(applet_hash.clone(), LinkTypes::AppletToTag)?,
+        categories: get_applet_labels(applet_hash, LinkTypes::AppletToCategory)?,
+    })
+}
+
+/// Searches the Applets of the group that are not archived, the earliest registered
+/// first. All given filters need to match.
+#[hdk_extern]
+pub fn search_group_applets(input: SearchAppletsInput) -> ExternResult<Vec<AppletListing>> {
+    let mut candidates: Option<HashSet<EntryHash>> = None;
+    if let Some(tag) = input.tag {
+        let path = applet_tag_path(&normalize_applet_tag(&tag));
+        candidates = Some(get_labelled_applets(path, LinkTypes::TagToApplet)?);
+    }
+    if let Some(category) = input.category {
+        let path = applet_category_path(&normalize_applet_tag(&category));
+        let applets = get_labelled_applets(path, LinkTypes::CategoryToApplet)?;
+        candidates = Some(match candidates {
+            Some(candidates) => candidates.intersection(&applets).cloned().collect(),
+            None => applets,
+        });
+    }
+    let query = input
+        .query
+        .map(|query| query.trim().to_lowercase())
+        .filter(|query| !query.is_empty());
+
+    let mut results: Vec<AppletListing> = Vec::new();
+    for listing in get_active_applet_listings()? {
+        if let Some(candidates) = &candidates {
+            if !candidates.contains(&listing.applet_hash) {
+                continue;
+            }
+        }
+        if let Some(query) = &query {
+            let matches_text = listing.applet.custom_name.to_lowercase().contains(query)
+                || listing.applet.description.to_lowercase().contains(query);
+            let matches = matches_text
+                || get_applet_labels(listing.applet_hash.clone(), LinkTypes::AppletToTag)?
+                    .iter()
+                    .any(|tag| tag.contains(query));
+            if !matches {
+                continue;
+            }
+        }
+        results.push(listing);
+    }
+    Ok(results)
+}
+
+/// Links the Applet with the given labels in both directions and removes our links to
+/// labels that are not given anymore
+fn set_my_applet_labels(
+    applet_hash: EntryHash,
+    labels: Vec<String>,
+    applet_to_label: LinkTypes,
+    label_to_applet: LinkTypes,
+    path: fn(&str) -> Path,
+) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let labels: BTreeSet<String> = labels
+        .iter()
+        .map(|label| normalize_applet_tag(label))
+        .filter(|label| !label.is_empty())
+        .collect();
+
+    let my_links: Vec<Link> = get_links(applet_hash.clone(), applet_to_label, None)?
+        .into_iter()
+        .filter(|link| link.author.eq(&my_pub_key))
+        .collect();
+
+    let mut existing_labels: HashSet<String> = HashSet::new();
+    for link in my_links {
+        let Some(label) = applet_tag_from_link_tag(link.tag.clone()) else {
+            continue;
+        };
+        if labels.contains(&label) {
+            existing_labels.insert(label);
+            continue;
+        }
+        delete_link(link.create_link_hash)?;
+        let reverse_links = get_links(path(&label).path_entry_hash()?, label_to_applet, None)?;
+        for reverse_link in reverse_links {
+            if reverse_link.author.eq(&my_pub_key)
+                && reverse_link.target.clone().into_entry_hash().eq(&Some(applet_hash.clone()))
+            {
+                delete_link(reverse_link.create_link_hash)?;
+            }
+        }
+    }
+
+    for label in labels {
+        if existing_labels.contains(&label) {
+            continue;
+        }
+        let label_hash = path(&label).path_entry_hash()?;
+        create_link(
+            applet_hash.clone(),
+            label_hash.clone(),
+            applet_to_label,
+            LinkTag::new(label.clone()),
+        )?;
+        create_link(
+            label_hash,
+            applet_hash.clone(),
+            label_to_applet,
+            LinkTag::new(label),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The distinct labels of an Applet in alphabetical order
+fn get_applet_labels(applet_hash: EntryHash, link_type: LinkTypes) -> ExternResult<Vec<String>> {
+    let labels: BTreeSet<String> = get_links(applet_hash, link_type, None)?
+        .into_iter()
+        .filter_map(|link| applet_tag_from_link_tag(link.tag))
+        .collect();
+    Ok(labels.into_iter().collect())
+}
+
+fn get_labelled_applets(path: Path, link_type: LinkTypes) -> ExternResult<HashSet<EntryHash>> {
+    Ok(get_links(path.path_entry_hash()?, link_type, None)?
+        .into_iter()
+        .filter_map(|link| link.target.into_entry_hash())
+        .collect())
+}
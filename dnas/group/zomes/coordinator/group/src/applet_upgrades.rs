@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use group_integrity::*;
+use hdk::prelude::*;
+
+use crate::stewards::ensure_steward_permission;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProposeAppletUpgradeInput {
+    pub predecessor_hash: EntryHash,
+    pub successor_hash: EntryHash,
+}
+
+/// An Applet of the group being superseded by another Applet of the group
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AppletUpgrade {
+    pub predecessor_hash: EntryHash,
+    pub successor_hash: EntryHash,
+    pub proposed_by: AgentPubKey,
+    pub timestamp: Timestamp,
+}
+
+/// Proposes that an Applet of the group is superseded by another Applet, e.g. one
+/// running a newer and incompatible release of the same tool. The successor needs to
+/// be registered in the group already. Only stewards can propose upgrades.
+#[hdk_extern]
+pub fn propose_applet_upgrade(input: ProposeAppletUpgradeInput) -> ExternResult<ActionHash> {
+    let permission = ensure_steward_permission()?;
+
+    if input.predecessor_hash.eq(&input.successor_hash) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "An Applet cannot be its own successor."
+        ))));
+    }
+    if get(input.successor_hash.clone(), GetOptions::default())?.is_none() {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The successor Applet needs to be registered in the group first."
+        ))));
+    }
+
+    let existing_link = get_links(
+        input.predecessor_hash.clone(),
+        LinkTypes::AppletToSuccessor,
+        None,
+    )?
+    .into_iter()
+    .find(|link| link.target.eq(&AnyLinkableHash::from(input.successor_hash.clone())));
+    if let Some(link) = existing_link {
+        return Ok(link.create_link_hash);
+    }
+
+    // Upgrade chains must not loop back onto themselves
+    if get_upgrade_chain(input.successor_hash.clone())?.contains(&input.predecessor_hash) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The successor Applet has already been superseded by the predecessor Applet."
+        ))));
+    }
+
+    let link_hash = create_link(
+        input.predecessor_hash.clone(),
+        input.successor_hash.clone(),
+        LinkTypes::AppletToSuccessor,
+        permission.clone().to_tag()?,
+    )?;
+    create_link(
+        input.successor_hash,
+        input.predecessor_hash,
+        LinkTypes::AppletToPredecessor,
+        permission.to_tag()?,
+    )?;
+
+    Ok(link_hash)
+}
+
+/// Get the upgrades that have been proposed for the given Applet, oldest first
+#[hdk_extern]
+pub fn get_applet_successors(applet_hash: EntryHash) -> ExternResult<Vec<AppletUpgrade>> {
+    let links = get_links(applet_hash.clone(), LinkTypes::AppletToSuccessor, None)?;
+
+    let mut upgrades: Vec<AppletUpgrade> = links
+        .into_iter()
+        .filter_map(|link| {
+            link.target.into_entry_hash().map(|successor_hash| AppletUpgrade {
+                predecessor_hash: applet_hash.clone(),
+                successor_hash,
+                proposed_by: link.author,
+                timestamp: link.timestamp,
+            })
+        })
+        .collect();
+    upgrades.sort_by_key(|upgrade| upgrade.timestamp);
+    Ok(upgrades)
+}
+
+/// Get the Applets that the given Applet has been proposed to supersede, oldest first
+#[hdk_extern]
+pub fn get_applet_predecessors(applet_hash: EntryHash) -> ExternResult<Vec<AppletUpgrade>> {
+    let links = get_links(applet_hash.clone(), LinkTypes::AppletToPredecessor, None)?;
+
+    let mut upgrades: Vec<AppletUpgrade> = links
+        .into_iter()
+        .filter_map(|link| {
+            link.target.into_entry_hash().map(|predecessor_hash| AppletUpgrade {
+                predecessor_hash,
+                successor_hash: applet_hash.clone(),
+                proposed_by: link.author,
+                timestamp: link.timestamp,
+            })
+        })
+        .collect();
+    upgrades.sort_by_key(|upgrade| upgrade.timestamp);
+    Ok(upgrades)
+}
+
+/// Follows the successors of the given Applet, always taking the most recently
+/// proposed one, and returns the Applet at the end of the chain. Returns the given
+/// Applet itself if it has not been superseded.
+#[hdk_extern]
+pub fn get_latest_applet_version(applet_hash: EntryHash) -> ExternResult<EntryHash> {
+    Ok(get_upgrade_chain(applet_hash.clone())?
+        .pop()
+        .unwrap_or(applet_hash))
+}
+
+/// Returns the successors of the given Applet in the order in which they supersede
+/// each other, not including the Applet itself
+fn get_upgrade_chain(applet_hash: EntryHash) -> ExternResult<Vec<EntryHash>> {
+    let mut chain: Vec<EntryHash> = Vec::new();
+    let mut visited: HashSet<EntryHash> = HashSet::new();
+    visited.insert(applet_hash.clone());
+
+    let mut current = applet_hash;
+    while let Some(upgrade) = get_applet_successors(current)?.pop() {
+        if !visited.insert(upgrade.successor_hash.clone()) {
+            break;
+        }
+        chain.push(upgrade.successor_hash.clone());
+        current = upgrade.successor_hash;
+    }
+
+    Ok(chain)
+}
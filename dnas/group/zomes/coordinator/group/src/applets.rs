@@ -3,13 +3,9 @@ use std::collections::HashMap;
 use group_integrity::*;
 use hdk::prelude::*;
 
-fn get_group_applets_path() -> Path {
-    Path::from("group_applets")
-}
-
-fn get_federated_applets_path() -> Path {
-    Path::from("federated_applets")
-}
+use crate::applet_proposals::get_applet_proposal_acceptance_hash;
+use crate::group_settings::{get_group_settings, group_settings_reference};
+use crate::stewards::{ensure_steward_permission, optional_steward_permission};
 
 #[hdk_extern]
 fn hash_applet(applet: Applet) -> ExternResult<EntryHash> {
@@ -32,23 +28,111 @@ fn register_applet(applet: Applet) -> ExternResult<EntryHash> {
     match get(applet_hash.clone(), GetOptions::default()) {
         Ok(Some(_record)) => (),
         _ => {
+            if get_group_settings(())?.applet_proposal_rules.is_some() {
+                return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+                    "Applets of this group need to be proposed and accepted before they can be added."
+                ))));
+            }
             create_entry(EntryTypes::Applet(applet.clone()))?;
 
-            let path = get_group_applets_path();
+            let path = group_applets_path();
             let anchor_hash = path.path_entry_hash()?;
+            // Stewards store their permission in the tag so that they can archive
+            // the applet again later. The progenitor attests their chain top after we
+            // looked up the settings, so it can be referenced for both.
+            let (group_settings_hash, progenitor_chain_top) = group_settings_reference()?;
+            let permission = optional_steward_permission();
             create_link(
                 anchor_hash,
                 applet_hash.clone(),
                 LinkTypes::AnchorToApplet,
-                (),
+                AnchorToAppletTag {
+                    permission_hash: permission.permission_hash,
+                    progenitor_chain_top: permission.progenitor_chain_top.or(progenitor_chain_top),
+                    valid_until: permission.valid_until,
+                    progenitor_signature: permission.progenitor_signature,
+                    group_settings_hash,
+                    proposal_acceptance_hash: None,
+                    archived_link_hash: None,
+                }
+                .to_tag()?,
             )?;
         }
     }
 
     create_entry(EntryTypes::AppletPrivate(applet))?;
+    create_applet_joined_links(applet_hash.clone())?;
     Ok(applet_hash)
 }
 
+/// Links the calling agent and the Applet in both directions so that the group can see
+/// who has joined which Applet and when. Does nothing if the links already exist.
+fn create_applet_joined_links(applet_hash: EntryHash) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_latest_pubkey;
+
+    let already_joined = get_links(my_pub_key.clone(), LinkTypes::AgentToApplet, None)?
+        .into_iter()
+        .any(|link| link.target.into_entry_hash().eq(&Some(applet_hash.clone())));
+    if already_joined {
+        return Ok(());
+    }
+
+    create_link(
+        my_pub_key.clone(),
+        applet_hash.clone(),
+        LinkTypes::AgentToApplet,
+        (),
+    )?;
+    create_link(
+        applet_hash,
+        my_pub_key,
+        LinkTypes::AppletToJoinedAgent,
+        (),
+    )?;
+
+    Ok(())
+}
+
+/// Get the agents that have joined the given Applet, together with the time at
+/// which they joined it
+#[hdk_extern]
+fn get_applet_agents(applet_hash: EntryHash) -> ExternResult<Vec<(AgentPubKey, Timestamp)>> {
+    let links = get_links(applet_hash, LinkTypes::AppletToJoinedAgent, None)?;
+
+    let mut joined_at: HashMap<AgentPubKey, Timestamp> = HashMap::new();
+    for link in links {
+        if let Some(agent) = link.target.into_entry_hash().map(AgentPubKey::from) {
+            let timestamp = joined_at.entry(agent).or_insert(link.timestamp);
+            if link.timestamp < *timestamp {
+                *timestamp = link.timestamp;
+            }
+        }
+    }
+
+    let mut applet_agents: Vec<(AgentPubKey, Timestamp)> = joined_at.into_iter().collect();
+    applet_agents.sort_by_key(|(_agent, timestamp)| *timestamp);
+    Ok(applet_agents)
+}
+
+/// Get the Applets that the calling agent has joined, together with the time at
+/// which they joined them
+#[hdk_extern]
+fn get_my_joined_applets_with_timestamps(_: ()) -> ExternResult<Vec<(EntryHash, Timestamp)>> {
+    let my_pub_key = agent_info()?.agent_latest_pubkey;
+    let links = get_links(my_pub_key, LinkTypes::AgentToApplet, None)?;
+
+    let mut joined_applets: Vec<(EntryHash, Timestamp)> = links
+        .into_iter()
+        .filter_map(|link| {
+            link.target
+                .into_entry_hash()
+                .map(|applet_hash| (applet_hash, link.timestamp))
+        })
+        .collect();
+    joined_applets.sort_by_key(|(_applet_hash, timestamp)| *timestamp);
+    Ok(joined_applets)
+}
+
 /// NOTE: This doesn't seem to affect what get_my_applets returns via source chain
 /// query so it's not used atm.
 /// Supposed to be called by everyone that installs an Applet that has already
@@ -74,7 +158,7 @@ fn get_group_applet(applet_hash: EntryHash) -> ExternResult<Option<Record>> {
 
 /// Get the Applets that the calling agent has installed
 #[hdk_extern]
-fn get_my_applets(_: ()) -> ExternResult<Vec<EntryHash>> {
+pub fn get_my_applets(_: ()) -> ExternResult<Vec<EntryHash>> {
     let private_applet_entry_type: EntryType = UnitEntryTypes::AppletPrivate.try_into()?;
     let filter = ChainQueryFilter::new()
         .entry_type(private_applet_entry_type)
@@ -107,7 +191,7 @@ fn get_my_applet(action_hash: ActionHash) -> ExternResult<Option<Record>> {
 /// Get all the Applets that have been registered in the group
 #[hdk_extern]
 fn get_group_applets(_: ()) -> ExternResult<Vec<EntryHash>> {
-    let path = get_group_applets_path();
+    let path = group_applets_path();
 
     let links = get_links(path.path_entry_hash()?, LinkTypes::AnchorToApplet, None)?;
 
@@ -126,7 +210,7 @@ fn get_group_applets(_: ()) -> ExternResult<Vec<EntryHash>> {
 fn get_unjoined_applets(_: ()) -> ExternResult<Vec<(EntryHash, AgentPubKey)>> {
     let my_applets = get_my_applets(())?;
 
-    let path = get_group_applets_path();
+    let path = group_applets_path();
     let links = get_links(path.path_entry_hash()?, LinkTypes::AnchorToApplet, None)?;
 
     let applet_infos: Vec<(EntryHash, AgentPubKey)> = links
@@ -146,52 +230,117 @@ fn get_unjoined_applets(_: ()) -> ExternResult<Vec<(EntryHash, AgentPubKey)>> {
 
 /// The person who registered the applet to the group may also archive it,
 /// meaning that it won't be discovered by default anymore by agents that have not
-/// installed it yet.
+/// installed it yet. Whether the calling agent is allowed to archive the applet
+/// is enforced in validation. Custom views that depend on the applet get flagged
+/// as broken, failing to do so doesn't prevent archiving.
 #[hdk_extern]
 fn archive_applet(applet_hash: EntryHash) -> ExternResult<()> {
-    let path = get_group_applets_path();
+    let path = group_applets_path();
 
     let links = get_links(path.path_entry_hash()?, LinkTypes::AnchorToApplet, None)?;
 
+    let mut archive_hash: Option<ActionHash> = None;
     for link in links {
-        // TODO Make this an actual validation rule
-        if link.author != agent_info()?.agent_latest_pubkey {
-            return Err(wasm_error!(
-                WasmErrorInner::Guest(
-                    String::from("Applet can only be archived by the same agent that registered it to the group.")
-                )
-            ));
-        }
         if let Some(target_applet_hash) = link.target.into_entry_hash() {
             if target_applet_hash.eq(&applet_hash) {
-                delete_link(link.create_link_hash)?;
+                archive_hash = Some(delete_link(link.create_link_hash)?);
             }
         }
     }
+    if let Some(archive_hash) = archive_hash {
+        let input = FlagCustomViewsBrokenInput {
+            applet_hash,
+            archive_hash,
+        };
+        if let Err(err) = call_custom_views_zome("flag_custom_views_broken", input) {
+            error!("Failed to flag the custom views of the archived Applet as broken: {:?}", err);
+        }
+    }
 
     Ok(())
 }
 
-/// Anyone can unarchive it again, provided that they know the hash, i.e. have
+/// Stewards can unarchive it again, provided that they know the hash, i.e. have
 /// had the Applet installed already
 #[hdk_extern]
 fn unarchive_applet(applet_hash: EntryHash) -> ExternResult<()> {
-    let path = get_group_applets_path();
+    let (group_settings_hash, progenitor_chain_top) = group_settings_reference()?;
+    let permission = ensure_steward_permission()?;
+    let path = group_applets_path();
     let anchor_hash = path.path_entry_hash()?;
+
+    // Validation requires the unarchiving link to refer to the archived one
+    let applet_address = AnyLinkableHash::from(applet_hash.clone());
+    let links_details = get_link_details(anchor_hash.clone(), LinkTypes::AnchorToApplet, None)?;
+    let archived_link_hash = links_details
+        .into_inner()
+        .into_iter()
+        .filter(|(link_action, deletes)| match link_action.action() {
+            Action::CreateLink(link) => {
+                link.target_address.eq(&applet_address) && !deletes.is_empty()
+            }
+            _ => false,
+        })
+        .max_by_key(|(link_action, _deletes)| link_action.action().timestamp())
+        .map(|(link_action, _deletes)| link_action.as_hash().clone())
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "This Applet has not been archived."
+        ))))?;
+
     create_link(
         anchor_hash,
         applet_hash.clone(),
         LinkTypes::AnchorToApplet,
-        (),
+        AnchorToAppletTag {
+            permission_hash: permission.permission_hash,
+            progenitor_chain_top: permission.progenitor_chain_top.or(progenitor_chain_top),
+            valid_until: permission.valid_until,
+            progenitor_signature: permission.progenitor_signature,
+            group_settings_hash,
+            proposal_acceptance_hash: get_applet_proposal_acceptance_hash(applet_hash.clone())?,
+            archived_link_hash: Some(archived_link_hash),
+        }
+        .to_tag()?,
     )?;
+    if let Err(err) = call_custom_views_zome("unflag_custom_views_broken", applet_hash) {
+        error!("Failed to unflag the custom views of the unarchived Applet: {:?}", err);
+    }
 
     Ok(())
 }
 
+/// Mirrors the FlagCustomViewsBrokenInput of the custom_views zome
+#[derive(Serialize, Deserialize, Debug)]
+struct FlagCustomViewsBrokenInput {
+    applet_hash: EntryHash,
+    archive_hash: ActionHash,
+}
+
+/// Custom views that depend on an Applet are flagged as broken while it is archived
+fn call_custom_views_zome<I>(fn_name: &str, payload: I) -> ExternResult<()>
+where
+    I: Serialize + std::fmt::Debug,
+{
+    let response = call(
+        CallTargetCell::Local,
+        ZomeName::from("custom_views"),
+        FunctionName::from(fn_name),
+        None,
+        payload,
+    )?;
+    match response {
+        ZomeCallResponse::Ok(_) => Ok(()),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Failed to call {} in the custom_views zome: {:?}",
+            fn_name, response
+        )))),
+    }
+}
+
 
 #[hdk_extern]
 fn get_archived_applets(_: ()) -> ExternResult<Vec<EntryHash>> {
-    let path = get_group_applets_path();
+    let path = group_applets_path();
 
     let links_details = get_link_details(path.path_entry_hash()?, LinkTypes::AnchorToApplet, None)?;
 
@@ -224,46 +373,302 @@ fn get_archived_applets(_: ()) -> ExternResult<Vec<EntryHash>> {
     Ok(entry_hashes)
 }
 
-/// Registers the federation of an applet. The actual federation happens in the front-end
-/// by installing the same applet in another group. It is only registered in the backend
-/// *that* this applet has been federated.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProposeAppletFederationInput {
+    pub applet_hash: EntryHash,
+    pub group_dna_hash: DnaHash,
+}
+
+/// First step of federating an Applet with another group: records the proposal in
+/// this group. Only stewards can propose to federate applets.
+#[hdk_extern]
+pub fn propose_applet_federation(input: ProposeAppletFederationInput) -> ExternResult<ActionHash> {
+    ensure_steward_permission()?;
+    let proposal_hash = create_entry(EntryTypes::AppletFederationProposal(
+        AppletFederationProposal {
+            applet_hash: input.applet_hash.clone(),
+            proposing_group_dna_hash: dna_info()?.hash,
+            invited_group_dna_hash: input.group_dna_hash,
+        },
+    ))?;
+    create_link(
+        input.applet_hash,
+        proposal_hash.clone(),
+        LinkTypes::AppletToFederationProposal,
+        (),
+    )?;
+    Ok(proposal_hash)
+}
+
+/// Gets the AppletFederationProposals that have been made for this Applet, including the
+/// ones that have not been accepted (yet)
+#[hdk_extern]
+pub fn get_applet_federation_proposals(applet_hash: EntryHash) -> ExternResult<Vec<Record>> {
+    let links = get_links(applet_hash, LinkTypes::AppletToFederationProposal, None)?;
+    let get_input: Vec<GetInput> = links
+        .into_iter()
+        .filter_map(|link| link.target.into_action_hash())
+        .map(|action_hash| GetInput::new(action_hash.into(), GetOptions::default()))
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+    Ok(records.into_iter().filter_map(|r| r).collect())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AcceptAppletFederationInput {
+    pub applet: Applet,
+    /// Action hash of the AppletFederationProposal in the proposing group
+    pub proposal_hash: ActionHash,
+    pub proposing_group_dna_hash: DnaHash,
+}
+
+/// Second step of federating an Applet, called in the invited group: registers the
+/// Applet in this group and records the acceptance of the proposal. Only stewards can
+/// accept federation proposals. The returned signed acceptance needs to be passed to
+/// `register_applet_federation` in the proposing group.
+///
+/// Groups that vote on Applets don't add them here: the Applet must have been proposed
+/// with `propose_applet` and the proposal accepted before the federation can be.
+#[hdk_extern]
+pub fn accept_applet_federation(
+    input: AcceptAppletFederationInput,
+) -> ExternResult<SignedAppletFederationAcceptance> {
+    if get_group_settings(())?.applet_proposal_rules.is_some()
+        && get_applet_proposal_acceptance_hash(hash_entry(&input.applet)?)?.is_none()
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Applets of this group need to be proposed and accepted before their federation can be accepted."
+        ))));
+    }
+    let permission = ensure_steward_permission()?;
+    let applet_hash = register_applet(input.applet)?;
+
+    let acceptance = AppletFederationAcceptance {
+        applet_hash: applet_hash.clone(),
+        proposal_hash: input.proposal_hash,
+        proposing_group_dna_hash: input.proposing_group_dna_hash,
+        accepting_group_dna_hash: dna_info()?.hash,
+    };
+    let acceptance_hash = create_entry(EntryTypes::AppletFederationAcceptance(
+        acceptance.clone(),
+    ))?;
+    create_federation_links(
+        applet_hash,
+        acceptance_hash,
+        AppletToInvitedGroupTag {
+            permission_hash: permission.permission_hash,
+            progenitor_chain_top: permission.progenitor_chain_top,
+            valid_until: permission.valid_until,
+            progenitor_signature: permission.progenitor_signature,
+            signed_acceptance: None,
+        },
+    )?;
+
+    let accepting_agent = agent_info()?.agent_initial_pubkey;
+    let signature = sign(accepting_agent.clone(), acceptance.clone())?;
+    Ok(SignedAppletFederationAcceptance {
+        acceptance,
+        accepting_agent,
+        signature,
+    })
+}
+
+/// Last step of federating an Applet, called in the proposing group once the invited
+/// group has accepted the proposal. Only stewards can register federations.
 #[hdk_extern]
 pub fn register_applet_federation(input: RegisterAppletFederationInput) -> ExternResult<ActionHash> {
+    let permission = ensure_steward_permission()?;
+
+    let record = get(input.proposal_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("AppletFederationProposal not found."))
+    ))?;
+    let proposal = record
+        .entry()
+        .to_app_option::<AppletFederationProposal>()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given proposal hash is not an AppletFederationProposal."
+        ))))?;
+    if proposal.applet_hash.ne(&input.applet_hash)
+        || proposal.invited_group_dna_hash.ne(&input.group_dna_hash)
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The AppletFederationProposal is for a different Applet or group."
+        ))));
+    }
+    if input
+        .signed_acceptance
+        .acceptance
+        .proposal_hash
+        .ne(&input.proposal_hash)
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given acceptance is for a different AppletFederationProposal."
+        ))));
+    }
+
+    create_federation_links(
+        input.applet_hash,
+        input.proposal_hash,
+        AppletToInvitedGroupTag {
+            permission_hash: permission.permission_hash,
+            progenitor_chain_top: permission.progenitor_chain_top,
+            valid_until: permission.valid_until,
+            progenitor_signature: permission.progenitor_signature,
+            signed_acceptance: Some(input.signed_acceptance),
+        },
+    )
+}
+
+fn create_federation_links(
+    applet_hash: EntryHash,
+    federation_hash: ActionHash,
+    tag: AppletToInvitedGroupTag,
+) -> ExternResult<ActionHash> {
     create_link(
-        input.applet_hash.clone(),
-        input.group_dna_hash,
+        applet_hash.clone(),
+        federation_hash,
         LinkTypes::AppletToInvitedGroup,
-        (),
+        tag.to_tag()?,
     )?;
-    let path = get_federated_applets_path();
+    let path = federated_applets_path();
     let anchor_hash = path.path_entry_hash()?;
     create_link(
         anchor_hash,
-        input.applet_hash,
+        applet_hash,
         LinkTypes::AnchorToFederatedApplet,
         ()
     )
 }
 
+/// Gets the AppletToInvitedGroup links of an Applet together with the group that each
+/// of them federates the Applet with
+fn get_federation_links(applet_hash: EntryHash) -> ExternResult<Vec<(Link, DnaHash)>> {
+    let links = get_links(applet_hash, LinkTypes::AppletToInvitedGroup, None)?;
+
+    let mut federation_links: Vec<(Link, DnaHash)> = Vec::new();
+    for link in links {
+        let Some(federation_hash) = link.target.clone().into_action_hash() else {
+            continue;
+        };
+        if let Some(record) = get(federation_hash, GetOptions::default())? {
+            if let Some((_applet_hash, group_dna_hash)) = federated_group_from_record(&record) {
+                federation_links.push((link, group_dna_hash));
+            }
+        }
+    }
+    Ok(federation_links)
+}
+
 /// Get the nearest-neighbor groups this app is federated with. The applet may in reality
 /// be shared by arbitrarily many groups of which the group calling this function does
 /// not know about ("viral federation").
 #[hdk_extern]
-pub fn get_federated_groups(applet_hash: EntryHash) -> ExternResult<Vec<EntryHash>> {
-    let links = get_links(applet_hash, LinkTypes::AppletToInvitedGroup, None)?;
-    Ok(
-        links
-            .into_iter()
-            .filter_map(|link| link.target.into_entry_hash())
-            .collect::<Vec<holo_hash::EntryHash>>()
+pub fn get_federated_groups(applet_hash: EntryHash) -> ExternResult<Vec<DnaHash>> {
+    let mut groups: Vec<DnaHash> = Vec::new();
+    for (_link, group_dna_hash) in get_federation_links(applet_hash)? {
+        if !groups.contains(&group_dna_hash) {
+            groups.push(group_dna_hash);
+        }
+    }
+    Ok(groups)
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegisterExternalAgentInput {
+    pub applet_hash: EntryHash,
+    pub agent: AgentPubKey,
+    pub group_dna_hash: DnaHash,
+}
+
+/// An agent of another group that takes part in a federated Applet
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExternalAgent {
+    pub agent: AgentPubKey,
+    pub group_dna_hash: DnaHash,
+}
+
+/// Registers an agent from another group as a participant of a federated Applet. The
+/// group via which the agent joined must be one of the groups returned by
+/// `get_federated_groups` for this Applet. Only stewards can register external agents.
+#[hdk_extern]
+pub fn register_external_agent(input: RegisterExternalAgentInput) -> ExternResult<ActionHash> {
+    let steward_permission = ensure_steward_permission()?;
+    let (federation_link, _group_dna_hash) = get_federation_links(input.applet_hash.clone())?
+        .into_iter()
+        .find(|(_link, group_dna_hash)| group_dna_hash.eq(&input.group_dna_hash))
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The Applet is not federated with the group of the external agent."
+        ))))?;
+
+    let existing_link = get_links(
+        input.applet_hash.clone(),
+        LinkTypes::AppletToExternalAgent,
+        None,
+    )?
+    .into_iter()
+    .find(|link| {
+        link.target.eq(&AnyLinkableHash::from(input.agent.clone()))
+            && ExternalAgentTag::try_from(SerializedBytes::from(UnsafeBytes::from(
+                link.tag.0.clone(),
+            )))
+            .map(|tag| tag.group_dna_hash.eq(&input.group_dna_hash))
+            .unwrap_or(false)
+    });
+    if let Some(link) = existing_link {
+        return Ok(link.create_link_hash);
+    }
+
+    let tag = ExternalAgentTag {
+        group_dna_hash: input.group_dna_hash,
+        federation_link_hash: federation_link.create_link_hash,
+        steward_permission,
+    };
+
+    create_link(
+        input.applet_hash,
+        input.agent,
+        LinkTypes::AppletToExternalAgent,
+        LinkTag::new(
+            SerializedBytes::try_from(tag)
+                .map_err(|err| wasm_error!(err))?
+                .bytes()
+                .clone(),
+        ),
     )
 }
 
+/// Get the agents of other groups that take part in the given federated Applet
+#[hdk_extern]
+pub fn get_applet_external_agents(applet_hash: EntryHash) -> ExternResult<Vec<ExternalAgent>> {
+    let links = get_links(applet_hash, LinkTypes::AppletToExternalAgent, None)?;
+
+    let mut external_agents: Vec<ExternalAgent> = Vec::new();
+    for link in links {
+        let Some(agent) = link.target.into_agent_pub_key() else {
+            continue;
+        };
+        let Ok(tag) = ExternalAgentTag::try_from(SerializedBytes::from(UnsafeBytes::from(link.tag.0)))
+        else {
+            continue;
+        };
+        let external_agent = ExternalAgent {
+            agent,
+            group_dna_hash: tag.group_dna_hash,
+        };
+        if !external_agents.contains(&external_agent) {
+            external_agents.push(external_agent);
+        }
+    }
+
+    Ok(external_agents)
+}
 
 /// Get Applets of this group that are knowingly federated with other groups
 #[hdk_extern]
 pub fn get_federated_applets(_: ()) -> ExternResult<Vec<EntryHash>> {
-    let path = get_federated_applets_path();
+    let path = federated_applets_path();
     let anchor_hash = path.path_entry_hash()?;
     let links = get_links(anchor_hash, LinkTypes::AnchorToFederatedApplet, None)?;
     Ok(
@@ -0,0 +1,88 @@
+use group_integrity::*;
+use hdk::prelude::*;
+
+use crate::stewards::agent_chain_top;
+
+/// Gets the AnchorToGroupSettings link pointing to the latest GroupSettings, if any.
+/// Links with the same timestamp are ordered by their action hash.
+fn get_latest_group_settings_link() -> ExternResult<Option<Link>> {
+    let links = get_links(
+        group_settings_path().path_entry_hash()?,
+        LinkTypes::AnchorToGroupSettings,
+        None,
+    )?;
+    Ok(links.into_iter().max_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    }))
+}
+
+/// The action hash of the GroupSettings currently in effect, to be referenced in the
+/// tags of links whose validity depends on them
+pub fn latest_group_settings_hash() -> ExternResult<Option<ActionHash>> {
+    Ok(get_latest_group_settings_link()?.and_then(|link| link.target.into_action_hash()))
+}
+
+/// The GroupSettings currently in effect together with the latest action of the
+/// progenitor that we know of, to be referenced in the tags of links whose validity
+/// depends on the GroupSettings. Validators check on the chain of the progenitor that
+/// the referenced settings had not been superseded when the link was created, so the
+/// chain top is fetched after the settings.
+pub fn group_settings_reference() -> ExternResult<(Option<ActionHash>, Option<ActionHash>)> {
+    let group_settings_hash = latest_group_settings_hash()?;
+    let agent_info = agent_info()?;
+    let chain_top = match progenitor()? {
+        None => None,
+        Some(progenitor) if progenitor.eq(&agent_info.agent_initial_pubkey) => {
+            Some(agent_info.chain_head.0)
+        }
+        Some(progenitor) => Some(agent_chain_top(progenitor)?),
+    };
+    Ok((group_settings_hash, chain_top))
+}
+
+/// Gets the GroupSettings currently in effect. Returns the default settings if the
+/// progenitor has not set any (or we haven't synced up yet).
+#[hdk_extern]
+pub fn get_group_settings(_: ()) -> ExternResult<GroupSettings> {
+    let Some(group_settings_hash) = latest_group_settings_hash()? else {
+        return Ok(GroupSettings::default());
+    };
+    let Some(record) = get(group_settings_hash, GetOptions::default())? else {
+        return Ok(GroupSettings::default());
+    };
+    Ok(record
+        .entry()
+        .to_app_option::<GroupSettings>()
+        .map_err(|e| wasm_error!(e))?
+        .unwrap_or_default())
+}
+
+/// Sets new GroupSettings. Only the progenitor can do so. The new settings reference
+/// the latest GroupSettings on our source chain as their predecessor.
+#[hdk_extern]
+pub fn set_group_settings(mut group_settings: GroupSettings) -> ExternResult<ActionHash> {
+    if progenitor()?.ne(&Some(agent_info()?.agent_initial_pubkey)) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Only the progenitor of the group can set the GroupSettings."
+        ))));
+    }
+
+    let group_settings_entry_type: EntryType = UnitEntryTypes::GroupSettings.try_into()?;
+    let filter = ChainQueryFilter::new().entry_type(group_settings_entry_type);
+    group_settings.previous_group_settings_hash = query(filter)?
+        .last()
+        .map(|record| record.action_address().clone());
+
+    let group_settings_hash = create_entry(EntryTypes::GroupSettings(group_settings))?;
+    create_link(
+        group_settings_path().path_entry_hash()?,
+        group_settings_hash.clone(),
+        LinkTypes::AnchorToGroupSettings,
+        (),
+    )?;
+
+    Ok(group_settings_hash)
+}
@@ -1,33 +1,101 @@
 use group_integrity::*;
 use hdk::prelude::*;
 
+pub mod applet_listings;
+pub mod applet_proposals;
+pub mod applet_tags;
+pub mod applet_upgrades;
 pub mod applets;
+pub mod group_settings;
+pub mod membership;
 pub mod related_groups;
+pub mod signals;
+pub mod stewards;
+
+use stewards::{ensure_steward_permission, my_steward_permission};
+
+#[hdk_extern]
+pub fn init(_: ()) -> ExternResult<InitCallbackResult> {
+    // Peers need to be able to notify us of new, archived and federated Applets
+    let mut functions = BTreeSet::new();
+    functions.insert((zome_info()?.name, FunctionName::from("recv_remote_signal")));
+    create_cap_grant(CapGrantEntry {
+        tag: String::from("recv_remote_signal"),
+        access: CapAccess::Unrestricted,
+        functions: GrantedFunctions::Listed(functions),
+    })?;
+
+    // Stewards need the progenitor to attest their permissions
+    if progenitor()?.eq(&Some(agent_info()?.agent_initial_pubkey)) {
+        let mut functions = BTreeSet::new();
+        functions.insert((
+            zome_info()?.name,
+            FunctionName::from("attest_steward_permission"),
+        ));
+        create_cap_grant(CapGrantEntry {
+            tag: String::from("attest_steward_permission"),
+            access: CapAccess::Unrestricted,
+            functions: GrantedFunctions::Listed(functions),
+        })?;
+    }
+
+    membership::add_myself_as_member()?;
+
+    Ok(InitCallbackResult::Pass)
+}
 
 pub fn group_info_path() -> ExternResult<TypedPath> {
     Path::from("group_profile").typed(LinkTypes::GroupInfoPath)
 }
 
-// If this function returns None, it means that we haven't synced up yet
 #[hdk_extern]
-pub fn get_group_profile(_: ()) -> ExternResult<Option<Record>> {
+pub fn get_group_dna_properties(_: ()) -> ExternResult<GroupDnaProperties> {
+    group_dna_properties()
+}
+
+/// A version of the group profile, i.e. a GroupProfile together with the agent that
+/// set it as the group profile and when
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GroupProfileVersion {
+    pub group_profile_hash: ActionHash,
+    pub group_profile: GroupProfile,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+}
+
+/// Orders AnchorToGroupProfile links from oldest to newest. Links with the same
+/// timestamp, e.g. from concurrent edits, are ordered by their action hash so that
+/// every agent arrives at the same latest profile.
+fn sort_group_profile_links(links: &mut [Link]) {
+    links.sort_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+}
+
+fn get_group_profile_links() -> ExternResult<Vec<Link>> {
     let path = group_info_path()?;
 
-    let links = get_links(
+    let mut links = get_links(
         path.path_entry_hash()?,
         LinkTypes::AnchorToGroupProfile,
         None,
     )?;
+    sort_group_profile_links(&mut links);
+    Ok(links)
+}
 
-    let latest_group_info_link = links
-        .into_iter()
-        .max_by(|link_a, link_b| link_b.timestamp.cmp(&link_a.timestamp));
+// If this function returns None, it means that we haven't synced up yet
+#[hdk_extern]
+pub fn get_group_profile(_: ()) -> ExternResult<Option<Record>> {
+    let latest_group_info_link = get_group_profile_links()?.pop();
 
     match latest_group_info_link {
         None => Ok(None),
         Some(link) => {
             let record = get(
-                // ActionHash::from(link.target),
                 ActionHash::try_from(link.target)
                     .map_err(|e| wasm_error!(WasmErrorInner::from(e)))?,
                 GetOptions::default(),
@@ -38,6 +106,60 @@ pub fn get_group_profile(_: ()) -> ExternResult<Option<Record>> {
     }
 }
 
+/// Get all the versions that the group profile has had, oldest first. Rollbacks show
+/// up as a new version with the same group_profile_hash as the version they restore.
+#[hdk_extern]
+pub fn get_group_profile_history(_: ()) -> ExternResult<Vec<GroupProfileVersion>> {
+    let links = get_group_profile_links()?;
+
+    let mut history: Vec<GroupProfileVersion> = Vec::new();
+    for link in links {
+        let Some(group_profile_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        let Some(record) = get(group_profile_hash.clone(), GetOptions::default())? else {
+            continue;
+        };
+        let Ok(Some(group_profile)) = record.entry().to_app_option::<GroupProfile>() else {
+            continue;
+        };
+        history.push(GroupProfileVersion {
+            group_profile_hash,
+            group_profile,
+            author: link.author,
+            timestamp: link.timestamp,
+        });
+    }
+
+    Ok(history)
+}
+
+/// Makes a previous version of the group profile the current one again. Only stewards
+/// can roll back the group profile.
+#[hdk_extern]
+pub fn rollback_group_profile(group_profile_hash: ActionHash) -> ExternResult<()> {
+    let permission = ensure_steward_permission()?;
+
+    let is_previous_version = get_group_profile_links()?
+        .into_iter()
+        .any(|link| link.target.eq(&AnyLinkableHash::from(group_profile_hash.clone())));
+    if !is_previous_version {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given GroupProfile has never been the profile of this group."
+        ))));
+    }
+
+    let path = group_info_path()?;
+    create_link(
+        path.path_entry_hash()?,
+        group_profile_hash,
+        LinkTypes::AnchorToGroupProfile,
+        permission.to_tag()?,
+    )?;
+
+    Ok(())
+}
+
 #[hdk_extern]
 pub fn set_group_profile(group_profile: GroupProfile) -> ExternResult<()> {
     let path = group_info_path()?;
@@ -48,7 +170,7 @@ pub fn set_group_profile(group_profile: GroupProfile) -> ExternResult<()> {
         path.path_entry_hash()?,
         action_hash,
         LinkTypes::AnchorToGroupProfile,
-        (),
+        my_steward_permission()?.unwrap_or_default().to_tag()?,
     )?;
 
     Ok(())
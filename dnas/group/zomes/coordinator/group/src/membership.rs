@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use group_integrity::*;
+use hdk::prelude::*;
+
+/// A member of the group and the time at which they joined it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GroupMember {
+    pub agent: AgentPubKey,
+    pub joined_at: Timestamp,
+}
+
+/// Records the calling agent as a member of the group. Called from `init`.
+pub fn add_myself_as_member() -> ExternResult<()> {
+    let agent_validation_pkg_hash = query(
+        ChainQueryFilter::new().action_type(ActionType::AgentValidationPkg),
+    )?
+    .first()
+    .map(|record| record.action_address().clone())
+    .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+        "Could not find our AgentValidationPkg."
+    ))))?;
+
+    create_link(
+        members_path().path_entry_hash()?,
+        agent_info()?.agent_initial_pubkey,
+        LinkTypes::AnchorToMember,
+        AnchorToMemberTag {
+            agent_validation_pkg_hash,
+        }
+        .to_tag()?,
+    )?;
+    Ok(())
+}
+
+/// Records the calling agent as a member of the group again after they have left it,
+/// e.g. when the group app gets enabled again. If the agent is a member already, this
+/// is a no-op.
+#[hdk_extern]
+pub fn rejoin_group(_: ()) -> ExternResult<()> {
+    if !my_member_links()?.is_empty() {
+        return Ok(());
+    }
+    add_myself_as_member()
+}
+
+/// Removes the calling agent from the members of the group. The membership is kept
+/// as a deleted link so that it is still known when the agent left.
+#[hdk_extern]
+pub fn leave_group(_: ()) -> ExternResult<()> {
+    let my_links = my_member_links()?;
+    if my_links.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "We are not a member of this group."
+        ))));
+    }
+
+    for link in my_links {
+        delete_link(link.create_link_hash)?;
+    }
+
+    Ok(())
+}
+
+fn my_member_links() -> ExternResult<Vec<Link>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    Ok(get_links(
+        members_path().path_entry_hash()?,
+        LinkTypes::AnchorToMember,
+        None,
+    )?
+    .into_iter()
+    .filter(|link| link.author.eq(&my_pub_key))
+    .collect())
+}
+
+/// Get the current members of the group, the earliest to join first
+#[hdk_extern]
+pub fn get_members(_: ()) -> ExternResult<Vec<GroupMember>> {
+    let links = get_links(
+        members_path().path_entry_hash()?,
+        LinkTypes::AnchorToMember,
+        None,
+    )?;
+
+    let mut joined_at: HashMap<AgentPubKey, Timestamp> = HashMap::new();
+    for link in links {
+        if let Some(agent) = link.target.into_entry_hash().map(AgentPubKey::from) {
+            let timestamp = joined_at.entry(agent).or_insert(link.timestamp);
+            if link.timestamp < *timestamp {
+                *timestamp = link.timestamp;
+            }
+        }
+    }
+
+    let mut members: Vec<GroupMember> = joined_at
+        .into_iter()
+        .map(|(agent, joined_at)| GroupMember { agent, joined_at })
+        .collect();
+    members.sort_by_key(|member| member.joined_at);
+    Ok(members)
+}
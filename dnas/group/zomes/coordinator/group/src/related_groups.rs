@@ -1,24 +1,76 @@
+use std::collections::HashSet;
+
 use group_integrity::*;
 use hdk::prelude::*;
 
-pub fn related_groups_path() -> Path {
-    Path::from("related_groups")
+use crate::stewards::optional_steward_permission;
+
+/// Whether a related group has added this group as related as well
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RelatedGroupStatus {
+    /// Both groups have added each other as related
+    Mutual,
+    /// The related group has not added this group as related
+    OneSided,
+    /// We are not a member of the related group so we cannot tell
+    Unknown,
 }
 
-// If this function returns None, it means that we haven't synced up yet
-#[hdk_extern]
-pub fn get_related_groups(_: ()) -> ExternResult<Vec<Record>> {
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RelatedGroupWithStatus {
+    pub related_group_hash: EntryHash,
+    pub related_group: RelatedGroup,
+    pub added_by: AgentPubKey,
+    pub status: RelatedGroupStatus,
+}
+
+/// Gets the AnchorToRelatedGroup links together with the RelatedGroups they point to,
+/// keeping only the oldest link per related group DNA. Duplicates can still exist if
+/// two members added the same group before syncing with each other.
+fn get_related_group_links() -> ExternResult<Vec<(Link, RelatedGroup)>> {
     let path = related_groups_path();
 
-    let links = get_links(
+    let mut links = get_links(
         path.path_entry_hash()?,
         LinkTypes::AnchorToRelatedGroup,
         None,
     )?;
+    links.sort_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+
+    let mut seen_dna_hashes: HashSet<DnaHash> = HashSet::new();
+    let mut related_groups: Vec<(Link, RelatedGroup)> = Vec::new();
+    for link in links {
+        let Some(entry_hash) = link.target.clone().into_entry_hash() else {
+            continue;
+        };
+        let Some(record) = get(entry_hash, GetOptions::default())? else {
+            continue;
+        };
+        let Some(related_group) = record
+            .entry()
+            .to_app_option::<RelatedGroup>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        if seen_dna_hashes.insert(related_group.group_dna_hash.clone()) {
+            related_groups.push((link, related_group));
+        }
+    }
+    Ok(related_groups)
+}
 
-    let get_input: Vec<GetInput> = links
+// If this function returns None, it means that we haven't synced up yet
+#[hdk_extern]
+pub fn get_related_groups(_: ()) -> ExternResult<Vec<Record>> {
+    let get_input: Vec<GetInput> = get_related_group_links()?
         .into_iter()
-        .filter_map(|link| link.target.into_entry_hash())
+        .filter_map(|(link, _)| link.target.into_entry_hash())
         .map(|entry_hash| GetInput::new(entry_hash.into(), GetOptions::default()))
         .collect();
     let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
@@ -26,20 +78,132 @@ pub fn get_related_groups(_: ()) -> ExternResult<Vec<Record>> {
     Ok(records)
 }
 
+/// Adds a group as related to this group. If the group is already related, this is a no-op.
 #[hdk_extern]
 pub fn add_related_group(related_group: RelatedGroup) -> ExternResult<()> {
+    let already_related = get_related_group_links()?
+        .into_iter()
+        .any(|(_, existing)| existing.group_dna_hash.eq(&related_group.group_dna_hash));
+    if already_related {
+        return Ok(());
+    }
+
     let path = related_groups_path();
 
     let related_group_hash = hash_entry(&related_group)?;
 
-    create_entry(EntryTypes::RelatedGroup(related_group))?;
+    if get(related_group_hash.clone(), GetOptions::default())?.is_none() {
+        create_entry(EntryTypes::RelatedGroup(related_group))?;
+    }
 
     create_link(
         path.path_entry_hash()?,
         related_group_hash,
         LinkTypes::AnchorToRelatedGroup,
-        (),
+        optional_steward_permission().to_tag()?,
     )?;
 
     Ok(())
 }
+
+/// Removes the group with the given DNA hash from the related groups. Only the agent
+/// that added a related group can remove it.
+#[hdk_extern]
+pub fn remove_related_group(group_dna_hash: DnaHash) -> ExternResult<()> {
+    let path = related_groups_path();
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+
+    let links = get_links(
+        path.path_entry_hash()?,
+        LinkTypes::AnchorToRelatedGroup,
+        None,
+    )?;
+
+    let mut found = false;
+    let mut removed = false;
+    for link in links {
+        let Some(entry_hash) = link.target.clone().into_entry_hash() else {
+            continue;
+        };
+        let Some(record) = get(entry_hash, GetOptions::default())? else {
+            continue;
+        };
+        let Some(related_group) = record
+            .entry()
+            .to_app_option::<RelatedGroup>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        if related_group.group_dna_hash.ne(&group_dna_hash) {
+            continue;
+        }
+        found = true;
+        if link.author.eq(&my_pub_key) {
+            delete_link(link.create_link_hash)?;
+            removed = true;
+        }
+    }
+
+    match (found, removed) {
+        (false, _) => Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The group is not a related group of this group."
+        )))),
+        (true, false) => Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Related groups can only be removed by the agent that added them."
+        )))),
+        (true, true) => Ok(()),
+    }
+}
+
+/// Whether the group with the given DNA hash is a related group of this group. Called by
+/// the cells of other groups of the same agent to find out if a relation is mutual.
+#[hdk_extern]
+pub fn is_related_group(group_dna_hash: DnaHash) -> ExternResult<bool> {
+    Ok(get_related_group_links()?
+        .into_iter()
+        .any(|(_, related_group)| related_group.group_dna_hash.eq(&group_dna_hash)))
+}
+
+/// Gets the related groups together with whether they have added this group as related
+/// too. This can only be determined for the related groups that we are a member of, as
+/// the other group's cell is asked directly.
+#[hdk_extern]
+pub fn get_related_groups_with_status(_: ()) -> ExternResult<Vec<RelatedGroupWithStatus>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let my_dna_hash = dna_info()?.hash;
+
+    let mut related_groups: Vec<RelatedGroupWithStatus> = Vec::new();
+    for (link, related_group) in get_related_group_links()? {
+        let Some(related_group_hash) = link.target.into_entry_hash() else {
+            continue;
+        };
+
+        let response = call(
+            CallTargetCell::OtherCell(CellId::new(
+                related_group.group_dna_hash.clone(),
+                my_pub_key.clone(),
+            )),
+            ZomeName::from("group"),
+            FunctionName::from("is_related_group"),
+            None,
+            my_dna_hash.clone(),
+        );
+        let status = match response {
+            Ok(ZomeCallResponse::Ok(result)) => match result.decode::<bool>() {
+                Ok(true) => RelatedGroupStatus::Mutual,
+                Ok(false) => RelatedGroupStatus::OneSided,
+                Err(_) => RelatedGroupStatus::Unknown,
+            },
+            _ => RelatedGroupStatus::Unknown,
+        };
+
+        related_groups.push(RelatedGroupWithStatus {
+            related_group_hash,
+            related_group,
+            added_by: link.author,
+            status,
+        });
+    }
+    Ok(related_groups)
+}
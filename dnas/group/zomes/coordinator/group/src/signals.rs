@@ -0,0 +1,195 @@
+use group_integrity::*;
+use hdk::prelude::*;
+
+/// Changes to the Applets of the group that other members should notice without
+/// having to poll for them
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum GroupEvent {
+    AppletRegistered { applet_hash: EntryHash },
+    AppletArchived { applet_hash: EntryHash },
+    AppletFederated { applet_hash: EntryHash },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum Signal {
+    EntryCreated {
+        action: SignedActionHashed,
+        app_entry: EntryTypes,
+    },
+    EntryUpdated {
+        action: SignedActionHashed,
+        app_entry: EntryTypes,
+        original_app_entry: EntryTypes,
+    },
+    EntryDeleted {
+        action: SignedActionHashed,
+        original_app_entry: EntryTypes,
+    },
+    LinkCreated {
+        action: SignedActionHashed,
+        link_type: LinkTypes,
+    },
+    LinkDeleted {
+        action: SignedActionHashed,
+        link_type: LinkTypes,
+    },
+    /// A GroupEvent caused by an action of the calling agent. The UI is expected to pass
+    /// it on to the online peers of the group via `notify_peers`.
+    LocalEvent { event: GroupEvent },
+    /// A GroupEvent that a peer has notified us of
+    RemoteEvent {
+        from_agent: AgentPubKey,
+        event: GroupEvent,
+    },
+}
+
+#[hdk_extern(infallible)]
+pub fn post_commit(committed_actions: Vec<SignedActionHashed>) {
+    for action in committed_actions {
+        if let Err(err) = signal_action(action) {
+            error!("Error signaling new action: {:?}", err);
+        }
+    }
+}
+
+fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
+    match action.hashed.content.clone() {
+        Action::Create(_create) => {
+            if let Ok(Some(app_entry)) = get_entry_for_action(&action.hashed.hash) {
+                emit_signal(Signal::EntryCreated { action, app_entry })?;
+            }
+            Ok(())
+        }
+        Action::Update(update) => {
+            if let Ok(Some(app_entry)) = get_entry_for_action(&action.hashed.hash) {
+                if let Ok(Some(original_app_entry)) =
+                    get_entry_for_action(&update.original_action_address)
+                {
+                    emit_signal(Signal::EntryUpdated {
+                        action,
+                        app_entry,
+                        original_app_entry,
+                    })?;
+                }
+            }
+            Ok(())
+        }
+        Action::Delete(delete) => {
+            if let Ok(Some(original_app_entry)) = get_entry_for_action(&delete.deletes_address) {
+                emit_signal(Signal::EntryDeleted {
+                    action,
+                    original_app_entry,
+                })?;
+            }
+            Ok(())
+        }
+        Action::CreateLink(create_link) => {
+            if let Ok(Some(link_type)) =
+                LinkTypes::from_type(create_link.zome_index, create_link.link_type)
+            {
+                let event = match (&link_type, create_link.target_address.into_entry_hash()) {
+                    (LinkTypes::AnchorToApplet, Some(applet_hash)) => {
+                        Some(GroupEvent::AppletRegistered { applet_hash })
+                    }
+                    (LinkTypes::AnchorToFederatedApplet, Some(applet_hash)) => {
+                        Some(GroupEvent::AppletFederated { applet_hash })
+                    }
+                    _ => None,
+                };
+                emit_signal(Signal::LinkCreated { action, link_type })?;
+                if let Some(event) = event {
+                    emit_signal(Signal::LocalEvent { event })?;
+                }
+            }
+            Ok(())
+        }
+        Action::DeleteLink(delete_link) => {
+            // The deleted CreateLink may not be reachable yet, in which case there is
+            // nothing to signal
+            let Ok(Some(record)) = get(delete_link.link_add_address.clone(), GetOptions::default())
+            else {
+                return Ok(());
+            };
+            let Action::CreateLink(create_link) = record.action() else {
+                return Ok(());
+            };
+            if let Ok(Some(link_type)) =
+                LinkTypes::from_type(create_link.zome_index, create_link.link_type)
+            {
+                let event = match (
+                    &link_type,
+                    create_link.target_address.clone().into_entry_hash(),
+                ) {
+                    (LinkTypes::AnchorToApplet, Some(applet_hash)) => {
+                        Some(GroupEvent::AppletArchived { applet_hash })
+                    }
+                    _ => None,
+                };
+                emit_signal(Signal::LinkDeleted { action, link_type })?;
+                if let Some(event) = event {
+                    emit_signal(Signal::LocalEvent { event })?;
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTypes>> {
+    let record = match get_details(action_hash.clone(), GetOptions::default())? {
+        Some(Details::Record(record_details)) => record_details.record,
+        _ => {
+            return Ok(None);
+        }
+    };
+    let entry = match record.entry().as_option() {
+        Some(entry) => entry,
+        None => {
+            return Ok(None);
+        }
+    };
+    let (zome_index, entry_index) = match record.action().entry_type() {
+        Some(EntryType::App(AppEntryDef {
+            zome_index,
+            entry_index,
+            ..
+        })) => (zome_index, entry_index),
+        _ => {
+            return Ok(None);
+        }
+    };
+    EntryTypes::deserialize_from_type(zome_index.clone(), entry_index.clone(), entry)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NotifyPeersInput {
+    pub event: GroupEvent,
+    /// The peers to notify, typically the ones that peer_status reports as online
+    pub peers: Vec<AgentPubKey>,
+}
+
+/// Sends a GroupEvent to the given peers as a remote signal
+#[hdk_extern]
+pub fn notify_peers(input: NotifyPeersInput) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let peers: Vec<AgentPubKey> = input
+        .peers
+        .into_iter()
+        .filter(|peer| peer.ne(&my_pub_key))
+        .collect();
+    if peers.is_empty() {
+        return Ok(());
+    }
+    remote_signal(input.event, peers)
+}
+
+#[hdk_extern]
+pub fn recv_remote_signal(event: GroupEvent) -> ExternResult<()> {
+    emit_signal(Signal::RemoteEvent {
+        from_agent: call_info()?.provenance,
+        event,
+    })
+}
@@ -0,0 +1,328 @@
+use group_integrity::*;
+use hdk::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GrantStewardRoleInput {
+    pub steward: AgentPubKey,
+    pub expiry: Option<Timestamp>,
+}
+
+/// A StewardGrant together with its revocation, if it has been revoked
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StewardGrantInfo {
+    pub steward_grant_record: Record,
+    pub revocation_record: Option<Record>,
+}
+
+impl StewardGrantInfo {
+    fn steward_grant(&self) -> ExternResult<StewardGrant> {
+        self.steward_grant_record
+            .entry()
+            .to_app_option::<StewardGrant>()
+            .map_err(|e| wasm_error!(e))?
+            .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+                "Record is not a StewardGrant"
+            ))))
+    }
+
+    fn is_active(&self, now: Timestamp) -> ExternResult<bool> {
+        if self.revocation_record.is_some() {
+            return Ok(false);
+        }
+        match self.steward_grant()?.expiry {
+            Some(expiry) => Ok(expiry > now),
+            None => Ok(true),
+        }
+    }
+}
+
+#[hdk_extern]
+pub fn get_progenitor(_: ()) -> ExternResult<Option<AgentPubKey>> {
+    progenitor()
+}
+
+/// Grants the steward role to the given agent. Only the progenitor can do this.
+#[hdk_extern]
+pub fn grant_steward_role(input: GrantStewardRoleInput) -> ExternResult<ActionHash> {
+    let action_hash = create_entry(EntryTypes::StewardGrant(StewardGrant {
+        steward: input.steward.clone(),
+        expiry: input.expiry,
+    }))?;
+
+    create_link(
+        steward_grants_path().path_entry_hash()?,
+        action_hash.clone(),
+        LinkTypes::AnchorToStewardGrant,
+        (),
+    )?;
+    create_link(
+        input.steward,
+        action_hash.clone(),
+        LinkTypes::AgentToStewardGrant,
+        (),
+    )?;
+
+    Ok(action_hash)
+}
+
+/// Revokes the given StewardGrant. Only the progenitor can do this.
+#[hdk_extern]
+pub fn revoke_steward_role(steward_grant_hash: ActionHash) -> ExternResult<ActionHash> {
+    let action_hash = create_entry(EntryTypes::StewardRevocation(StewardRevocation {
+        steward_grant_hash: steward_grant_hash.clone(),
+    }))?;
+
+    create_link(
+        steward_grant_hash,
+        action_hash.clone(),
+        LinkTypes::StewardGrantToRevocation,
+        (),
+    )?;
+
+    Ok(action_hash)
+}
+
+/// Gets the agents that currently hold a steward role, i.e. that have a StewardGrant
+/// that is neither revoked nor expired. The progenitor is not included.
+#[hdk_extern]
+pub fn get_stewards(_: ()) -> ExternResult<Vec<AgentPubKey>> {
+    let now = sys_time()?;
+    let mut stewards: Vec<AgentPubKey> = Vec::new();
+
+    for info in get_steward_grant_history(())? {
+        if info.is_active(now)? {
+            let steward = info.steward_grant()?.steward;
+            if !stewards.contains(&steward) {
+                stewards.push(steward);
+            }
+        }
+    }
+
+    Ok(stewards)
+}
+
+/// Gets all StewardGrants that have ever been created in this group, oldest first
+#[hdk_extern]
+pub fn get_steward_grant_history(_: ()) -> ExternResult<Vec<StewardGrantInfo>> {
+    get_steward_grant_infos(
+        steward_grants_path().path_entry_hash()?.into(),
+        LinkTypes::AnchorToStewardGrant,
+    )
+}
+
+/// Gets all StewardGrants that have ever been created for the given agent, oldest first
+#[hdk_extern]
+pub fn get_steward_grants_for_agent(agent: AgentPubKey) -> ExternResult<Vec<StewardGrantInfo>> {
+    get_steward_grant_infos(agent.into(), LinkTypes::AgentToStewardGrant)
+}
+
+fn get_steward_grant_infos(
+    base: AnyLinkableHash,
+    link_type: LinkTypes,
+) -> ExternResult<Vec<StewardGrantInfo>> {
+    let links = get_links(base, link_type, None)?;
+
+    let get_input: Vec<GetInput> = links
+        .into_iter()
+        .filter_map(|link| link.target.into_action_hash())
+        .map(|action_hash| GetInput::new(action_hash.into(), GetOptions::default()))
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+    let mut records: Vec<Record> = records.into_iter().filter_map(|r| r).collect();
+    records.sort_by_key(|record| record.action().timestamp());
+
+    let mut infos: Vec<StewardGrantInfo> = Vec::new();
+    for steward_grant_record in records {
+        let revocation_links = get_links(
+            steward_grant_record.action_address().clone(),
+            LinkTypes::StewardGrantToRevocation,
+            None,
+        )?;
+        let revocation_record = match revocation_links
+            .into_iter()
+            .filter_map(|link| link.target.into_action_hash())
+            .next()
+        {
+            Some(revocation_hash) => get(revocation_hash, GetOptions::default())?,
+            None => None,
+        };
+        infos.push(StewardGrantInfo {
+            steward_grant_record,
+            revocation_record,
+        });
+    }
+
+    Ok(infos)
+}
+
+/// Gets the latest action of the given agent that we know of
+pub fn agent_chain_top(agent: AgentPubKey) -> ExternResult<ActionHash> {
+    let activity = get_agent_activity(agent, ChainQueryFilter::new(), ActivityRequest::Full)?;
+    activity
+        .valid_activity
+        .into_iter()
+        .max_by_key(|(action_seq, _action_hash)| *action_seq)
+        .map(|(_action_seq, action_hash)| action_hash)
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not find the source chain of the agent."
+        ))))
+}
+
+/// How long the steward permissions that we use for our own actions are attested for
+const STEWARD_ATTESTATION_LIFETIME_MICROS: i64 = 10 * 60 * 1_000_000;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttestStewardPermissionInput {
+    pub steward_grant_hash: ActionHash,
+    pub valid_until: Timestamp,
+}
+
+/// Called remotely by stewards on the progenitor: attests that their StewardGrant is
+/// active, signing our current chain top and until when they may use the permission
+#[hdk_extern]
+pub fn attest_steward_permission(
+    input: AttestStewardPermissionInput,
+) -> ExternResult<StewardPermission> {
+    let agent_info = agent_info()?;
+    if progenitor()?.ne(&Some(agent_info.agent_initial_pubkey.clone())) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Only the progenitor can attest steward permissions."
+        ))));
+    }
+    let now = sys_time()?;
+    if input.valid_until.as_micros()
+        > now
+            .as_micros()
+            .saturating_add(MAX_STEWARD_ATTESTATION_LIFETIME_MICROS)
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Steward permissions cannot be attested for that long."
+        ))));
+    }
+    let steward = call_info()?.provenance;
+    let mut is_active = false;
+    for info in get_steward_grants_for_agent(steward.clone())? {
+        if info.steward_grant_record.action_address().eq(&input.steward_grant_hash) {
+            is_active = info.is_active(input.valid_until)?;
+        }
+    }
+    if !is_active {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The StewardGrant is not active."
+        ))));
+    }
+
+    let attestation = StewardAttestation {
+        steward,
+        steward_grant_hash: input.steward_grant_hash.clone(),
+        progenitor_chain_top: agent_info.chain_head.0.clone(),
+        valid_until: input.valid_until,
+    };
+    let progenitor_signature = sign(agent_info.agent_initial_pubkey, attestation)?;
+    Ok(StewardPermission {
+        permission_hash: Some(input.steward_grant_hash),
+        progenitor_chain_top: Some(agent_info.chain_head.0),
+        valid_until: Some(input.valid_until),
+        progenitor_signature: Some(progenitor_signature),
+    })
+}
+
+/// The StewardGrant with which the calling agent currently holds the steward role, if
+/// any. None for the progenitor, who doesn't need one.
+fn my_active_steward_grant_hash() -> ExternResult<Option<ActionHash>> {
+    let now = sys_time()?;
+    for info in get_steward_grants_for_agent(agent_info()?.agent_initial_pubkey)?
+        .into_iter()
+        .rev()
+    {
+        if info.is_active(now)? {
+            return Ok(Some(info.steward_grant_record.action_address().clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the permission with which the calling agent can act as a steward until
+/// `valid_until`, or None if they are not a steward of this group. Stewards need the
+/// progenitor to be online to attest their permission.
+fn steward_permission_until(valid_until: Timestamp) -> ExternResult<Option<StewardPermission>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+
+    let progenitor = match progenitor()? {
+        None => return Ok(Some(StewardPermission::default())),
+        Some(progenitor) if progenitor.eq(&my_pub_key) => {
+            return Ok(Some(StewardPermission::default()))
+        }
+        Some(progenitor) => progenitor,
+    };
+
+    let Some(steward_grant_hash) = my_active_steward_grant_hash()? else {
+        return Ok(None);
+    };
+    let response = call_remote(
+        progenitor,
+        zome_info()?.name,
+        FunctionName::from("attest_steward_permission"),
+        None,
+        AttestStewardPermissionInput {
+            steward_grant_hash,
+            valid_until,
+        },
+    )?;
+    match response {
+        ZomeCallResponse::Ok(result) => Ok(Some(result.decode().map_err(|e| wasm_error!(e))?)),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "The progenitor could not attest our steward permission: {:?}",
+            response
+        )))),
+    }
+}
+
+/// Returns the permission with which the calling agent can act as a steward, or None
+/// if they are not a steward of this group
+pub fn my_steward_permission() -> ExternResult<Option<StewardPermission>> {
+    let valid_until = Timestamp::from_micros(
+        sys_time()?
+            .as_micros()
+            .saturating_add(STEWARD_ATTESTATION_LIFETIME_MICROS),
+    );
+    steward_permission_until(valid_until)
+}
+
+/// Like `my_steward_permission` but errors if the calling agent is not a steward
+pub fn ensure_steward_permission() -> ExternResult<StewardPermission> {
+    my_steward_permission()?.ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+        "Only stewards of the group are allowed to perform this action."
+    ))))
+}
+
+/// For links that stewards may create with or without their permission: non-stewards
+/// and stewards whose permission the progenitor can't attest right now create them
+/// without one
+pub fn optional_steward_permission() -> StewardPermission {
+    my_steward_permission().ok().flatten().unwrap_or_default()
+}
+
+/// Gets a permission that lasts as long as possible, e.g. for invitations that the
+/// invitee can only use while the permission of the inviter is valid
+#[hdk_extern]
+pub fn get_my_steward_permission(_: ()) -> ExternResult<Option<StewardPermission>> {
+    // Leave some leeway for the clock of the progenitor being behind ours
+    let valid_until = Timestamp::from_micros(
+        sys_time()?
+            .as_micros()
+            .saturating_add(MAX_STEWARD_ATTESTATION_LIFETIME_MICROS - 60 * 60 * 1_000_000),
+    );
+    steward_permission_until(valid_until)
+}
+
+/// Whether the calling agent is the progenitor or currently holds the steward role.
+/// Doesn't need the progenitor to be online.
+#[hdk_extern]
+pub fn am_i_steward(_: ()) -> ExternResult<bool> {
+    match progenitor()? {
+        None => Ok(true),
+        Some(progenitor) if progenitor.eq(&agent_info()?.agent_initial_pubkey) => Ok(true),
+        Some(_) => Ok(my_active_steward_grant_hash()?.is_some()),
+    }
+}
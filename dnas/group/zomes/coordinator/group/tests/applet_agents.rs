@@ -0,0 +1,76 @@
+use ::fixt::prelude::fixt;
+use std::collections::BTreeMap;
+
+use group_integrity::Applet;
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applet_agents_are_tracked_when_joining() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let applet = Applet {
+        custom_name: String::from("custom name"),
+        description: String::from("description"),
+        appstore_app_hash: fixt!(ActionHash),
+
+        devhub_dna_hash: fixt!(DnaHash),
+        devhub_happ_entry_action_hash: fixt!(ActionHash),
+        devhub_happ_release_hash: fixt!(ActionHash),
+        initial_devhub_gui_release_hash: Some(fixt!(ActionHash)),
+
+        network_seed: None,
+        properties: BTreeMap::new(), // Segmented by RoleName
+    };
+
+    // Alice adds the applet to the group
+    let applet_hash: EntryHash = conductors[0]
+        .call(&alice_zome, "register_applet", applet.clone())
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Bob joins it
+    let _: EntryHash = conductors[1]
+        .call(&bob_zome, "register_applet", applet.clone())
+        .await;
+    // Registering it again must not create duplicate links
+    let _: EntryHash = conductors[1]
+        .call(&bob_zome, "register_applet", applet)
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let applet_agents: Vec<(AgentPubKey, Timestamp)> = conductors[0]
+        .call(&alice_zome, "get_applet_agents", applet_hash.clone())
+        .await;
+
+    assert_eq!(applet_agents.len(), 2);
+    assert_eq!(applet_agents[0].0, alice.agent_pubkey().clone());
+    assert_eq!(applet_agents[1].0, bobbo.agent_pubkey().clone());
+    assert!(applet_agents[0].1 <= applet_agents[1].1);
+
+    let bobs_joined_applets: Vec<(EntryHash, Timestamp)> = conductors[1]
+        .call(&bob_zome, "get_my_joined_applets_with_timestamps", ())
+        .await;
+
+    assert_eq!(bobs_joined_applets.len(), 1);
+    assert_eq!(bobs_joined_applets[0].0, applet_hash);
+    assert_eq!(bobs_joined_applets[0].1, applet_agents[1].1);
+}
@@ -0,0 +1,88 @@
+use group_coordinator::applet_listings::{AppletListingPage, AppletSortKey, ListAppletsInput};
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+mod common;
+use common::sample_applet;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applet_listings_are_sorted_and_paginated() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let mut applet_hashes: Vec<EntryHash> = Vec::new();
+    for name in ["b applet", "c applet", "a applet"] {
+        let applet_hash: EntryHash = conductors[0]
+            .call(&alice_zome, "register_applet", sample_applet(name))
+            .await;
+        applet_hashes.push(applet_hash);
+    }
+
+    let _: () = conductors[0]
+        .call(&alice_zome, "archive_applet", applet_hashes[1].clone())
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Newest first, one per page
+    let page: AppletListingPage = conductors[1]
+        .call(
+            &bob_zome,
+            "list_group_applets",
+            ListAppletsInput {
+                descending: true,
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(page.total, 2);
+    assert_eq!(page.applets.len(), 1);
+    assert_eq!(page.applets[0].applet_hash, applet_hashes[2]);
+    assert_eq!(page.applets[0].registered_by, alice.agent_pubkey().clone());
+    assert!(!page.applets[0].archived);
+
+    let page: AppletListingPage = conductors[1]
+        .call(
+            &bob_zome,
+            "list_unjoined_applets",
+            ListAppletsInput {
+                sort_by: AppletSortKey::Name,
+                ..Default::default()
+            },
+        )
+        .await;
+    let names: Vec<String> = page
+        .applets
+        .into_iter()
+        .map(|listing| listing.applet.custom_name)
+        .collect();
+    assert_eq!(names, vec![String::from("a applet"), String::from("b applet")]);
+
+    let page: AppletListingPage = conductors[1]
+        .call(&bob_zome, "list_archived_applets", ListAppletsInput::default())
+        .await;
+    assert_eq!(page.total, 1);
+    assert_eq!(page.applets[0].applet_hash, applet_hashes[1]);
+    assert!(page.applets[0].archived);
+
+    // Alice has joined all the applets she registered
+    let page: AppletListingPage = conductors[0]
+        .call(&alice_zome, "list_unjoined_applets", ListAppletsInput::default())
+        .await;
+    assert_eq!(page.total, 0);
+}
@@ -0,0 +1,167 @@
+use group_coordinator::applet_proposals::{
+    AppletProposalStatus, AppletProposalWithTally, VoteOnAppletProposalInput,
+};
+use group_integrity::{
+    AppletAddPolicy, AppletArchivePolicy, AppletProposalRules, GroupDnaProperties, GroupSettings,
+};
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+mod common;
+use common::sample_applet;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applets_need_to_be_accepted_by_a_quorum() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let alice_pubkey = SweetAgents::one(conductors[0].keystore()).await;
+
+    let properties = SerializedBytes::try_from(GroupDnaProperties {
+        progenitor: Some(alice_pubkey.clone().into()),
+        invite_only: false,
+    })
+    .unwrap();
+    let dna = dna.update_modifiers(DnaModifiersOpt::none().with_properties(properties));
+
+    let alice_app = conductors[0]
+        .setup_app_for_agent("we", alice_pubkey, &[dna.clone()])
+        .await
+        .unwrap();
+    let bob_app = conductors[1].setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let (alice,) = alice_app.into_tuple();
+    let (bob,) = bob_app.into_tuple();
+    let alice_zome = alice.zome("group");
+    let bob_zome = bob.zome("group");
+
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "set_group_settings",
+            GroupSettings {
+                applet_add_policy: AppletAddPolicy::Anyone,
+                applet_archive_policy: AppletArchivePolicy::RegistrantOrProgenitor,
+                applet_proposal_rules: Some(AppletProposalRules {
+                    quorum: 2,
+                    voting_period_seconds: 30,
+                }),
+                previous_group_settings_hash: None,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    // Applets can't be registered directly anymore
+    let result: Result<EntryHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "register_applet", sample_applet("unvoted applet"))
+        .await;
+    assert!(result.is_err());
+
+    let proposal_hash: ActionHash = conductors[1]
+        .call(&bob_zome, "propose_applet", sample_applet("proposed applet"))
+        .await;
+    let _: ActionHash = conductors[1]
+        .call(
+            &bob_zome,
+            "vote_on_applet_proposal",
+            VoteOnAppletProposalInput {
+                proposal_hash: proposal_hash.clone(),
+                approve: true,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let proposals: Vec<AppletProposalWithTally> = conductors[0]
+        .call(&alice_zome, "get_open_applet_proposals", ())
+        .await;
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].proposal_hash, proposal_hash);
+    assert_eq!(proposals[0].quorum, 2);
+    assert_eq!(proposals[0].approvals, 1);
+    assert_eq!(proposals[0].my_vote, None);
+
+    // One approval is not enough
+    let result: Result<EntryHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "accept_applet_proposal", proposal_hash.clone())
+        .await;
+    assert!(result.is_err());
+
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "vote_on_applet_proposal",
+            VoteOnAppletProposalInput {
+                proposal_hash: proposal_hash.clone(),
+                approve: true,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let group_applets: Vec<EntryHash> = conductors[1]
+        .call(&bob_zome, "get_group_applets", ())
+        .await;
+    assert!(group_applets.is_empty());
+
+    // The quorum is reached, but the proposal can't be accepted before voting closes
+    let result: Result<EntryHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "accept_applet_proposal", proposal_hash.clone())
+        .await;
+    assert!(result.is_err());
+
+    let voting_closes_at = proposals[0].voting_closes_at.as_micros();
+    let now = Timestamp::now().as_micros();
+    if voting_closes_at > now {
+        tokio::time::sleep(std::time::Duration::from_micros(
+            (voting_closes_at - now) as u64 + 1_000_000,
+        ))
+        .await;
+    }
+
+    // Alice's vote only counts once she has been active after voting closed
+    let result: Result<EntryHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "accept_applet_proposal", proposal_hash.clone())
+        .await;
+    assert!(result.is_err());
+
+    let other_proposal_hash: ActionHash = conductors[0]
+        .call(&alice_zome, "propose_applet", sample_applet("other applet"))
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let applet_hash: EntryHash = conductors[1]
+        .call(&bob_zome, "accept_applet_proposal", proposal_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let group_applets: Vec<EntryHash> = conductors[0]
+        .call(&alice_zome, "get_group_applets", ())
+        .await;
+    assert_eq!(group_applets, vec![applet_hash]);
+
+    let proposal: Option<AppletProposalWithTally> = conductors[0]
+        .call(&alice_zome, "get_applet_proposal", proposal_hash)
+        .await;
+    let proposal = proposal.unwrap();
+    assert_eq!(proposal.status, AppletProposalStatus::Accepted);
+    assert_eq!(proposal.my_vote, Some(true));
+
+    let proposals: Vec<AppletProposalWithTally> = conductors[0]
+        .call(&alice_zome, "get_open_applet_proposals", ())
+        .await;
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].proposal_hash, other_proposal_hash);
+}
@@ -0,0 +1,168 @@
+use group_coordinator::applet_listings::AppletListing;
+use group_coordinator::applet_tags::{AppletTags, SearchAppletsInput, SetAppletTagsInput};
+use group_integrity::Applet;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+mod common;
+
+fn sample_applet(custom_name: &str, description: &str) -> Applet {
+    Applet {
+        description: String::from(description),
+        ..common::sample_applet(custom_name)
+    }
+}
+
+fn applet_hashes(listings: Vec<AppletListing>) -> Vec<EntryHash> {
+    listings.into_iter().map(|listing| listing.applet_hash).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tag_and_search_applets() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let chat_hash: EntryHash = conductors[0]
+        .call(
+            &alice_zome,
+            "register_applet",
+            sample_applet("Chat", "Talk to each other"),
+        )
+        .await;
+    let notes_hash: EntryHash = conductors[0]
+        .call(
+            &alice_zome,
+            "register_applet",
+            sample_applet("Notes", "Collaborative documents"),
+        )
+        .await;
+
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "set_applet_tags",
+            SetAppletTagsInput {
+                applet_hash: chat_hash.clone(),
+                tags: vec![String::from("Messaging "), String::from("realtime")],
+                categories: vec![String::from("communication")],
+            },
+        )
+        .await;
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "set_applet_tags",
+            SetAppletTagsInput {
+                applet_hash: notes_hash.clone(),
+                tags: vec![String::from("realtime")],
+                categories: vec![String::from("productivity")],
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let tags: AppletTags = conductors[1]
+        .call(&bob_zome, "get_applet_tags", chat_hash.clone())
+        .await;
+    assert_eq!(
+        tags,
+        AppletTags {
+            tags: vec![String::from("messaging"), String::from("realtime")],
+            categories: vec![String::from("communication")],
+        }
+    );
+
+    let results: Vec<AppletListing> = conductors[1]
+        .call(
+            &bob_zome,
+            "search_group_applets",
+            SearchAppletsInput {
+                tag: Some(String::from("Realtime")),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(
+        applet_hashes(results),
+        vec![chat_hash.clone(), notes_hash.clone()]
+    );
+
+    let results: Vec<AppletListing> = conductors[1]
+        .call(
+            &bob_zome,
+            "search_group_applets",
+            SearchAppletsInput {
+                tag: Some(String::from("realtime")),
+                category: Some(String::from("productivity")),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(applet_hashes(results), vec![notes_hash.clone()]);
+
+    let results: Vec<AppletListing> = conductors[1]
+        .call(
+            &bob_zome,
+            "search_group_applets",
+            SearchAppletsInput {
+                query: Some(String::from("DOCUMENTS")),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(applet_hashes(results), vec![notes_hash.clone()]);
+
+    // Editing the tags keeps the Applet hash
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "set_applet_tags",
+            SetAppletTagsInput {
+                applet_hash: chat_hash.clone(),
+                tags: vec![String::from("messaging")],
+                categories: vec![String::from("communication")],
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let results: Vec<AppletListing> = conductors[1]
+        .call(
+            &bob_zome,
+            "search_group_applets",
+            SearchAppletsInput {
+                tag: Some(String::from("realtime")),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(applet_hashes(results), vec![notes_hash]);
+
+    let results: Vec<AppletListing> = conductors[1]
+        .call(
+            &bob_zome,
+            "search_group_applets",
+            SearchAppletsInput {
+                query: Some(String::from("messag")),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(applet_hashes(results), vec![chat_hash]);
+}
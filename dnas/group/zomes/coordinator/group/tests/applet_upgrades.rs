@@ -0,0 +1,105 @@
+use ::fixt::prelude::fixt;
+
+use group_coordinator::applet_upgrades::{AppletUpgrade, ProposeAppletUpgradeInput};
+use group_integrity::Applet;
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+mod common;
+
+fn sample_applet(happ_release_hash: ActionHash) -> Applet {
+    Applet {
+        devhub_happ_release_hash: happ_release_hash,
+        ..common::sample_applet("custom name")
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applet_can_be_superseded_by_successor() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let old_applet_hash: EntryHash = conductors[0]
+        .call(&alice_zome, "register_applet", sample_applet(fixt!(ActionHash)))
+        .await;
+    let new_applet_hash: EntryHash = conductors[0]
+        .call(&alice_zome, "register_applet", sample_applet(fixt!(ActionHash)))
+        .await;
+
+    // An applet cannot supersede itself
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "propose_applet_upgrade",
+            ProposeAppletUpgradeInput {
+                predecessor_hash: old_applet_hash.clone(),
+                successor_hash: old_applet_hash.clone(),
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "propose_applet_upgrade",
+            ProposeAppletUpgradeInput {
+                predecessor_hash: old_applet_hash.clone(),
+                successor_hash: new_applet_hash.clone(),
+            },
+        )
+        .await;
+
+    // Upgrades cannot loop back onto themselves
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "propose_applet_upgrade",
+            ProposeAppletUpgradeInput {
+                predecessor_hash: new_applet_hash.clone(),
+                successor_hash: old_applet_hash.clone(),
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let successors: Vec<AppletUpgrade> = conductors[1]
+        .call(&bob_zome, "get_applet_successors", old_applet_hash.clone())
+        .await;
+    assert_eq!(successors.len(), 1);
+    assert_eq!(successors[0].successor_hash, new_applet_hash);
+    assert_eq!(successors[0].proposed_by, alice.agent_pubkey().clone());
+
+    let predecessors: Vec<AppletUpgrade> = conductors[1]
+        .call(&bob_zome, "get_applet_predecessors", new_applet_hash.clone())
+        .await;
+    assert_eq!(predecessors, successors);
+
+    let latest: EntryHash = conductors[1]
+        .call(&bob_zome, "get_latest_applet_version", old_applet_hash.clone())
+        .await;
+    assert_eq!(latest, new_applet_hash);
+
+    // The old applet stays part of the group
+    let group_applets: Vec<EntryHash> = conductors[1]
+        .call(&bob_zome, "get_group_applets", ())
+        .await;
+    assert!(group_applets.contains(&old_applet_hash));
+}
@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+
+use ::fixt::prelude::fixt;
+use std::collections::BTreeMap;
+
+use group_integrity::{Applet, GroupDnaProperties, GroupProfile};
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+pub fn sample_applet(custom_name: &str) -> Applet {
+    Applet {
+        custom_name: String::from(custom_name),
+        description: String::from("description"),
+        appstore_app_hash: fixt!(ActionHash),
+
+        devhub_dna_hash: fixt!(DnaHash),
+        devhub_happ_entry_action_hash: fixt!(ActionHash),
+        devhub_happ_release_hash: fixt!(ActionHash),
+        initial_devhub_gui_release_hash: Some(fixt!(ActionHash)),
+
+        network_seed: None,
+        properties: BTreeMap::new(), // Segmented by RoleName
+    }
+}
+
+pub fn sample_group_profile(name: &str) -> GroupProfile {
+    GroupProfile {
+        name: String::from(name),
+        logo_src: String::from("data:image/png;base64,iVBORw0KGgo="),
+        description: String::from("description"),
+        banner_src: None,
+        tags: vec![String::from("tag")],
+        logo_blob_hash: None,
+    }
+}
+
+/// Sets up a group DNA with alice as the progenitor and bob as a regular member
+pub async fn setup_group_with_progenitor() -> (SweetConductorBatch, SweetCell, SweetCell) {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let alice_pubkey = SweetAgents::one(conductors[0].keystore()).await;
+
+    let properties = SerializedBytes::try_from(GroupDnaProperties {
+        progenitor: Some(alice_pubkey.clone().into()),
+        invite_only: false,
+    })
+    .unwrap();
+    let dna = dna.update_modifiers(DnaModifiersOpt::none().with_properties(properties));
+
+    let alice_app = conductors[0]
+        .setup_app_for_agent("we", alice_pubkey, &[dna.clone()])
+        .await
+        .unwrap();
+    let bob_app = conductors[1].setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let (alice,) = alice_app.into_tuple();
+    let (bob,) = bob_app.into_tuple();
+
+    (conductors, alice, bob)
+}
@@ -0,0 +1,132 @@
+use ::fixt::prelude::fixt;
+use std::collections::BTreeMap;
+
+use group_coordinator::applets::{
+    AcceptAppletFederationInput, ExternalAgent, ProposeAppletFederationInput,
+    RegisterExternalAgentInput,
+};
+use group_integrity::{Applet, RegisterAppletFederationInput, SignedAppletFederationAcceptance};
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn external_agents_must_come_from_federated_groups() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+    let federated_group_dna = dna.clone().update_modifiers(
+        DnaModifiersOpt::none().with_network_seed(String::from("federated group")),
+    );
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors
+        .setup_app("we", &[dna, federated_group_dna.clone()])
+        .await
+        .unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice, _alice_federated), (bobbo, bob_federated)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let applet = Applet {
+        custom_name: String::from("custom name"),
+        description: String::from("description"),
+        appstore_app_hash: fixt!(ActionHash),
+
+        devhub_dna_hash: fixt!(DnaHash),
+        devhub_happ_entry_action_hash: fixt!(ActionHash),
+        devhub_happ_release_hash: fixt!(ActionHash),
+        initial_devhub_gui_release_hash: Some(fixt!(ActionHash)),
+
+        network_seed: None,
+        properties: BTreeMap::new(), // Segmented by RoleName
+    };
+
+    let applet_hash: EntryHash = conductors[0]
+        .call(&alice_zome, "register_applet", applet.clone())
+        .await;
+
+    let federated_group_dna_hash = federated_group_dna.dna_hash().clone();
+    let external_agent = fixt!(AgentPubKey);
+
+    // The applet is not federated yet
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "register_external_agent",
+            RegisterExternalAgentInput {
+                applet_hash: applet_hash.clone(),
+                agent: external_agent.clone(),
+                group_dna_hash: federated_group_dna_hash.clone(),
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    let proposal_hash: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "propose_applet_federation",
+            ProposeAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: federated_group_dna_hash.clone(),
+            },
+        )
+        .await;
+    let signed_acceptance: SignedAppletFederationAcceptance = conductors[1]
+        .call(
+            &bob_federated.zome("group"),
+            "accept_applet_federation",
+            AcceptAppletFederationInput {
+                applet,
+                proposal_hash: proposal_hash.clone(),
+                proposing_group_dna_hash: alice.cell_id().dna_hash().clone(),
+            },
+        )
+        .await;
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "register_applet_federation",
+            RegisterAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: federated_group_dna_hash.clone(),
+                proposal_hash,
+                signed_acceptance,
+            },
+        )
+        .await;
+
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "register_external_agent",
+            RegisterExternalAgentInput {
+                applet_hash: applet_hash.clone(),
+                agent: external_agent.clone(),
+                group_dna_hash: federated_group_dna_hash.clone(),
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let external_agents: Vec<ExternalAgent> = conductors[1]
+        .call(&bob_zome, "get_applet_external_agents", applet_hash)
+        .await;
+
+    assert_eq!(
+        external_agents,
+        vec![ExternalAgent {
+            agent: external_agent,
+            group_dna_hash: federated_group_dna_hash,
+        }]
+    );
+}
@@ -0,0 +1,158 @@
+use ::fixt::prelude::fixt;
+use std::collections::BTreeMap;
+
+use group_coordinator::applets::{AcceptAppletFederationInput, ProposeAppletFederationInput};
+use group_integrity::{Applet, RegisterAppletFederationInput, SignedAppletFederationAcceptance};
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn federation_requires_proposal_and_acceptance() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+    let group_a_dna = dna.clone().update_modifiers(
+        DnaModifiersOpt::none().with_network_seed(String::from("group a")),
+    );
+    let group_b_dna = dna.clone().update_modifiers(
+        DnaModifiersOpt::none().with_network_seed(String::from("group b")),
+    );
+
+    // Set up conductors, both agents are members of both groups
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors
+        .setup_app("we", &[group_a_dna.clone(), group_b_dna.clone()])
+        .await
+        .unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice_a, alice_b), (bob_a, bob_b)) = apps.into_tuples();
+
+    let applet = Applet {
+        custom_name: String::from("custom name"),
+        description: String::from("description"),
+        appstore_app_hash: fixt!(ActionHash),
+
+        devhub_dna_hash: fixt!(DnaHash),
+        devhub_happ_entry_action_hash: fixt!(ActionHash),
+        devhub_happ_release_hash: fixt!(ActionHash),
+        initial_devhub_gui_release_hash: Some(fixt!(ActionHash)),
+
+        network_seed: Some(String::from("applet network seed")),
+        properties: BTreeMap::new(), // Segmented by RoleName
+    };
+
+    let applet_hash: EntryHash = conductors[0]
+        .call(&alice_a.zome("group"), "register_applet", applet.clone())
+        .await;
+
+    let proposal_hash: ActionHash = conductors[0]
+        .call(
+            &alice_a.zome("group"),
+            "propose_applet_federation",
+            ProposeAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: group_b_dna.dna_hash().clone(),
+            },
+        )
+        .await;
+
+    // Bob accepts the proposal in group B
+    let signed_acceptance: SignedAppletFederationAcceptance = conductors[1]
+        .call(
+            &bob_b.zome("group"),
+            "accept_applet_federation",
+            AcceptAppletFederationInput {
+                applet: applet.clone(),
+                proposal_hash: proposal_hash.clone(),
+                proposing_group_dna_hash: group_a_dna.dna_hash().clone(),
+            },
+        )
+        .await;
+    assert_eq!(signed_acceptance.accepting_agent, bob_b.agent_pubkey().clone());
+
+    // The proposal can't be used to federate with a group that it wasn't made for
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_a.zome("group"),
+            "register_applet_federation",
+            RegisterAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: fixt!(DnaHash),
+                proposal_hash: proposal_hash.clone(),
+                signed_acceptance: signed_acceptance.clone(),
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Alice can't register the federation with an acceptance that bob didn't sign
+    let mut forged_acceptance = signed_acceptance.clone();
+    forged_acceptance.accepting_agent = alice_a.agent_pubkey().clone();
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_a.zome("group"),
+            "register_applet_federation",
+            RegisterAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: group_b_dna.dna_hash().clone(),
+                proposal_hash: proposal_hash.clone(),
+                signed_acceptance: forged_acceptance,
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Nor with an acceptance that was made for another group
+    let mut forged_acceptance = signed_acceptance.clone();
+    forged_acceptance.acceptance.accepting_group_dna_hash = fixt!(DnaHash);
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_a.zome("group"),
+            "register_applet_federation",
+            RegisterAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: group_b_dna.dna_hash().clone(),
+                proposal_hash: proposal_hash.clone(),
+                signed_acceptance: forged_acceptance,
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_a.zome("group"),
+            "register_applet_federation",
+            RegisterAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: group_b_dna.dna_hash().clone(),
+                proposal_hash,
+                signed_acceptance,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice_a, &bob_a]).await;
+    consistency_10s([&alice_b, &bob_b]).await;
+
+    let federated_groups_a: Vec<DnaHash> = conductors[1]
+        .call(&bob_a.zome("group"), "get_federated_groups", applet_hash.clone())
+        .await;
+    assert_eq!(federated_groups_a, vec![group_b_dna.dna_hash().clone()]);
+
+    let federated_groups_b: Vec<DnaHash> = conductors[1]
+        .call(&bob_b.zome("group"), "get_federated_groups", applet_hash.clone())
+        .await;
+    assert_eq!(federated_groups_b, vec![group_a_dna.dna_hash().clone()]);
+
+    // The applet is part of group B with the same hash, i.e. the same network seed and properties
+    let group_b_applets: Vec<EntryHash> = conductors[1]
+        .call(&bob_b.zome("group"), "get_group_applets", ())
+        .await;
+    assert_eq!(group_b_applets, vec![applet_hash]);
+}
@@ -0,0 +1,96 @@
+use group_coordinator::GroupProfileVersion;
+use group_integrity::GroupProfile;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+mod common;
+use common::sample_group_profile;
+
+/// The GroupProfile as it was before description, banner and tags were added
+#[derive(Serialize, Deserialize, SerializedBytes, Debug)]
+struct LegacyGroupProfile {
+    name: String,
+    logo_src: String,
+}
+
+#[test]
+fn legacy_group_profiles_can_be_deserialized() {
+    let legacy = LegacyGroupProfile {
+        name: String::from("group name"),
+        logo_src: String::from("data:image/png;base64,iVBORw0KGgo="),
+    };
+    let bytes = SerializedBytes::try_from(legacy).unwrap();
+
+    let group_profile = GroupProfile::try_from(bytes).unwrap();
+    assert_eq!(group_profile.name, "group name");
+    assert_eq!(group_profile.description, "");
+    assert_eq!(group_profile.banner_src, None);
+    assert!(group_profile.tags.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn group_profile_history_and_rollback() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let _: () = conductors[0]
+        .call(&alice_zome, "set_group_profile", sample_group_profile("first"))
+        .await;
+    let _: () = conductors[0]
+        .call(&alice_zome, "set_group_profile", sample_group_profile("second"))
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // The latest profile is returned, not the oldest one
+    let record: Option<Record> = conductors[1]
+        .call(&bob_zome, "get_group_profile", ())
+        .await;
+    let group_profile: GroupProfile = record.unwrap().entry().to_app_option().unwrap().unwrap();
+    assert_eq!(group_profile.name, "second");
+
+    let history: Vec<GroupProfileVersion> = conductors[1]
+        .call(&bob_zome, "get_group_profile_history", ())
+        .await;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].group_profile.name, "first");
+    assert_eq!(history[1].group_profile.name, "second");
+    assert_eq!(history[0].author, alice.agent_pubkey().clone());
+    assert!(history[0].timestamp <= history[1].timestamp);
+
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "rollback_group_profile",
+            history[0].group_profile_hash.clone(),
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let record: Option<Record> = conductors[1]
+        .call(&bob_zome, "get_group_profile", ())
+        .await;
+    let group_profile: GroupProfile = record.unwrap().entry().to_app_option().unwrap().unwrap();
+    assert_eq!(group_profile.name, "first");
+
+    let history: Vec<GroupProfileVersion> = conductors[1]
+        .call(&bob_zome, "get_group_profile_history", ())
+        .await;
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[2].group_profile_hash, history[0].group_profile_hash);
+}
@@ -0,0 +1,87 @@
+use group_integrity::{AppletAddPolicy, AppletArchivePolicy, GroupSettings};
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use holochain::test_utils::consistency_10s;
+
+mod common;
+use common::{sample_applet, setup_group_with_progenitor};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn group_settings_govern_who_may_add_and_archive_applets() {
+    let (conductors, alice, bob) = setup_group_with_progenitor().await;
+    let alice_zome = alice.zome("group");
+    let bob_zome = bob.zome("group");
+
+    // Without settings anyone may add applets
+    let bobs_applet_hash: EntryHash = conductors[1]
+        .call(&bob_zome, "register_applet", sample_applet("bob's applet"))
+        .await;
+
+    let curated = GroupSettings {
+        applet_add_policy: AppletAddPolicy::ProgenitorOnly,
+        applet_archive_policy: AppletArchivePolicy::ProgenitorOnly,
+        applet_proposal_rules: None,
+        previous_group_settings_hash: None,
+    };
+
+    // Only the progenitor can change the settings
+    let result: Result<ActionHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "set_group_settings", curated.clone())
+        .await;
+    assert!(result.is_err());
+
+    let curated_hash: ActionHash = conductors[0]
+        .call(&alice_zome, "set_group_settings", curated.clone())
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let group_settings: GroupSettings = conductors[1]
+        .call(&bob_zome, "get_group_settings", ())
+        .await;
+    assert_eq!(group_settings, curated);
+
+    let result: Result<EntryHash, _> = conductors[1]
+        .call_fallible(&bob_zome, "register_applet", sample_applet("another applet"))
+        .await;
+    assert!(result.is_err());
+
+    let applet_hash: EntryHash = conductors[0]
+        .call(&alice_zome, "register_applet", sample_applet("curated applet"))
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let group_applets: Vec<EntryHash> = conductors[1]
+        .call(&bob_zome, "get_group_applets", ())
+        .await;
+    assert!(group_applets.contains(&applet_hash));
+
+    // The progenitor can archive any applet
+    let _: () = conductors[0]
+        .call(&alice_zome, "archive_applet", bobs_applet_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let archived_applets: Vec<EntryHash> = conductors[1]
+        .call(&bob_zome, "get_archived_applets", ())
+        .await;
+    assert_eq!(archived_applets, vec![bobs_applet_hash]);
+
+    // New settings replace the latest ones, after which anyone may add applets again
+    let _: ActionHash = conductors[0]
+        .call(&alice_zome, "set_group_settings", GroupSettings::default())
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let group_settings: GroupSettings = conductors[1]
+        .call(&bob_zome, "get_group_settings", ())
+        .await;
+    assert_eq!(group_settings.previous_group_settings_hash, Some(curated_hash));
+
+    let _: EntryHash = conductors[1]
+        .call(&bob_zome, "register_applet", sample_applet("yet another applet"))
+        .await;
+}
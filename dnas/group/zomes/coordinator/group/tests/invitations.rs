@@ -0,0 +1,374 @@
+use group_coordinator::membership::GroupMember;
+use group_coordinator::stewards::GrantStewardRoleInput;
+use group_integrity::{
+    GroupDnaProperties, GroupInvitation, SignedGroupInvitation, StewardPermission,
+};
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+/// Returns the group DNA as an invite-only group with the given progenitor
+async fn invite_only_group_dna(progenitor: &AgentPubKey) -> SweetDnaFile {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    let properties = SerializedBytes::try_from(GroupDnaProperties {
+        progenitor: Some(progenitor.clone().into()),
+        invite_only: true,
+    })
+    .unwrap();
+    dna.update_modifiers(DnaModifiersOpt::none().with_properties(properties))
+}
+
+/// Signs the invitation with the key of the inviter, which must live on the given
+/// conductor, and returns it as membrane proof
+async fn sign_invitation(conductor: &SweetConductor, invitation: GroupInvitation) -> MembraneProof {
+    let invitation_bytes = SerializedBytes::try_from(invitation.clone()).unwrap();
+    let signature = conductor
+        .keystore()
+        .sign(
+            invitation.inviter.clone(),
+            invitation_bytes.bytes().to_vec().into(),
+        )
+        .await
+        .unwrap();
+    let membrane_proof = SerializedBytes::try_from(SignedGroupInvitation {
+        invitation,
+        signature,
+    })
+    .unwrap();
+    std::sync::Arc::new(membrane_proof)
+}
+
+/// Installs and enables the group DNA for the given agent with the given membrane proof
+async fn join_with_membrane_proof(
+    conductor: &SweetConductor,
+    dna: &SweetDnaFile,
+    agent: AgentPubKey,
+    membrane_proof: MembraneProof,
+) -> SweetCell {
+    conductor
+        .raw_handle()
+        .install_app_legacy(
+            String::from("we"),
+            agent.clone(),
+            &[(dna.clone(), Some(membrane_proof))],
+        )
+        .await
+        .unwrap();
+    conductor
+        .raw_handle()
+        .enable_app(String::from("we"))
+        .await
+        .unwrap();
+    conductor
+        .get_sweet_cell(CellId::new(dna.dna_hash().clone(), agent))
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cannot_join_invite_only_group_without_invitation() {
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let alice_pubkey = SweetAgents::one(conductors[0].keystore()).await;
+    let dna = invite_only_group_dna(&alice_pubkey).await;
+
+    // The progenitor does not need an invitation
+    conductors[0]
+        .setup_app_for_agent("we", alice_pubkey, &[dna.clone()])
+        .await
+        .unwrap();
+
+    let result = conductors[1].setup_app("we", &[dna]).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_join_invite_only_group_with_invitation_from_progenitor() {
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let alice_pubkey = SweetAgents::one(conductors[0].keystore()).await;
+    let bob_pubkey = SweetAgents::one(conductors[1].keystore()).await;
+    let dna = invite_only_group_dna(&alice_pubkey).await;
+
+    let alice_app = conductors[0]
+        .setup_app_for_agent("we", alice_pubkey.clone(), &[dna.clone()])
+        .await
+        .unwrap();
+
+    let invitation = GroupInvitation {
+        group_dna_hash: dna.dna_hash().clone(),
+        invitee: bob_pubkey.clone(),
+        inviter: alice_pubkey.clone(),
+        steward_grant_hash: None,
+        progenitor_chain_top: None,
+        valid_until: None,
+        progenitor_signature: None,
+        expiry: None,
+    };
+    let membrane_proof = sign_invitation(&conductors[0], invitation).await;
+    let bob = join_with_membrane_proof(&conductors[1], &dna, bob_pubkey, membrane_proof).await;
+    conductors.exchange_peer_info().await;
+
+    let (alice,) = alice_app.into_tuple();
+
+    consistency_10s([&alice, &bob]).await;
+
+    let progenitor: Option<AgentPubKey> = conductors[1]
+        .call(&bob.zome("group"), "get_progenitor", ())
+        .await;
+    assert_eq!(progenitor, Some(alice.agent_pubkey().clone()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cannot_join_invite_only_group_with_invitation_from_revoked_steward() {
+    let mut conductors = SweetConductorBatch::from_config(3, ConductorConfig::default()).await;
+    let alice_pubkey = SweetAgents::one(conductors[0].keystore()).await;
+    let bob_pubkey = SweetAgents::one(conductors[1].keystore()).await;
+    let carol_pubkey = SweetAgents::one(conductors[2].keystore()).await;
+    let dna = invite_only_group_dna(&alice_pubkey).await;
+
+    let alice_app = conductors[0]
+        .setup_app_for_agent("we", alice_pubkey.clone(), &[dna.clone()])
+        .await
+        .unwrap();
+    let (alice,) = alice_app.into_tuple();
+    let alice_zome = alice.zome("group");
+
+    // Bob joins with an invitation from the progenitor
+    let invitation = GroupInvitation {
+        group_dna_hash: dna.dna_hash().clone(),
+        invitee: bob_pubkey.clone(),
+        inviter: alice_pubkey.clone(),
+        steward_grant_hash: None,
+        progenitor_chain_top: None,
+        valid_until: None,
+        progenitor_signature: None,
+        expiry: None,
+    };
+    let membrane_proof = sign_invitation(&conductors[0], invitation).await;
+    let bob =
+        join_with_membrane_proof(&conductors[1], &dna, bob_pubkey.clone(), membrane_proof).await;
+    conductors.exchange_peer_info().await;
+
+    // Alice makes bob a steward and revokes the role again
+    let steward_grant_hash: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "grant_steward_role",
+            GrantStewardRoleInput {
+                steward: bob_pubkey.clone(),
+                expiry: None,
+            },
+        )
+        .await;
+    let revocation_hash: ActionHash = conductors[0]
+        .call(&alice_zome, "revoke_steward_role", steward_grant_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    // Bob still invites carol with the revoked StewardGrant. The signature is valid, so
+    // carol passes genesis, but her joining is rejected.
+    let invitation = GroupInvitation {
+        group_dna_hash: dna.dna_hash().clone(),
+        invitee: carol_pubkey.clone(),
+        inviter: bob_pubkey,
+        steward_grant_hash: Some(steward_grant_hash),
+        progenitor_chain_top: Some(revocation_hash),
+        valid_until: None,
+        progenitor_signature: None,
+        expiry: None,
+    };
+    let membrane_proof = sign_invitation(&conductors[1], invitation).await;
+    let carol =
+        join_with_membrane_proof(&conductors[2], &dna, carol_pubkey.clone(), membrane_proof).await;
+    conductors.exchange_peer_info().await;
+
+    let result: Result<Vec<GroupMember>, _> = conductors[2]
+        .call_fallible(&carol.zome("group"), "get_members", ())
+        .await;
+    assert!(result.is_err());
+
+    consistency_10s([&alice, &bob]).await;
+
+    let members: Vec<GroupMember> = conductors[0].call(&alice_zome, "get_members", ()).await;
+    assert!(members.iter().all(|member| member.agent.ne(&carol_pubkey)));
+}
+
+/// Sets up an invite-only group with alice as the progenitor, bob as a member whose
+/// steward role has been granted, and a third conductor for the invitee
+async fn setup_invite_only_group_with_steward(
+) -> (SweetConductorBatch, SweetDnaFile, SweetCell, SweetCell, ActionHash) {
+    let mut conductors = SweetConductorBatch::from_config(3, ConductorConfig::default()).await;
+    let alice_pubkey = SweetAgents::one(conductors[0].keystore()).await;
+    let bob_pubkey = SweetAgents::one(conductors[1].keystore()).await;
+    let dna = invite_only_group_dna(&alice_pubkey).await;
+
+    let alice_app = conductors[0]
+        .setup_app_for_agent("we", alice_pubkey.clone(), &[dna.clone()])
+        .await
+        .unwrap();
+    let (alice,) = alice_app.into_tuple();
+
+    let invitation = GroupInvitation {
+        group_dna_hash: dna.dna_hash().clone(),
+        invitee: bob_pubkey.clone(),
+        inviter: alice_pubkey,
+        steward_grant_hash: None,
+        progenitor_chain_top: None,
+        valid_until: None,
+        progenitor_signature: None,
+        expiry: None,
+    };
+    let membrane_proof = sign_invitation(&conductors[0], invitation).await;
+    let bob =
+        join_with_membrane_proof(&conductors[1], &dna, bob_pubkey.clone(), membrane_proof).await;
+    conductors.exchange_peer_info().await;
+
+    let steward_grant_hash: ActionHash = conductors[0]
+        .call(
+            &alice.zome("group"),
+            "grant_steward_role",
+            GrantStewardRoleInput {
+                steward: bob_pubkey,
+                expiry: None,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    (conductors, dna, alice, bob, steward_grant_hash)
+}
+
+/// Asserts that the agent with the given key on the third conductor can't join with the
+/// given invitation, signed by bob
+async fn assert_joining_is_rejected(
+    conductors: &SweetConductorBatch,
+    dna: &SweetDnaFile,
+    alice: &SweetCell,
+    invitation: GroupInvitation,
+) {
+    let invitee = invitation.invitee.clone();
+    let membrane_proof = sign_invitation(&conductors[1], invitation).await;
+    let carol = join_with_membrane_proof(&conductors[2], dna, invitee.clone(), membrane_proof).await;
+    conductors.exchange_peer_info().await;
+
+    let result: Result<Vec<GroupMember>, _> = conductors[2]
+        .call_fallible(&carol.zome("group"), "get_members", ())
+        .await;
+    assert!(result.is_err());
+
+    let members: Vec<GroupMember> = conductors[0]
+        .call(&alice.zome("group"), "get_members", ())
+        .await;
+    assert!(members.iter().all(|member| member.agent.ne(&invitee)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn revoked_steward_cannot_pass_the_grant_as_progenitor_chain_top() {
+    let (conductors, dna, alice, bob, steward_grant_hash) =
+        setup_invite_only_group_with_steward().await;
+    let carol_pubkey = SweetAgents::one(conductors[2].keystore()).await;
+
+    let _: ActionHash = conductors[0]
+        .call(&alice.zome("group"), "revoke_steward_role", steward_grant_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    // Bob can't get his permission attested anymore
+    let result: Result<Option<StewardPermission>, _> = conductors[1]
+        .call_fallible(&bob.zome("group"), "get_my_steward_permission", ())
+        .await;
+    assert!(!matches!(result, Ok(Some(_))));
+
+    // Invitations are the one place where a client chooses the steward permission
+    // freely. Referring to the grant as the chain top of the progenitor would skip the
+    // revocation, but without the attestation of the progenitor it is rejected.
+    let invitation = GroupInvitation {
+        group_dna_hash: dna.dna_hash().clone(),
+        invitee: carol_pubkey,
+        inviter: bob.agent_pubkey().clone(),
+        steward_grant_hash: Some(steward_grant_hash.clone()),
+        progenitor_chain_top: Some(steward_grant_hash),
+        valid_until: None,
+        progenitor_signature: None,
+        expiry: None,
+    };
+    assert_joining_is_rejected(&conductors, &dna, &alice, invitation).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_join_invite_only_group_with_invitation_from_attested_steward() {
+    let (conductors, dna, alice, bob, _steward_grant_hash) =
+        setup_invite_only_group_with_steward().await;
+    let carol_pubkey = SweetAgents::one(conductors[2].keystore()).await;
+
+    let permission: Option<StewardPermission> = conductors[1]
+        .call(&bob.zome("group"), "get_my_steward_permission", ())
+        .await;
+    let permission = permission.unwrap();
+
+    let invitation = GroupInvitation {
+        group_dna_hash: dna.dna_hash().clone(),
+        invitee: carol_pubkey.clone(),
+        inviter: bob.agent_pubkey().clone(),
+        steward_grant_hash: permission.permission_hash,
+        progenitor_chain_top: permission.progenitor_chain_top,
+        valid_until: permission.valid_until,
+        progenitor_signature: permission.progenitor_signature,
+        expiry: None,
+    };
+    let membrane_proof = sign_invitation(&conductors[1], invitation).await;
+    let carol =
+        join_with_membrane_proof(&conductors[2], &dna, carol_pubkey.clone(), membrane_proof).await;
+    conductors.exchange_peer_info().await;
+
+    let _: Vec<GroupMember> = conductors[2]
+        .call(&carol.zome("group"), "get_members", ())
+        .await;
+
+    consistency_10s([&alice, &bob, &carol]).await;
+
+    let members: Vec<GroupMember> = conductors[0]
+        .call(&alice.zome("group"), "get_members", ())
+        .await;
+    assert!(members.iter().any(|member| member.agent.eq(&carol_pubkey)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn inviter_cannot_reuse_an_attestation_with_the_grant_as_progenitor_chain_top() {
+    let (conductors, dna, alice, bob, steward_grant_hash) =
+        setup_invite_only_group_with_steward().await;
+    let carol_pubkey = SweetAgents::one(conductors[2].keystore()).await;
+
+    // Bob gets his permission attested while he is still a steward
+    let permission: Option<StewardPermission> = conductors[1]
+        .call(&bob.zome("group"), "get_my_steward_permission", ())
+        .await;
+    let permission = permission.unwrap();
+
+    let _: ActionHash = conductors[0]
+        .call(&alice.zome("group"), "revoke_steward_role", steward_grant_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    // The attestation hasn't expired yet, but bob signs the invitation himself and
+    // swaps the attested chain top for the grant, so that validators wouldn't find
+    // the revocation. The signature of the progenitor doesn't cover that chain top.
+    let invitation = GroupInvitation {
+        group_dna_hash: dna.dna_hash().clone(),
+        invitee: carol_pubkey,
+        inviter: bob.agent_pubkey().clone(),
+        steward_grant_hash: Some(steward_grant_hash.clone()),
+        progenitor_chain_top: Some(steward_grant_hash),
+        valid_until: permission.valid_until,
+        progenitor_signature: permission.progenitor_signature,
+        expiry: None,
+    };
+    assert_joining_is_rejected(&conductors, &dna, &alice, invitation).await;
+}
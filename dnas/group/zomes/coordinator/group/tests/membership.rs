@@ -0,0 +1,65 @@
+use group_coordinator::membership::GroupMember;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn members_are_recorded_on_init_and_can_leave() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    // The first zome call runs init, which adds the caller to the members
+    let _: Vec<GroupMember> = conductors[0].call(&alice_zome, "get_members", ()).await;
+    let _: Vec<GroupMember> = conductors[1].call(&bob_zome, "get_members", ()).await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let members: Vec<GroupMember> = conductors[0].call(&alice_zome, "get_members", ()).await;
+    let agents: Vec<AgentPubKey> = members.iter().map(|m| m.agent.clone()).collect();
+    assert_eq!(members.len(), 2);
+    assert!(agents.contains(alice.agent_pubkey()));
+    assert!(agents.contains(bobbo.agent_pubkey()));
+    assert!(members[0].joined_at <= members[1].joined_at);
+
+    let _: () = conductors[1].call(&bob_zome, "leave_group", ()).await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let members: Vec<GroupMember> = conductors[0].call(&alice_zome, "get_members", ()).await;
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].agent, alice.agent_pubkey().clone());
+
+    // Bob is not a member anymore so he cannot leave again
+    let result: Result<(), _> = conductors[1]
+        .call_fallible(&bob_zome, "leave_group", ())
+        .await;
+    assert!(result.is_err());
+
+    // Bob rejoins, e.g. because he enabled the group again
+    let _: () = conductors[1].call(&bob_zome, "rejoin_group", ()).await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Rejoining as a member is a no-op
+    let _: () = conductors[1].call(&bob_zome, "rejoin_group", ()).await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let members: Vec<GroupMember> = conductors[0].call(&alice_zome, "get_members", ()).await;
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[1].agent, bobbo.agent_pubkey().clone());
+    assert!(members[0].joined_at < members[1].joined_at);
+}
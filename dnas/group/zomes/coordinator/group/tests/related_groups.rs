@@ -0,0 +1,134 @@
+use ::fixt::prelude::fixt;
+
+use group_coordinator::related_groups::{RelatedGroupStatus, RelatedGroupWithStatus};
+use group_integrity::{GroupProfile, RelatedGroup};
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+fn sample_related_group(name: &str, network_seed: &str, group_dna_hash: DnaHash) -> RelatedGroup {
+    RelatedGroup {
+        group_profile: GroupProfile {
+            name: String::from(name),
+            logo_src: String::from("data:image/png;base64,iVBORw0KGgo="),
+            ..Default::default()
+        },
+        network_seed: String::from(network_seed),
+        group_dna_hash,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn related_groups_are_deduplicated_and_removable() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let group_dna_hash = fixt!(DnaHash);
+
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "add_related_group",
+            sample_related_group("related group", "related seed", group_dna_hash.clone()),
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Bob adds the same group with a different profile, which must not duplicate it
+    let _: () = conductors[1]
+        .call(
+            &bob_zome,
+            "add_related_group",
+            sample_related_group("renamed group", "related seed", group_dna_hash.clone()),
+        )
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let related_groups: Vec<RelatedGroupWithStatus> = conductors[1]
+        .call(&bob_zome, "get_related_groups_with_status", ())
+        .await;
+    assert_eq!(related_groups.len(), 1);
+    assert_eq!(related_groups[0].added_by, alice.agent_pubkey().clone());
+    assert_eq!(related_groups[0].status, RelatedGroupStatus::Unknown);
+
+    // Bob did not add the related group so he cannot remove it
+    let result: Result<(), _> = conductors[1]
+        .call_fallible(&bob_zome, "remove_related_group", group_dna_hash.clone())
+        .await;
+    assert!(result.is_err());
+
+    let _: () = conductors[0]
+        .call(&alice_zome, "remove_related_group", group_dna_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let related_groups: Vec<Record> = conductors[1]
+        .call(&bob_zome, "get_related_groups", ())
+        .await;
+    assert_eq!(related_groups.len(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mutual_related_groups_are_detected() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+    let parent_dna = dna.clone().update_modifiers(
+        DnaModifiersOpt::none().with_network_seed(String::from("parent")),
+    );
+    let child_dna = dna.clone().update_modifiers(
+        DnaModifiersOpt::none().with_network_seed(String::from("child")),
+    );
+
+    let mut conductor = SweetConductor::from_config(ConductorConfig::default()).await;
+    let app = conductor
+        .setup_app("we", &[parent_dna.clone(), child_dna.clone()])
+        .await
+        .unwrap();
+    let (parent, child) = app.into_tuple();
+
+    let _: () = conductor
+        .call(
+            &parent.zome("group"),
+            "add_related_group",
+            sample_related_group("child", "child", child_dna.dna_hash().clone()),
+        )
+        .await;
+
+    let related_groups: Vec<RelatedGroupWithStatus> = conductor
+        .call(&parent.zome("group"), "get_related_groups_with_status", ())
+        .await;
+    assert_eq!(related_groups[0].status, RelatedGroupStatus::OneSided);
+
+    let _: () = conductor
+        .call(
+            &child.zome("group"),
+            "add_related_group",
+            sample_related_group("parent", "parent", parent_dna.dna_hash().clone()),
+        )
+        .await;
+
+    let related_groups: Vec<RelatedGroupWithStatus> = conductor
+        .call(&parent.zome("group"), "get_related_groups_with_status", ())
+        .await;
+    assert_eq!(related_groups[0].status, RelatedGroupStatus::Mutual);
+}
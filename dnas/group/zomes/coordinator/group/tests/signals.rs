@@ -0,0 +1,103 @@
+use ::fixt::prelude::fixt;
+use futures::StreamExt;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use group_coordinator::signals::{GroupEvent, NotifyPeersInput, Signal as GroupSignal};
+use group_integrity::Applet;
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::conductor::api::Signal as ConductorSignal;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn peers_are_notified_of_registered_applets() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+
+    let mut alice_signals = conductors[0].signals().take(20);
+    let mut bob_signals = conductors[1].signals().take(20);
+
+    let applet = Applet {
+        custom_name: String::from("custom name"),
+        description: String::from("description"),
+        appstore_app_hash: fixt!(ActionHash),
+
+        devhub_dna_hash: fixt!(DnaHash),
+        devhub_happ_entry_action_hash: fixt!(ActionHash),
+        devhub_happ_release_hash: fixt!(ActionHash),
+        initial_devhub_gui_release_hash: Some(fixt!(ActionHash)),
+
+        network_seed: None,
+        properties: BTreeMap::new(), // Segmented by RoleName
+    };
+    let applet_hash: EntryHash = conductors[0]
+        .call(&alice_zome, "register_applet", applet)
+        .await;
+
+    // Alice's UI is told that she registered an applet
+    let local_event = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(signal) = alice_signals.next().await {
+            let ConductorSignal::App { signal, .. } = signal else {
+                continue;
+            };
+            if let Ok(GroupSignal::LocalEvent { event }) = signal.into_inner().decode() {
+                return Some(event);
+            }
+        }
+        None
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        local_event,
+        Some(GroupEvent::AppletRegistered {
+            applet_hash: applet_hash.clone()
+        })
+    );
+
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "notify_peers",
+            NotifyPeersInput {
+                event: local_event.unwrap(),
+                peers: vec![bobbo.agent_pubkey().clone()],
+            },
+        )
+        .await;
+
+    let remote_event = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(signal) = bob_signals.next().await {
+            let ConductorSignal::App { signal, .. } = signal else {
+                continue;
+            };
+            if let Ok(GroupSignal::RemoteEvent { from_agent, event }) = signal.into_inner().decode()
+            {
+                return Some((from_agent, event));
+            }
+        }
+        None
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        remote_event,
+        Some((
+            alice.agent_pubkey().clone(),
+            GroupEvent::AppletRegistered { applet_hash }
+        ))
+    );
+}
@@ -0,0 +1,114 @@
+use group_coordinator::stewards::GrantStewardRoleInput;
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use holochain::test_utils::consistency_10s;
+
+mod common;
+use common::{sample_group_profile, setup_group_with_progenitor};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn only_stewards_can_set_group_profile() {
+    let (conductors, alice, bob) = setup_group_with_progenitor().await;
+    let alice_zome = alice.zome("group");
+    let bob_zome = bob.zome("group");
+
+    let progenitor: Option<AgentPubKey> = conductors[1]
+        .call(&bob_zome, "get_progenitor", ())
+        .await;
+    assert_eq!(progenitor, Some(alice.agent_pubkey().clone()));
+
+    // The progenitor can always set the group profile
+    let _: () = conductors[0]
+        .call(&alice_zome, "set_group_profile", sample_group_profile("Group"))
+        .await;
+
+    // Bob is not a steward yet
+    let result: Result<(), _> = conductors[1]
+        .call_fallible(&bob_zome, "set_group_profile", sample_group_profile("Bob's group"))
+        .await;
+    assert!(result.is_err());
+
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "grant_steward_role",
+            GrantStewardRoleInput {
+                steward: bob.agent_pubkey().clone(),
+                expiry: None,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let stewards: Vec<AgentPubKey> = conductors[1].call(&bob_zome, "get_stewards", ()).await;
+    assert_eq!(stewards, vec![bob.agent_pubkey().clone()]);
+
+    let _: () = conductors[1]
+        .call(&bob_zome, "set_group_profile", sample_group_profile("Bob's group"))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn only_progenitor_can_grant_steward_role() {
+    let (conductors, alice, bob) = setup_group_with_progenitor().await;
+    let alice_zome = alice.zome("group");
+    let bob_zome = bob.zome("group");
+
+    let result: Result<ActionHash, _> = conductors[1]
+        .call_fallible(
+            &bob_zome,
+            "grant_steward_role",
+            GrantStewardRoleInput {
+                steward: bob.agent_pubkey().clone(),
+                expiry: None,
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    consistency_10s([&alice, &bob]).await;
+
+    let stewards: Vec<AgentPubKey> = conductors[0].call(&alice_zome, "get_stewards", ()).await;
+    assert_eq!(stewards.len(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn revoked_steward_loses_permissions() {
+    let (conductors, alice, bob) = setup_group_with_progenitor().await;
+    let alice_zome = alice.zome("group");
+    let bob_zome = bob.zome("group");
+
+    let steward_grant_hash: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "grant_steward_role",
+            GrantStewardRoleInput {
+                steward: bob.agent_pubkey().clone(),
+                expiry: None,
+            },
+        )
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let am_i_steward: bool = conductors[1].call(&bob_zome, "am_i_steward", ()).await;
+    assert!(am_i_steward);
+
+    let _: ActionHash = conductors[0]
+        .call(&alice_zome, "revoke_steward_role", steward_grant_hash)
+        .await;
+
+    consistency_10s([&alice, &bob]).await;
+
+    let am_i_steward: bool = conductors[1].call(&bob_zome, "am_i_steward", ()).await;
+    assert!(!am_i_steward);
+
+    let stewards: Vec<AgentPubKey> = conductors[1].call(&bob_zome, "get_stewards", ()).await;
+    assert_eq!(stewards.len(), 0);
+
+    let result: Result<(), _> = conductors[1]
+        .call_fallible(&bob_zome, "set_group_profile", sample_group_profile("Bob's group"))
+        .await;
+    assert!(result.is_err());
+}
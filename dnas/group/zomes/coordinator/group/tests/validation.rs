@@ -0,0 +1,174 @@
+use ::fixt::prelude::fixt;
+
+use group_integrity::RelatedGroup;
+use hdk::prelude::holo_hash::*;
+use hdk::prelude::*;
+use holochain::test_utils::consistency_10s;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+
+mod common;
+use common::{sample_applet, sample_group_profile};
+
+async fn setup_group_dna() -> (SweetConductor, SweetZome) {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    let mut conductor = SweetConductor::from_config(ConductorConfig::default()).await;
+    let app = conductor.setup_app("we", &[dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let group_zome = cell.zome("group");
+
+    (conductor, group_zome)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applet_with_empty_name_is_rejected() {
+    let (conductor, group_zome) = setup_group_dna().await;
+
+    let result: Result<EntryHash, _> = conductor
+        .call_fallible(&group_zome, "register_applet", sample_applet(""))
+        .await;
+    assert!(result.is_err());
+
+    let too_long_name = "a".repeat(group_integrity::MAX_APPLET_NAME_LENGTH + 1);
+    let result: Result<EntryHash, _> = conductor
+        .call_fallible(&group_zome, "register_applet", sample_applet(&too_long_name))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applet_with_empty_network_seed_is_rejected() {
+    let (conductor, group_zome) = setup_group_dna().await;
+
+    let mut applet = sample_applet("custom name");
+    applet.network_seed = Some(String::from(""));
+
+    let result: Result<EntryHash, _> = conductor
+        .call_fallible(&group_zome, "register_applet", applet)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn anchor_to_applet_link_must_target_an_applet() {
+    let (conductor, group_zome) = setup_group_dna().await;
+
+    let related_group = RelatedGroup {
+        group_profile: sample_group_profile("related group"),
+        network_seed: String::from("related network seed"),
+        group_dna_hash: fixt!(DnaHash),
+    };
+    let _: () = conductor
+        .call(&group_zome, "add_related_group", related_group)
+        .await;
+
+    let related_groups: Vec<Record> = conductor
+        .call(&group_zome, "get_related_groups", ())
+        .await;
+    let related_group_hash = related_groups[0].action().entry_hash().unwrap().clone();
+
+    // Linking a RelatedGroup entry from the group_applets anchor must fail
+    let result: Result<(), _> = conductor
+        .call_fallible(&group_zome, "unarchive_applet", related_group_hash)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applet_can_only_be_archived_by_its_registrant() {
+    // Use prebuilt DNA file
+    let dna_path = std::env::current_dir()
+        .unwrap()
+        .join("../../../workdir/group.dna");
+    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("we", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("group");
+    let bob_zome = bobbo.zome("group");
+
+    let applet_hash: EntryHash = conductors[0]
+        .call(&alice_zome, "register_applet", sample_applet("custom name"))
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    // Bob did not register the applet so deleting Alice's link must be rejected
+    let result: Result<(), _> = conductors[1]
+        .call_fallible(&bob_zome, "archive_applet", applet_hash.clone())
+        .await;
+    assert!(result.is_err());
+
+    let _: () = conductors[0]
+        .call(&alice_zome, "archive_applet", applet_hash.clone())
+        .await;
+
+    consistency_10s([&alice, &bobbo]).await;
+
+    let archived_applets: Vec<EntryHash> = conductors[1]
+        .call(&bob_zome, "get_archived_applets", ())
+        .await;
+    assert_eq!(archived_applets, vec![applet_hash]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_group_profile_is_rejected() {
+    let (conductor, group_zome) = setup_group_dna().await;
+
+    let result: Result<(), _> = conductor
+        .call_fallible(&group_zome, "set_group_profile", sample_group_profile(""))
+        .await;
+    assert!(result.is_err());
+
+    let mut group_profile = sample_group_profile("group name");
+    group_profile.logo_src = String::from("");
+    let result: Result<(), _> = conductor
+        .call_fallible(&group_zome, "set_group_profile", group_profile)
+        .await;
+    assert!(result.is_err());
+
+    let mut group_profile = sample_group_profile("group name");
+    group_profile.tags = vec![String::from("tag"), String::from("tag")];
+    let result: Result<(), _> = conductor
+        .call_fallible(&group_zome, "set_group_profile", group_profile)
+        .await;
+    assert!(result.is_err());
+
+    let _: () = conductor
+        .call(&group_zome, "set_group_profile", sample_group_profile("group name"))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_related_group_is_rejected() {
+    let (conductor, group_zome) = setup_group_dna().await;
+
+    let related_group = RelatedGroup {
+        group_profile: sample_group_profile("related group"),
+        network_seed: String::from(""),
+        group_dna_hash: fixt!(DnaHash),
+    };
+    let result: Result<(), _> = conductor
+        .call_fallible(&group_zome, "add_related_group", related_group)
+        .await;
+    assert!(result.is_err());
+
+    let related_group = RelatedGroup {
+        group_profile: sample_group_profile(""),
+        network_seed: String::from("related network seed"),
+        group_dna_hash: fixt!(DnaHash),
+    };
+    let result: Result<(), _> = conductor
+        .call_fallible(&group_zome, "add_related_group", related_group)
+        .await;
+    assert!(result.is_err());
+}
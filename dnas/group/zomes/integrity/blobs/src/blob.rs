@@ -0,0 +1,126 @@
+use hdi::prelude::*;
+pub use we_types::MAX_BLOB_CHUNK_SIZE;
+
+pub const MAX_BLOB_SIZE: usize = 50 * MAX_BLOB_CHUNK_SIZE;
+
+/// A piece of the contents of a Blob. Chunks are content addressed, so identical chunks
+/// are only stored once.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct BlobChunk {
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+/// Binary data like images, assembled from its chunks in the given order. Blobs are
+/// content addressed, so they are referenced by their entry hash.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Blob {
+    pub mime_type: String,
+    /// Total size in bytes, i.e. the sum of the sizes of all chunks
+    pub size: usize,
+    pub chunk_hashes: Vec<EntryHash>,
+}
+
+pub fn validate_create_blob_chunk(
+    _action: EntryCreationAction,
+    blob_chunk: BlobChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    if blob_chunk.bytes.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "BlobChunks must not be empty",
+        )));
+    }
+    if blob_chunk.bytes.len() > MAX_BLOB_CHUNK_SIZE {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "BlobChunks must not be larger than {} bytes",
+            MAX_BLOB_CHUNK_SIZE
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_blob_chunk(
+    _action: Update,
+    _blob_chunk: BlobChunk,
+    _original_action: EntryCreationAction,
+    _original_blob_chunk: BlobChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "BlobChunks cannot be updated",
+    )))
+}
+
+pub fn validate_delete_blob_chunk(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_blob_chunk: BlobChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "BlobChunks cannot be deleted",
+    )))
+}
+
+pub fn validate_create_blob(
+    _action: EntryCreationAction,
+    blob: Blob,
+) -> ExternResult<ValidateCallbackResult> {
+    if blob.mime_type.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The mime type of a Blob must not be empty",
+        )));
+    }
+    if blob.chunk_hashes.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Blobs must consist of at least one BlobChunk",
+        )));
+    }
+    if blob.size > MAX_BLOB_SIZE {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Blobs must not be larger than {} bytes",
+            MAX_BLOB_SIZE
+        )));
+    }
+
+    let mut size: usize = 0;
+    for chunk_hash in blob.chunk_hashes {
+        let entry = must_get_entry(chunk_hash)?.content;
+        match BlobChunk::try_from(entry) {
+            Ok(blob_chunk) => size += blob_chunk.bytes.len(),
+            Err(_) => {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "The chunks of a Blob must be BlobChunks",
+                )))
+            }
+        }
+    }
+    if size != blob.size {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The size of a Blob must be the sum of the sizes of its chunks",
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_blob(
+    _action: Update,
+    _blob: Blob,
+    _original_action: EntryCreationAction,
+    _original_blob: Blob,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Blobs cannot be updated",
+    )))
+}
+
+pub fn validate_delete_blob(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_blob: Blob,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Blobs cannot be deleted",
+    )))
+}
@@ -0,0 +1,106 @@
+use hdi::prelude::*;
+
+pub mod blob;
+pub use blob::*;
+
+#[hdk_entry_defs]
+#[unit_enum(UnitEntryTypes)]
+pub enum EntryTypes {
+    BlobChunk(BlobChunk),
+    Blob(Blob),
+}
+
+/// Blobs and their chunks are immutable and content addressed, so this zome
+/// doesn't define any link types
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op.flattened::<EntryTypes, ()>()? {
+        FlatOp::StoreEntry(store_entry) => match store_entry {
+            OpEntry::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpEntry::UpdateEntry {
+                app_entry, action, ..
+            } => validate_create_entry(EntryCreationAction::Update(action), app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterUpdate(update_entry) => match update_entry {
+            OpUpdate::Entry {
+                original_action,
+                original_app_entry,
+                app_entry,
+                action,
+            } => validate_update_entry(action, app_entry, original_action, original_app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterDelete(delete_entry) => match delete_entry {
+            OpDelete::Entry {
+                original_action,
+                original_app_entry,
+                action,
+            } => validate_delete_entry(action, original_action, original_app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterCreateLink { .. } | FlatOp::RegisterDeleteLink { .. } => Ok(
+            ValidateCallbackResult::Invalid(String::from("The blobs zome has no link types")),
+        ),
+        FlatOp::StoreRecord(store_record) => match store_record {
+            OpRecord::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpRecord::UpdateEntry { .. } => Ok(ValidateCallbackResult::Invalid(String::from(
+                "Blobs and BlobChunks cannot be updated",
+            ))),
+            OpRecord::DeleteEntry { .. } => Ok(ValidateCallbackResult::Invalid(String::from(
+                "Blobs and BlobChunks cannot be deleted",
+            ))),
+            OpRecord::CreateLink { .. } | OpRecord::DeleteLink { .. } => Ok(
+                ValidateCallbackResult::Invalid(String::from("The blobs zome has no link types")),
+            ),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterAgentActivity(_) => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_create_entry(
+    action: EntryCreationAction,
+    app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match app_entry {
+        EntryTypes::BlobChunk(blob_chunk) => validate_create_blob_chunk(action, blob_chunk),
+        EntryTypes::Blob(blob) => validate_create_blob(action, blob),
+    }
+}
+
+fn validate_update_entry(
+    action: Update,
+    app_entry: EntryTypes,
+    original_action: EntryCreationAction,
+    original_app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match (app_entry, original_app_entry) {
+        (EntryTypes::BlobChunk(blob_chunk), EntryTypes::BlobChunk(original_blob_chunk)) => {
+            validate_update_blob_chunk(action, blob_chunk, original_action, original_blob_chunk)
+        }
+        (EntryTypes::Blob(blob), EntryTypes::Blob(original_blob)) => {
+            validate_update_blob(action, blob, original_action, original_blob)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(
+            "Original and updated entry types must be the same".to_string(),
+        )),
+    }
+}
+
+fn validate_delete_entry(
+    action: Delete,
+    original_action: EntryCreationAction,
+    original_app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match original_app_entry {
+        EntryTypes::BlobChunk(blob_chunk) => {
+            validate_delete_blob_chunk(action, original_action, blob_chunk)
+        }
+        EntryTypes::Blob(blob) => validate_delete_blob(action, original_action, blob),
+    }
+}
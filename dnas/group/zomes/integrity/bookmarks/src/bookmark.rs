@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use hdi::prelude::*;
+pub use we_types::{Hrl, HrlWithContext};
+
+pub const MAX_BOOKMARK_TITLE_LENGTH: usize = 200;
+
+/// An HRL from any applet, pinned to the group home
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Bookmark {
+    pub hrl_with_context: HrlWithContext,
+    pub title: String,
+}
+
+/// The order in which the bookmarks of the group are shown. Any member can reorder
+/// them, the latest order wins. Bookmarks that are not part of it are shown after
+/// the ordered ones.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct BookmarkOrder {
+    pub bookmark_hashes: Vec<ActionHash>,
+}
+
+pub fn bookmarks_path() -> Path {
+    Path::from("bookmarks")
+}
+
+pub fn bookmark_order_path() -> Path {
+    Path::from("bookmark_order")
+}
+
+pub fn validate_create_bookmark(
+    _action: EntryCreationAction,
+    bookmark: Bookmark,
+) -> ExternResult<ValidateCallbackResult> {
+    if bookmark.title.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Bookmark title must not be empty",
+        )));
+    }
+    if bookmark.title.chars().count() > MAX_BOOKMARK_TITLE_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Bookmark title must not be longer than {} characters",
+            MAX_BOOKMARK_TITLE_LENGTH
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_bookmark(
+    _action: Update,
+    _bookmark: Bookmark,
+    _original_action: EntryCreationAction,
+    _original_bookmark: Bookmark,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Bookmarks cannot be updated, unpin them and pin them again instead",
+    )))
+}
+
+pub fn validate_delete_bookmark(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_bookmark: Bookmark,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Bookmarks can only be removed by their author",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_bookmark_order(
+    _action: EntryCreationAction,
+    bookmark_order: BookmarkOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    let mut bookmark_hashes: HashSet<&ActionHash> = HashSet::new();
+    if !bookmark_order
+        .bookmark_hashes
+        .iter()
+        .all(|bookmark_hash| bookmark_hashes.insert(bookmark_hash))
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "A BookmarkOrder must not contain the same bookmark twice",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_bookmark_order(
+    _action: Update,
+    _bookmark_order: BookmarkOrder,
+    _original_action: EntryCreationAction,
+    _original_bookmark_order: BookmarkOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "BookmarkOrders cannot be updated, create a new one instead",
+    )))
+}
+
+pub fn validate_delete_bookmark_order(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_bookmark_order: BookmarkOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "BookmarkOrders cannot be deleted",
+    )))
+}
+
+/// Bookmarks can only be pinned by their author
+pub fn validate_create_link_anchor_to_bookmark(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(bookmarks_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToBookmark links must be based on the bookmarks anchor",
+        )));
+    }
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToBookmark link must be the action hash of a Bookmark",
+        )));
+    };
+    let record = must_get_valid_record(action_hash)?;
+    let Ok(Some(_bookmark)) = record.entry().to_app_option::<Bookmark>() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToBookmark link must be the action hash of a Bookmark",
+        )));
+    };
+    if record.action().author().ne(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Bookmarks can only be pinned by their author",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_anchor_to_bookmark(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Bookmarks can only be unpinned by their author",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_anchor_to_bookmark_order(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(bookmark_order_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToBookmarkOrder links must be based on the bookmark_order anchor",
+        )));
+    }
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToBookmarkOrder link must be the action hash of a BookmarkOrder",
+        )));
+    };
+    let record = must_get_valid_record(action_hash)?;
+    match record.entry().to_app_option::<BookmarkOrder>() {
+        Ok(Some(_bookmark_order)) => Ok(ValidateCallbackResult::Valid),
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToBookmarkOrder link must be the action hash of a BookmarkOrder",
+        ))),
+    }
+}
+
+pub fn validate_delete_link_anchor_to_bookmark_order(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AnchorToBookmarkOrder links cannot be deleted",
+    )))
+}
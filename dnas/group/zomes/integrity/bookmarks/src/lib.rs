@@ -0,0 +1,301 @@
+use hdi::prelude::*;
+
+pub mod bookmark;
+pub use bookmark::*;
+
+#[hdk_entry_defs]
+#[unit_enum(UnitEntryTypes)]
+pub enum EntryTypes {
+    Bookmark(Bookmark),
+    BookmarkOrder(BookmarkOrder),
+}
+
+#[hdk_link_types]
+pub enum LinkTypes {
+    AnchorToBookmark,
+    AnchorToBookmarkOrder,
+}
+
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op.flattened::<EntryTypes, LinkTypes>()? {
+        FlatOp::StoreEntry(store_entry) => match store_entry {
+            OpEntry::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpEntry::UpdateEntry {
+                app_entry, action, ..
+            } => validate_create_entry(EntryCreationAction::Update(action), app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterUpdate(update_entry) => match update_entry {
+            OpUpdate::Entry {
+                original_action,
+                original_app_entry,
+                app_entry,
+                action,
+            } => validate_update_entry(action, app_entry, original_action, original_app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterDelete(delete_entry) => match delete_entry {
+            OpDelete::Entry {
+                original_action,
+                original_app_entry,
+                action,
+            } => validate_delete_entry(action, original_action, original_app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterCreateLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            action,
+        } => validate_create_link(action, link_type, base_address, target_address, tag),
+        FlatOp::RegisterDeleteLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            original_action,
+            action,
+        } => validate_delete_link(
+            action,
+            original_action,
+            link_type,
+            base_address,
+            target_address,
+            tag,
+        ),
+        FlatOp::StoreRecord(store_record) => match store_record {
+            OpRecord::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpRecord::UpdateEntry {
+                original_action_hash,
+                app_entry,
+                action,
+                ..
+            } => {
+                let result = validate_create_entry(
+                    EntryCreationAction::Update(action.clone()),
+                    app_entry.clone(),
+                )?;
+                if let ValidateCallbackResult::Valid = result {
+                    match get_original_app_entry(original_action_hash)? {
+                        Ok(Some((original_action, original_app_entry))) => validate_update_entry(
+                            action,
+                            app_entry,
+                            original_action,
+                            original_app_entry,
+                        ),
+                        Ok(None) => Ok(ValidateCallbackResult::Valid),
+                        Err(invalid) => Ok(invalid),
+                    }
+                } else {
+                    Ok(result)
+                }
+            }
+            OpRecord::DeleteEntry {
+                original_action_hash,
+                action,
+                ..
+            } => match get_original_app_entry(original_action_hash)? {
+                Ok(Some((original_action, original_app_entry))) => {
+                    validate_delete_entry(action, original_action, original_app_entry)
+                }
+                Ok(None) => Ok(ValidateCallbackResult::Valid),
+                Err(invalid) => Ok(invalid),
+            },
+            OpRecord::CreateLink {
+                base_address,
+                target_address,
+                tag,
+                link_type,
+                action,
+            } => validate_create_link(action, link_type, base_address, target_address, tag),
+            OpRecord::DeleteLink {
+                original_action_hash,
+                base_address,
+                action,
+            } => {
+                let record = must_get_valid_record(original_action_hash)?;
+                let create_link = match record.action() {
+                    Action::CreateLink(create_link) => create_link.clone(),
+                    _ => {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "The action that a DeleteLink deletes must be a CreateLink".to_string(),
+                        ));
+                    }
+                };
+                let link_type = match LinkTypes::from_type(
+                    create_link.zome_index.clone(),
+                    create_link.link_type.clone(),
+                )? {
+                    Some(lt) => lt,
+                    None => {
+                        return Ok(ValidateCallbackResult::Valid);
+                    }
+                };
+                validate_delete_link(
+                    action,
+                    create_link.clone(),
+                    link_type,
+                    base_address,
+                    create_link.target_address,
+                    create_link.tag,
+                )
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterAgentActivity(_) => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_create_entry(
+    action: EntryCreationAction,
+    app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match app_entry {
+        EntryTypes::Bookmark(bookmark) => validate_create_bookmark(action, bookmark),
+        EntryTypes::BookmarkOrder(bookmark_order) => {
+            validate_create_bookmark_order(action, bookmark_order)
+        }
+    }
+}
+
+fn validate_update_entry(
+    action: Update,
+    app_entry: EntryTypes,
+    original_action: EntryCreationAction,
+    original_app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match (app_entry, original_app_entry) {
+        (EntryTypes::Bookmark(bookmark), EntryTypes::Bookmark(original_bookmark)) => {
+            validate_update_bookmark(action, bookmark, original_action, original_bookmark)
+        }
+        (
+            EntryTypes::BookmarkOrder(bookmark_order),
+            EntryTypes::BookmarkOrder(original_bookmark_order),
+        ) => validate_update_bookmark_order(
+            action,
+            bookmark_order,
+            original_action,
+            original_bookmark_order,
+        ),
+        _ => Ok(ValidateCallbackResult::Invalid(
+            "Original and updated entry types must be the same".to_string(),
+        )),
+    }
+}
+
+fn validate_delete_entry(
+    action: Delete,
+    original_action: EntryCreationAction,
+    original_app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match original_app_entry {
+        EntryTypes::Bookmark(bookmark) => {
+            validate_delete_bookmark(action, original_action, bookmark)
+        }
+        EntryTypes::BookmarkOrder(bookmark_order) => {
+            validate_delete_bookmark_order(action, original_action, bookmark_order)
+        }
+    }
+}
+
+fn validate_create_link(
+    action: CreateLink,
+    link_type: LinkTypes,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::AnchorToBookmark => {
+            validate_create_link_anchor_to_bookmark(action, base_address, target_address, tag)
+        }
+        LinkTypes::AnchorToBookmarkOrder => validate_create_link_anchor_to_bookmark_order(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+    }
+}
+
+fn validate_delete_link(
+    action: DeleteLink,
+    original_action: CreateLink,
+    link_type: LinkTypes,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::AnchorToBookmark => validate_delete_link_anchor_to_bookmark(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToBookmarkOrder => validate_delete_link_anchor_to_bookmark_order(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+    }
+}
+
+/// Fetches the record that an update or delete refers to and deserializes its app entry.
+///
+/// Returns `Ok(None)` if the original entry is private and therefore not available to
+/// the validating agent, and `Err` with an invalid result if the original action
+/// is not one of this zome's entry creation actions.
+fn get_original_app_entry(
+    original_action_hash: ActionHash,
+) -> ExternResult<Result<Option<(EntryCreationAction, EntryTypes)>, ValidateCallbackResult>> {
+    let original_record = must_get_valid_record(original_action_hash)?;
+    let original_action = match original_record.action().clone() {
+        Action::Create(create) => EntryCreationAction::Create(create),
+        Action::Update(update) => EntryCreationAction::Update(update),
+        _ => {
+            return Ok(Err(ValidateCallbackResult::Invalid(
+                "Original action for an update or delete must be a Create or Update action"
+                    .to_string(),
+            )));
+        }
+    };
+    let app_entry_type = match original_action.entry_type() {
+        EntryType::App(app_entry_type) => app_entry_type,
+        _ => {
+            return Ok(Ok(None));
+        }
+    };
+    let entry = match original_record.entry().as_option() {
+        Some(entry) => entry,
+        None => {
+            if original_action.entry_type().visibility().is_public() {
+                return Ok(Err(ValidateCallbackResult::Invalid(
+                    "Original record for an update or delete of a public entry must contain an entry"
+                        .to_string(),
+                )));
+            } else {
+                return Ok(Ok(None));
+            }
+        }
+    };
+    match EntryTypes::deserialize_from_type(
+        app_entry_type.zome_index.clone(),
+        app_entry_type.entry_index.clone(),
+        &entry,
+    )? {
+        Some(original_app_entry) => Ok(Ok(Some((original_action, original_app_entry)))),
+        None => Ok(Err(ValidateCallbackResult::Invalid(
+            "Original app entry must be one of the defined entry types for this zome".to_string(),
+        ))),
+    }
+}
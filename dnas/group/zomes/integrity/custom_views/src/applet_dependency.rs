@@ -0,0 +1,235 @@
+use hdi::prelude::*;
+use we_types::{ANCHOR_TO_APPLET_LINK_TYPE, GROUP_APPLETS_ANCHOR, GROUP_INTEGRITY_ZOME_NAME};
+
+use crate::CustomView;
+
+/// How many CustomViews of an Applet the unarchiving agent can unflag at once. Bounds
+/// the walk over their source chain when validating the deletions.
+pub const MAX_UNFLAGGED_CUSTOM_VIEWS: u32 = 100;
+
+/// Stored in the tag of AppletToCustomViews and CustomViewToArchivedApplet links. Points
+/// to the version of the CustomView that declares the dependency on the Applet, since
+/// the link itself points to the original create action of the view.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+pub struct CustomViewDependencyTag {
+    pub custom_view_version_hash: ActionHash,
+    /// The deletion of the AnchorToApplet link that archived the Applet. Required for
+    /// CustomViewToArchivedApplet links.
+    #[serde(default)]
+    pub archive_hash: Option<ActionHash>,
+}
+
+impl CustomViewDependencyTag {
+    pub fn from_tag(tag: LinkTag) -> Option<Self> {
+        CustomViewDependencyTag::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0))).ok()
+    }
+
+    pub fn to_tag(self) -> ExternResult<LinkTag> {
+        Ok(LinkTag::new(
+            SerializedBytes::try_from(self)
+                .map_err(|err| wasm_error!(err))?
+                .bytes()
+                .clone(),
+        ))
+    }
+}
+
+/// Follows the update chain of a CustomView back to its original create action
+pub fn original_custom_view_hash(custom_view_hash: ActionHash) -> ExternResult<ActionHash> {
+    let mut action_hash = custom_view_hash;
+    loop {
+        match must_get_action(action_hash.clone())?.hashed.content {
+            Action::Update(update) => action_hash = update.original_action_address,
+            _ => return Ok(action_hash),
+        }
+    }
+}
+
+/// The link must point from the original create action of a CustomView to an Applet
+/// that the version of the view in the tag depends on, or the other way around
+fn validate_custom_view_dependency(
+    custom_view_address: AnyLinkableHash,
+    applet_address: AnyLinkableHash,
+    tag: LinkTag,
+    link_name: &str,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(applet_hash) = applet_address.into_entry_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The Applet of an {} link must be an entry hash",
+            link_name
+        )));
+    };
+    let Some(custom_view_hash) = custom_view_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The CustomView of an {} link must be the action hash of its create action",
+            link_name
+        )));
+    };
+    let Some(tag) = CustomViewDependencyTag::from_tag(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of an {} link must be a CustomViewDependencyTag",
+            link_name
+        )));
+    };
+    let record = must_get_valid_record(tag.custom_view_version_hash.clone())?;
+    let Ok(Some(custom_view)) = record.entry().to_app_option::<CustomView>() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of an {} link must point to a version of a CustomView",
+            link_name
+        )));
+    };
+    if !custom_view.applet_dependencies.contains(&applet_hash) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "{} links must point to an Applet that the CustomView depends on",
+            link_name
+        )));
+    }
+    if original_custom_view_hash(tag.custom_view_version_hash)?.ne(&custom_view_hash) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of an {} link must point to a version of the linked CustomView",
+            link_name
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Whether the given CreateLink is an AnchorToApplet link of the group integrity zome
+/// from the group_applets anchor to the given Applet
+fn is_group_applet_link(
+    create_link: &CreateLink,
+    applet_address: &AnyLinkableHash,
+) -> ExternResult<bool> {
+    let group_integrity_zome_index = dna_info()?
+        .zome_names
+        .iter()
+        .position(|zome_name| zome_name.0.eq(GROUP_INTEGRITY_ZOME_NAME));
+    let group_applets_anchor =
+        AnyLinkableHash::from(Path::from(GROUP_APPLETS_ANCHOR).path_entry_hash()?);
+    Ok(group_integrity_zome_index
+        .is_some_and(|index| create_link.zome_index.0 as usize == index)
+        && create_link.link_type.0 == ANCHOR_TO_APPLET_LINK_TYPE
+        && create_link.base_address.eq(&group_applets_anchor)
+        && create_link.target_address.eq(applet_address))
+}
+
+/// Only the author of a CustomView can declare its dependencies
+pub fn validate_create_link_applet_to_custom_views(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let result = validate_custom_view_dependency(
+        target_address.clone(),
+        base_address,
+        tag,
+        "AppletToCustomViews",
+    )?;
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+    let Some(custom_view_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletToCustomViews links must point to the action hash of a CustomView",
+        )));
+    };
+    let custom_view_action = must_get_action(custom_view_hash)?;
+    if custom_view_action.hashed.content.author().ne(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a CustomView can declare its Applet dependencies",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_applet_to_custom_views(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a CustomView can remove its Applet dependencies",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Flags a CustomView as broken because an Applet it depends on got archived. Any
+/// member can create these links, but the view must depend on the Applet and the tag
+/// must point to the deletion of an AnchorToApplet link of the Applet in the group
+/// integrity zome.
+pub fn validate_create_link_custom_view_to_archived_applet(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let result = validate_custom_view_dependency(
+        base_address,
+        target_address.clone(),
+        tag.clone(),
+        "CustomViewToArchivedApplet",
+    )?;
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+    let Some(archive_hash) = CustomViewDependencyTag::from_tag(tag).and_then(|tag| tag.archive_hash)
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of a CustomViewToArchivedApplet link must point to the archiving of the Applet",
+        )));
+    };
+    let Action::DeleteLink(delete_link) = must_get_action(archive_hash)?.hashed.content else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The archiving of the Applet must be the deletion of a link",
+        )));
+    };
+    let archived_link_is_group_applet_link =
+        match must_get_action(delete_link.link_add_address)?.hashed.content {
+            Action::CreateLink(create_link) => is_group_applet_link(&create_link, &target_address)?,
+            _ => false,
+        };
+    if !archived_link_is_group_applet_link {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The archiving of the Applet must delete the link from the group_applets anchor to the Applet",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Removed again by the agent that flagged the CustomView, or by whoever unarchives
+/// the Applet. The group zome links the Applet from the group_applets anchor again
+/// right before unflagging its CustomViews, so the actions between that link and the
+/// deletion on the source chain of the unarchiving agent must all be deletions, at most
+/// MAX_UNFLAGGED_CUSTOM_VIEWS of them.
+pub fn validate_delete_link_custom_view_to_archived_applet(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.eq(&original_action.author) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let mut activity = must_get_agent_activity(
+        action.author.clone(),
+        ChainFilter::new(action.prev_action).take(MAX_UNFLAGGED_CUSTOM_VIEWS + 1),
+    )?;
+    activity.sort_by_key(|activity| std::cmp::Reverse(activity.action.action().action_seq()));
+    for activity in activity {
+        match activity.action.action() {
+            Action::DeleteLink(_) => continue,
+            Action::CreateLink(create_link) if is_group_applet_link(create_link, &target)? => {
+                return Ok(ValidateCallbackResult::Valid)
+            }
+            _ => break,
+        }
+    }
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Only the flagging agent or the agent that unarchives the Applet can unflag a CustomView",
+    )))
+}
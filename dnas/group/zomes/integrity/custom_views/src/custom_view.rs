@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use hdi::prelude::*;
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
@@ -7,47 +9,141 @@ pub struct CustomView {
     pub html: String,
     pub js: String,
     pub css: String,
+    /// Hash of a Blob in the blobs zome holding the logo, as an alternative to inlining it
+    #[serde(default)]
+    pub logo_blob_hash: Option<EntryHash>,
+    /// Entry hashes of the group Applets that this view needs in order to work
+    #[serde(default)]
+    pub applet_dependencies: Vec<EntryHash>,
+}
+pub const MAX_CUSTOM_VIEW_LOGO_SIZE: usize = 300_000;
+pub const MAX_CUSTOM_VIEW_HTML_SIZE: usize = 1_000_000;
+pub const MAX_CUSTOM_VIEW_JS_SIZE: usize = 1_500_000;
+pub const MAX_CUSTOM_VIEW_CSS_SIZE: usize = 500_000;
+pub fn all_custom_views_path() -> Path {
+    Path::from("all_custom_views")
+}
+fn validate_field_size(
+    field_name: &str,
+    field: &String,
+    max_size: usize,
+) -> Option<ValidateCallbackResult> {
+    match field.len() > max_size {
+        true => Some(ValidateCallbackResult::Invalid(format!(
+            "The {} of a CustomView must not be larger than {} bytes",
+            field_name, max_size
+        ))),
+        false => None,
+    }
 }
 pub fn validate_create_custom_view(
     _action: EntryCreationAction,
-    _custom_view: CustomView,
+    custom_view: CustomView,
 ) -> ExternResult<ValidateCallbackResult> {
+    let size_checks = [
+        validate_field_size("logo", &custom_view.logo, MAX_CUSTOM_VIEW_LOGO_SIZE),
+        validate_field_size("html", &custom_view.html, MAX_CUSTOM_VIEW_HTML_SIZE),
+        validate_field_size("js", &custom_view.js, MAX_CUSTOM_VIEW_JS_SIZE),
+        validate_field_size("css", &custom_view.css, MAX_CUSTOM_VIEW_CSS_SIZE),
+    ];
+    if let Some(invalid) = size_checks.into_iter().flatten().next() {
+        return Ok(invalid);
+    }
+    let mut applet_dependencies: HashSet<&EntryHash> = HashSet::new();
+    if !custom_view
+        .applet_dependencies
+        .iter()
+        .all(|applet_hash| applet_dependencies.insert(applet_hash))
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "A CustomView must not depend on the same Applet twice",
+        )));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
+/// CustomViews are edited by their author only. Other members who want to change a
+/// view create their own one instead. The revision history and `revert_custom_view`
+/// let the author go back to any earlier version, including ones made concurrently
+/// from another of their devices.
 pub fn validate_update_custom_view(
-    _action: Update,
+    action: Update,
     _custom_view: CustomView,
-    _original_action: EntryCreationAction,
+    original_action: EntryCreationAction,
     _original_custom_view: CustomView,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a CustomView can update it",
+        )));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_custom_view(
-    _action: Delete,
-    _original_action: EntryCreationAction,
+    action: Delete,
+    original_action: EntryCreationAction,
     _original_custom_view: CustomView,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a CustomView can delete it",
+        )));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
+/// AllCustomViews links must be created by the author of the CustomView, before
+/// they delete it. Since only the author can delete a CustomView, their chain up to
+/// the link shows whether it was still live. A CustomView deleted after it was linked
+/// keeps its link, as AllCustomViews links cannot be deleted.
 pub fn validate_create_link_all_custom_views(
-    _action: CreateLink,
-    _base_address: AnyLinkableHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(all_custom_views_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AllCustomViews links must be based on the all_custom_views anchor",
+        )));
+    }
     // Check the entry type for the given action hash
-    // let action_hash = ActionHash::from(target_address);
-    let action_hash = ActionHash::try_from(target_address)
-        .map_err(|e| wasm_error!(WasmErrorInner::from(e)))?;
-    let record = must_get_valid_record(action_hash)?;
-    let _custom_view: crate::CustomView = record
-        .entry()
-        .to_app_option()
-        .map_err(|e| wasm_error!(e))?
-        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
-            "Linked action must reference an entry"
-        ))))?;
-    // TODO: add the appropriate validation rules
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AllCustomViews links must point to the action hash of a CustomView",
+        )));
+    };
+    let record = must_get_valid_record(action_hash.clone())?;
+    // Only the original create action is linked, updates are reached by following
+    // the update chain from there
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AllCustomViews links must point to the create action of a CustomView",
+        )));
+    }
+    let Ok(Some(_custom_view)) = record.entry().to_app_option::<crate::CustomView>() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AllCustomViews links must point to the action hash of a CustomView",
+        )));
+    };
+    if action.author.ne(record.action().author()) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AllCustomViews links must be created by the author of the CustomView",
+        )));
+    }
+    let activity = must_get_agent_activity(
+        action.author.clone(),
+        ChainFilter::new(action.prev_action.clone()).until(action_hash.clone()),
+    )?;
+    let deleted = activity.iter().any(|activity| {
+        matches!(
+            activity.action.action(),
+            Action::Delete(delete) if delete.deletes_address.eq(&action_hash)
+        )
+    });
+    if deleted {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AllCustomViews links must point to a CustomView that has not been deleted",
+        )));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_link_all_custom_views(
@@ -1,4 +1,6 @@
+pub mod applet_dependency;
 pub mod custom_view;
+pub use applet_dependency::*;
 pub use custom_view::*;
 use hdi::prelude::*;
 #[derive(Serialize, Deserialize)]
@@ -12,6 +14,8 @@ pub enum EntryTypes {
 #[hdk_link_types]
 pub enum LinkTypes {
     AllCustomViews,
+    AppletToCustomViews,
+    CustomViewToArchivedApplet,
 }
 #[hdk_extern]
 pub fn genesis_self_check(_data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
@@ -23,9 +27,8 @@ pub fn validate_agent_joining(
 ) -> ExternResult<ValidateCallbackResult> {
     Ok(ValidateCallbackResult::Valid)
 }
-// TODO: fix name of this function
 #[hdk_extern]
-pub fn validatee(op: Op) -> ExternResult<ValidateCallbackResult> {
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
     match op.flattened::<EntryTypes, LinkTypes>()? {
         FlatOp::StoreEntry(store_entry) => match store_entry {
             OpEntry::CreateEntry { app_entry, action } => match app_entry {
@@ -86,6 +89,20 @@ pub fn validatee(op: Op) -> ExternResult<ValidateCallbackResult> {
             LinkTypes::AllCustomViews => {
                 validate_create_link_all_custom_views(action, base_address, target_address, tag)
             }
+            LinkTypes::AppletToCustomViews => validate_create_link_applet_to_custom_views(
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::CustomViewToArchivedApplet => {
+                validate_create_link_custom_view_to_archived_applet(
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
         },
         FlatOp::RegisterDeleteLink {
             link_type,
@@ -102,6 +119,22 @@ pub fn validatee(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::AppletToCustomViews => validate_delete_link_applet_to_custom_views(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::CustomViewToArchivedApplet => {
+                validate_delete_link_custom_view_to_archived_applet(
+                    action,
+                    original_action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
         },
         FlatOp::StoreRecord(store_record) => match store_record {
             OpRecord::CreateEntry { app_entry, action } => match app_entry {
@@ -230,6 +263,20 @@ pub fn validatee(op: Op) -> ExternResult<ValidateCallbackResult> {
                 LinkTypes::AllCustomViews => {
                     validate_create_link_all_custom_views(action, base_address, target_address, tag)
                 }
+                LinkTypes::AppletToCustomViews => validate_create_link_applet_to_custom_views(
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                ),
+                LinkTypes::CustomViewToArchivedApplet => {
+                    validate_create_link_custom_view_to_archived_applet(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
             },
             OpRecord::DeleteLink {
                 original_action_hash,
@@ -262,6 +309,22 @@ pub fn validatee(op: Op) -> ExternResult<ValidateCallbackResult> {
                         create_link.target_address,
                         create_link.tag,
                     ),
+                    LinkTypes::AppletToCustomViews => validate_delete_link_applet_to_custom_views(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::CustomViewToArchivedApplet => {
+                        validate_delete_link_custom_view_to_archived_applet(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
                 }
             }
             OpRecord::CreatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
@@ -0,0 +1,417 @@
+use hdi::prelude::*;
+
+use crate::{
+    federated_group_from_record, group_settings_at, group_settings_in_effect, progenitor,
+    validate_accepted_applet_proposal, validate_applet_add_policy, validate_steward_permission,
+    AnchorToAppletTag, Applet, AppletArchivePolicy, LinkTypes, StewardPermission,
+    GROUP_APPLETS_ANCHOR,
+};
+
+pub const MAX_APPLET_NAME_LENGTH: usize = 100;
+pub const MAX_APPLET_DESCRIPTION_LENGTH: usize = 5000;
+
+/// Stored in the tag of AppletToExternalAgent links
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct ExternalAgentTag {
+    /// The group via which the external agent takes part in the federated Applet
+    pub group_dna_hash: DnaHash,
+    /// The AppletToInvitedGroup link that federated the Applet with that group
+    pub federation_link_hash: ActionHash,
+    /// External agents are registered by stewards of the group
+    #[serde(default)]
+    pub steward_permission: StewardPermission,
+}
+
+pub fn group_applets_path() -> Path {
+    Path::from(GROUP_APPLETS_ANCHOR)
+}
+
+pub fn federated_applets_path() -> Path {
+    Path::from("federated_applets")
+}
+
+pub fn validate_create_applet(
+    _action: EntryCreationAction,
+    applet: Applet,
+) -> ExternResult<ValidateCallbackResult> {
+    if applet.custom_name.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Applet name must not be empty",
+        )));
+    }
+    if applet.custom_name.chars().count() > MAX_APPLET_NAME_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Applet name must not be longer than {} characters",
+            MAX_APPLET_NAME_LENGTH
+        )));
+    }
+    if applet.description.chars().count() > MAX_APPLET_DESCRIPTION_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Applet description must not be longer than {} characters",
+            MAX_APPLET_DESCRIPTION_LENGTH
+        )));
+    }
+    if let Some(network_seed) = applet.network_seed {
+        if network_seed.is_empty() {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Applet network seed must either be omitted or non-empty",
+            )));
+        }
+    }
+    if applet.properties.keys().any(|role_name| role_name.is_empty()) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Applet properties must be segmented by non-empty role names",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_applet(
+    _action: Update,
+    _applet: Applet,
+    _original_action: EntryCreationAction,
+    _original_applet: Applet,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Applets cannot be updated",
+    )))
+}
+
+pub fn validate_delete_applet(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_applet: Applet,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Applets cannot be deleted, they can only be archived",
+    )))
+}
+
+/// Private Applet entries only live on the source chain of the agent that
+/// installed the applet, so only they can delete them again.
+pub fn validate_delete_applet_private(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_applet: Applet,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a private Applet entry can delete it",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Checks that the given address points to an existing Applet entry
+pub fn validate_applet_address(
+    address: AnyLinkableHash,
+    context: &str,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(entry_hash) = address.into_entry_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "{} must be the entry hash of an Applet",
+            context
+        )));
+    };
+    let entry = must_get_entry(entry_hash)?.content;
+    match Applet::try_from(entry) {
+        Ok(_applet) => Ok(ValidateCallbackResult::Valid),
+        Err(_) => Ok(ValidateCallbackResult::Invalid(format!(
+            "{} must be the entry hash of an Applet",
+            context
+        ))),
+    }
+}
+
+/// Who may register Applets is governed by the GroupSettings referenced in the tag. If
+/// they require proposals, the tag must also reference the accepted proposal. Links
+/// that unarchive an Applet additionally require a steward permission.
+pub fn validate_create_link_anchor_to_applet(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(group_applets_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToApplet links must be based on the group_applets anchor",
+        )));
+    }
+    let result =
+        validate_applet_address(target_address.clone(), "Target of an AnchorToApplet link")?;
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+    let result = validate_applet_add_policy(&action, tag.clone())?;
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+    let anchor_to_applet_tag = AnchorToAppletTag::from_tag(tag.clone());
+    if let Some(archived_link_hash) = anchor_to_applet_tag.archived_link_hash.clone() {
+        let archived_link = must_get_action(archived_link_hash)?;
+        let scoped_link_type: ScopedLinkType = LinkTypes::AnchorToApplet.try_into()?;
+        match archived_link.action() {
+            Action::CreateLink(create_link)
+                if create_link.zome_index.eq(&scoped_link_type.zome_index)
+                    && create_link.link_type.eq(&scoped_link_type.zome_type)
+                    && create_link.target_address.eq(&target_address) => {}
+            _ => {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Unarchiving must refer to an AnchorToApplet link of the same Applet",
+                )))
+            }
+        }
+        let result = validate_steward_permission(
+            &action.author,
+            action.timestamp,
+            StewardPermission::from_tag(tag),
+        )?;
+        let ValidateCallbackResult::Valid = result else {
+            return Ok(result);
+        };
+    }
+    let group_settings = match group_settings_in_effect(
+        anchor_to_applet_tag.group_settings_hash,
+        anchor_to_applet_tag.progenitor_chain_top,
+        action.timestamp,
+    )? {
+        Ok(group_settings) => group_settings,
+        Err(invalid) => return Ok(invalid),
+    };
+    if group_settings.applet_proposal_rules.is_some() {
+        return validate_accepted_applet_proposal(
+            target_address,
+            anchor_to_applet_tag.proposal_acceptance_hash,
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Deleting an AnchorToApplet link archives the applet. This is always allowed for the
+/// progenitor. Unless the GroupSettings under which the link was created restrict
+/// archiving to the progenitor, it is also allowed for the agent that created the link
+/// in the first place. In groups with a progenitor, the latter also needs to have been
+/// a steward when creating the link.
+pub fn validate_delete_link_anchor_to_applet(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(progenitor) = progenitor()? {
+        if action.author.eq(&progenitor) {
+            return Ok(ValidateCallbackResult::Valid);
+        }
+    }
+    let group_settings = match group_settings_at(
+        AnchorToAppletTag::from_tag(tag.clone()).group_settings_hash,
+        original_action.timestamp,
+    )? {
+        Ok(group_settings) => group_settings,
+        Err(invalid) => return Ok(invalid),
+    };
+    if group_settings.applet_archive_policy == AppletArchivePolicy::ProgenitorOnly {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the progenitor can archive Applets in this group",
+        )));
+    }
+    if action.author.ne(&original_action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Applet can only be archived by the same agent that registered it to the group",
+        )));
+    }
+    validate_steward_permission(
+        &action.author,
+        action.timestamp,
+        StewardPermission::from_tag(tag),
+    )
+}
+
+pub fn validate_create_link_anchor_to_federated_applet(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(federated_applets_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToFederatedApplet links must be based on the federated_applets anchor",
+        )));
+    }
+    validate_applet_address(target_address, "Target of an AnchorToFederatedApplet link")
+}
+
+pub fn validate_create_link_agent_to_applet(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(action.author)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AgentToApplet links can only be created from the author's own public key",
+        )));
+    }
+    validate_applet_address(target_address, "Target of an AgentToApplet link")
+}
+
+pub fn validate_create_link_applet_to_joined_agent(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address.ne(&AnyLinkableHash::from(action.author)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletToJoinedAgent links can only point to the author's own public key",
+        )));
+    }
+    validate_applet_address(base_address, "Base of an AppletToJoinedAgent link")
+}
+
+pub fn validate_create_link_applet_to_external_agent(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address.into_agent_pub_key().is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AppletToExternalAgent link must be an agent public key",
+        )));
+    }
+    let result =
+        validate_applet_address(base_address.clone(), "Base of an AppletToExternalAgent link")?;
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+
+    let Ok(external_agent_tag) =
+        ExternalAgentTag::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0)))
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an AppletToExternalAgent link must be an ExternalAgentTag",
+        )));
+    };
+
+    let result = validate_steward_permission(
+        &action.author,
+        action.timestamp,
+        external_agent_tag.steward_permission,
+    )?;
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+
+    // The external agent must have joined via a group that the Applet is federated with
+    let federation_action = must_get_action(external_agent_tag.federation_link_hash)?;
+    let Action::CreateLink(federation_link) = federation_action.action() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The federation of an external agent must refer to an AppletToInvitedGroup link",
+        )));
+    };
+    let scoped_link_type: ScopedLinkType = LinkTypes::AppletToInvitedGroup.try_into()?;
+    if federation_link.zome_index.ne(&scoped_link_type.zome_index)
+        || federation_link.link_type.ne(&scoped_link_type.zome_type)
+        || federation_link.base_address.ne(&base_address)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The federation of an external agent must refer to an AppletToInvitedGroup link of the same Applet",
+        )));
+    }
+    let Some(federation_hash) = federation_link.target_address.clone().into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The federation of an external agent must refer to an AppletToInvitedGroup link",
+        )));
+    };
+    let federation_record = must_get_valid_record(federation_hash)?;
+    match federated_group_from_record(&federation_record) {
+        Some((_applet_hash, group_dna_hash))
+            if group_dna_hash.eq(&external_agent_tag.group_dna_hash) =>
+        {
+            Ok(ValidateCallbackResult::Valid)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "External agents can only take part via a group that the Applet is federated with",
+        ))),
+    }
+}
+
+/// AppletToSuccessor and AppletToPredecessor links mark an Applet of the group as
+/// superseded by another Applet, e.g. one that runs a newer release of the same tool.
+/// Only stewards can propose upgrades.
+fn validate_create_link_applet_upgrade(
+    action: CreateLink,
+    applet_address: AnyLinkableHash,
+    other_applet_address: AnyLinkableHash,
+    tag: LinkTag,
+    link_name: &str,
+) -> ExternResult<ValidateCallbackResult> {
+    if applet_address.eq(&other_applet_address) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "{} links cannot point from an Applet to itself",
+            link_name
+        )));
+    }
+    let result =
+        validate_applet_address(applet_address, &format!("Base of an {} link", link_name))?;
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+    let result = validate_applet_address(
+        other_applet_address,
+        &format!("Target of an {} link", link_name),
+    )?;
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+    validate_steward_permission(
+        &action.author,
+        action.timestamp,
+        StewardPermission::from_tag(tag),
+    )
+}
+
+pub fn validate_create_link_applet_to_successor(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_applet_upgrade(
+        action,
+        base_address,
+        target_address,
+        tag,
+        "AppletToSuccessor",
+    )
+}
+
+pub fn validate_create_link_applet_to_predecessor(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_applet_upgrade(
+        action,
+        base_address,
+        target_address,
+        tag,
+        "AppletToPredecessor",
+    )
+}
+
+/// Generic rule for links that may only be removed by whoever created them
+pub fn validate_delete_link_only_by_author(
+    action: DeleteLink,
+    original_action: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Links can only be deleted by their author",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
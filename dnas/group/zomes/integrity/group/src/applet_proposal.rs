@@ -0,0 +1,386 @@
+use std::collections::HashSet;
+
+use hdi::prelude::*;
+
+use crate::{
+    entries_created_by, group_settings_at, validate_applet_address, was_member_at,
+    AppletProposalRules, UnitEntryTypes,
+};
+
+/// Proposes to add an Applet to the group. Only used if the GroupSettings require
+/// Applets to be voted on before they go live.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AppletProposal {
+    pub applet_hash: EntryHash,
+    /// The GroupSettings whose AppletProposalRules apply to this proposal
+    pub group_settings_hash: Option<ActionHash>,
+}
+
+/// A member's vote on an AppletProposal. If a member votes more than once, their
+/// latest vote counts: an AppletProposalAcceptance can only count votes that have not
+/// been replaced by a later vote of the same member. Only members of the group can
+/// vote.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AppletVote {
+    pub proposal_hash: ActionHash,
+    pub approve: bool,
+}
+
+/// An approving vote counted by an AppletProposalAcceptance, together with an action
+/// of the voter from after the voting period closed. No valid vote can come after
+/// that action, so validators find every later vote of the voter below it, whichever
+/// action the accepting agent picked.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ApprovingVote {
+    pub vote_hash: ActionHash,
+    /// None for the vote of the accepting agent, whose own chain is checked up to the
+    /// acceptance instead
+    pub voter_chain_top: Option<ActionHash>,
+}
+
+/// Records that an AppletProposal has reached its quorum, together with the approving
+/// votes that prove it. Can only be created after the voting period has closed.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AppletProposalAcceptance {
+    pub proposal_hash: ActionHash,
+    pub approving_votes: Vec<ApprovingVote>,
+}
+
+pub fn applet_proposals_path() -> Path {
+    Path::from("applet_proposals")
+}
+
+/// Votes are only accepted until this point in time
+pub fn voting_closes_at(proposed_at: Timestamp, rules: &AppletProposalRules) -> Timestamp {
+    let voting_period_micros =
+        i64::try_from(rules.voting_period_seconds).unwrap_or(i64::MAX).saturating_mul(1_000_000);
+    Timestamp::from_micros(proposed_at.as_micros().saturating_add(voting_period_micros))
+}
+
+/// Fetches an AppletProposal together with the rules that apply to it
+pub fn must_get_applet_proposal(
+    proposal_hash: ActionHash,
+) -> ExternResult<Result<(Record, AppletProposal, AppletProposalRules), ValidateCallbackResult>> {
+    let record = must_get_valid_record(proposal_hash)?;
+    let proposal = match record.entry().to_app_option::<AppletProposal>() {
+        Ok(Some(proposal)) => proposal,
+        _ => {
+            return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+                "Proposal hash must point to an AppletProposal",
+            ))))
+        }
+    };
+    let group_settings =
+        match group_settings_at(proposal.group_settings_hash.clone(), record.action().timestamp())? {
+            Ok(group_settings) => group_settings,
+            Err(invalid) => return Ok(Err(invalid)),
+        };
+    match group_settings.applet_proposal_rules {
+        Some(rules) => Ok(Ok((record, proposal, rules))),
+        None => Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "The GroupSettings of an AppletProposal must enable applet proposals",
+        )))),
+    }
+}
+
+/// Checks that the acceptance referenced by an AnchorToApplet link accepts a proposal
+/// for the Applet that the link points to
+pub fn validate_accepted_applet_proposal(
+    applet_hash: AnyLinkableHash,
+    acceptance_hash: Option<ActionHash>,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(acceptance_hash) = acceptance_hash else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Applets of this group need to be proposed and accepted before they can be added",
+        )));
+    };
+    let record = must_get_valid_record(acceptance_hash)?;
+    let acceptance = match record.entry().to_app_option::<AppletProposalAcceptance>() {
+        Ok(Some(acceptance)) => acceptance,
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Acceptance hash must point to an AppletProposalAcceptance",
+            )))
+        }
+    };
+    let (_record, proposal, _rules) = match must_get_applet_proposal(acceptance.proposal_hash)? {
+        Ok(proposal) => proposal,
+        Err(invalid) => return Ok(invalid),
+    };
+    if applet_hash.ne(&AnyLinkableHash::from(proposal.applet_hash)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The accepted AppletProposal is for a different Applet",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_applet_proposal(
+    _action: EntryCreationAction,
+    proposal: AppletProposal,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_applet_address(proposal.applet_hash.into(), "Applet of an AppletProposal")
+}
+
+pub fn validate_update_applet_proposal(
+    _action: Update,
+    _proposal: AppletProposal,
+    _original_action: EntryCreationAction,
+    _original_proposal: AppletProposal,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletProposals cannot be updated",
+    )))
+}
+
+pub fn validate_delete_applet_proposal(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_proposal: AppletProposal,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletProposals cannot be deleted",
+    )))
+}
+
+pub fn validate_create_applet_vote(
+    action: EntryCreationAction,
+    vote: AppletVote,
+) -> ExternResult<ValidateCallbackResult> {
+    let (record, _proposal, rules) = match must_get_applet_proposal(vote.proposal_hash)? {
+        Ok(proposal) => proposal,
+        Err(invalid) => return Ok(invalid),
+    };
+    if action.timestamp() > &voting_closes_at(record.action().timestamp(), &rules) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The voting period of the AppletProposal has ended",
+        )));
+    }
+    if !was_member_at(action.author().clone(), action.prev_action().clone())? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only members of the group can vote on AppletProposals",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_applet_vote(
+    _action: Update,
+    _vote: AppletVote,
+    _original_action: EntryCreationAction,
+    _original_vote: AppletVote,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletVotes cannot be updated, vote again instead",
+    )))
+}
+
+pub fn validate_delete_applet_vote(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_vote: AppletVote,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletVotes cannot be deleted, vote again instead",
+    )))
+}
+
+/// The voting period must have closed, and the approving votes must be valid votes on
+/// the proposal from distinct agents that had not been replaced by a later vote of the
+/// same agent. There must be at least as many of them as the quorum requires.
+pub fn validate_create_applet_proposal_acceptance(
+    action: EntryCreationAction,
+    acceptance: AppletProposalAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    let (record, _proposal, rules) =
+        match must_get_applet_proposal(acceptance.proposal_hash.clone())? {
+            Ok(proposal) => proposal,
+            Err(invalid) => return Ok(invalid),
+        };
+    let closes_at = voting_closes_at(record.action().timestamp(), &rules);
+    if action.timestamp() <= &closes_at {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletProposals can only be accepted after their voting period has closed",
+        )));
+    }
+
+    let mut voters: HashSet<AgentPubKey> = HashSet::new();
+    for approving_vote in acceptance.approving_votes {
+        let record = must_get_valid_record(approving_vote.vote_hash.clone())?;
+        let vote = match record.entry().to_app_option::<AppletVote>() {
+            Ok(Some(vote)) => vote,
+            _ => {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Approving votes must point to AppletVotes",
+                )))
+            }
+        };
+        if vote.proposal_hash.ne(&acceptance.proposal_hash) || !vote.approve {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Approving votes must approve the accepted AppletProposal",
+            )));
+        }
+        let voter = record.action().author().clone();
+        if !voters.insert(voter.clone()) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Approving votes must be from distinct agents",
+            )));
+        }
+        // Votes are only valid until the voting period closes, so every later vote of
+        // the voter is below any of their actions from after that
+        let voter_chain_top = if voter.eq(action.author()) {
+            action.prev_action().clone()
+        } else {
+            let Some(voter_chain_top) = approving_vote.voter_chain_top else {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Approving votes of other agents must refer to an action of their voter",
+                )));
+            };
+            let voter_action = must_get_action(voter_chain_top.clone())?;
+            if voter_action.action().author().ne(&voter)
+                || voter_action.action().timestamp() <= closes_at
+            {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Approving votes must refer to an action of their voter from after the voting period closed",
+                )));
+            }
+            voter_chain_top
+        };
+        let later_votes = entries_created_by(
+            voter,
+            voter_chain_top,
+            Some(approving_vote.vote_hash.clone()),
+            UnitEntryTypes::AppletVote,
+        )?;
+        for (later_vote_hash, _voted_at) in later_votes {
+            if later_vote_hash.eq(&approving_vote.vote_hash) {
+                continue;
+            }
+            let record = must_get_valid_record(later_vote_hash)?;
+            if let Ok(Some(later_vote)) = record.entry().to_app_option::<AppletVote>() {
+                if later_vote.proposal_hash.eq(&acceptance.proposal_hash) {
+                    return Ok(ValidateCallbackResult::Invalid(String::from(
+                        "Approving votes must be the latest votes of their voters",
+                    )));
+                }
+            }
+        }
+    }
+
+    if (voters.len() as u32) < rules.quorum {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "An AppletProposal needs {} approving votes to be accepted",
+            rules.quorum
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_applet_proposal_acceptance(
+    _action: Update,
+    _acceptance: AppletProposalAcceptance,
+    _original_action: EntryCreationAction,
+    _original_acceptance: AppletProposalAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletProposalAcceptances cannot be updated",
+    )))
+}
+
+pub fn validate_delete_applet_proposal_acceptance(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_acceptance: AppletProposalAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletProposalAcceptances cannot be deleted",
+    )))
+}
+
+pub fn validate_create_link_anchor_to_applet_proposal(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(applet_proposals_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToAppletProposal links must be based on the applet_proposals anchor",
+        )));
+    }
+    let Some(proposal_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToAppletProposal link must be the action hash of an AppletProposal",
+        )));
+    };
+    let (record, _proposal, _rules) = match must_get_applet_proposal(proposal_hash)? {
+        Ok(proposal) => proposal,
+        Err(invalid) => return Ok(invalid),
+    };
+    if record.action().author().ne(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletProposals can only be linked by their author",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_applet_proposal_to_vote(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(vote_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AppletProposalToVote link must be the action hash of an AppletVote",
+        )));
+    };
+    let record = must_get_valid_record(vote_hash)?;
+    let vote = match record.entry().to_app_option::<AppletVote>() {
+        Ok(Some(vote)) => vote,
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Target of an AppletProposalToVote link must be the action hash of an AppletVote",
+            )))
+        }
+    };
+    if base_address.ne(&AnyLinkableHash::from(vote.proposal_hash)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletProposalToVote links must be based on the proposal that was voted on",
+        )));
+    }
+    if record.action().author().ne(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletVotes can only be linked by their author",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_applet_proposal_to_acceptance(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(acceptance_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AppletProposalToAcceptance link must be the action hash of an AppletProposalAcceptance",
+        )));
+    };
+    let record = must_get_valid_record(acceptance_hash)?;
+    match record.entry().to_app_option::<AppletProposalAcceptance>() {
+        Ok(Some(acceptance))
+            if base_address.eq(&AnyLinkableHash::from(acceptance.proposal_hash.clone())) =>
+        {
+            Ok(ValidateCallbackResult::Valid)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletProposalToAcceptance links must point from a proposal to its acceptance",
+        ))),
+    }
+}
@@ -0,0 +1,145 @@
+use hdi::prelude::*;
+
+use crate::validate_applet_address;
+
+pub const MAX_APPLET_TAG_LENGTH: usize = 50;
+
+/// Tags and categories are stored as lowercase strings without surrounding whitespace,
+/// so that e.g. "Chat" and "chat " end up at the same path
+pub fn normalize_applet_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+pub fn applet_tag_path(tag: &str) -> Path {
+    Path::from(vec![Component::from("applet_tags"), Component::from(tag)])
+}
+
+pub fn applet_category_path(category: &str) -> Path {
+    Path::from(vec![
+        Component::from("applet_categories"),
+        Component::from(category),
+    ])
+}
+
+/// The tag or category that an applet tag or category link is about is stored in the
+/// link tag as UTF-8, so that it can be read without fetching the path
+pub fn applet_tag_from_link_tag(tag: LinkTag) -> Option<String> {
+    String::from_utf8(tag.0).ok()
+}
+
+fn validate_applet_tag(tag: &str, kind: &str) -> ValidateCallbackResult {
+    if tag.is_empty() {
+        return ValidateCallbackResult::Invalid(format!("Applet {} must not be empty", kind));
+    }
+    if tag.chars().count() > MAX_APPLET_TAG_LENGTH {
+        return ValidateCallbackResult::Invalid(format!(
+            "Applet {} must not be longer than {} characters",
+            kind, MAX_APPLET_TAG_LENGTH
+        ));
+    }
+    if normalize_applet_tag(tag).ne(tag) {
+        return ValidateCallbackResult::Invalid(format!(
+            "Applet {} must be lowercase and must not start or end with whitespace",
+            kind
+        ));
+    }
+    ValidateCallbackResult::Valid
+}
+
+/// Links between an Applet and the path of one of its tags or categories, in either
+/// direction. The path must be the one of the tag or category in the link tag.
+fn validate_create_link_applet_label(
+    applet_address: AnyLinkableHash,
+    path_address: AnyLinkableHash,
+    tag: LinkTag,
+    path: fn(&str) -> Path,
+    kind: &str,
+    link_name: &str,
+) -> ExternResult<ValidateCallbackResult> {
+    let result =
+        validate_applet_address(applet_address, &format!("Applet of an {} link", link_name))?;
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+    let Some(label) = applet_tag_from_link_tag(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of an {} link must be a UTF-8 string",
+            link_name
+        )));
+    };
+    let result = validate_applet_tag(&label, kind);
+    if let ValidateCallbackResult::Invalid(_) = result {
+        return Ok(result);
+    }
+    if path_address.ne(&AnyLinkableHash::from(path(&label).path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "{} links must refer to the path of the {} in their tag",
+            link_name, kind
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_applet_to_tag(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_applet_label(
+        base_address,
+        target_address,
+        tag,
+        applet_tag_path,
+        "tag",
+        "AppletToTag",
+    )
+}
+
+pub fn validate_create_link_tag_to_applet(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_applet_label(
+        target_address,
+        base_address,
+        tag,
+        applet_tag_path,
+        "tag",
+        "TagToApplet",
+    )
+}
+
+pub fn validate_create_link_applet_to_category(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_applet_label(
+        base_address,
+        target_address,
+        tag,
+        applet_category_path,
+        "category",
+        "AppletToCategory",
+    )
+}
+
+pub fn validate_create_link_category_to_applet(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_applet_label(
+        target_address,
+        base_address,
+        tag,
+        applet_category_path,
+        "category",
+        "CategoryToApplet",
+    )
+}
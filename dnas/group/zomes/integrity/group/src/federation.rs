@@ -0,0 +1,286 @@
+use hdi::prelude::*;
+
+use crate::{validate_applet_address, validate_steward_permission, StewardPermission};
+
+/// Created in the group that proposes to federate one of its Applets with another group
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AppletFederationProposal {
+    pub applet_hash: EntryHash,
+    pub proposing_group_dna_hash: DnaHash,
+    pub invited_group_dna_hash: DnaHash,
+}
+
+/// Created in the invited group by one of its members to accept an
+/// AppletFederationProposal of another group
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AppletFederationAcceptance {
+    pub applet_hash: EntryHash,
+    /// Action hash of the AppletFederationProposal in the proposing group
+    pub proposal_hash: ActionHash,
+    pub proposing_group_dna_hash: DnaHash,
+    pub accepting_group_dna_hash: DnaHash,
+}
+
+/// An AppletFederationAcceptance signed by the steward of the accepting group who
+/// created it. The proposing group can only check the signature, not that the signer
+/// belongs to the invited group, see `validate_create_link_applet_to_invited_group`.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+pub struct SignedAppletFederationAcceptance {
+    pub acceptance: AppletFederationAcceptance,
+    pub accepting_agent: AgentPubKey,
+    pub signature: Signature,
+}
+
+/// Stored in the tag of AppletToInvitedGroup links. Extends the StewardPermission with
+/// the signed acceptance of the invited group, which is required for links to an
+/// AppletFederationProposal, so that StewardPermission::from_tag keeps working for
+/// these tags.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Default)]
+pub struct AppletToInvitedGroupTag {
+    pub permission_hash: Option<ActionHash>,
+    #[serde(default)]
+    pub progenitor_chain_top: Option<ActionHash>,
+    #[serde(default)]
+    pub valid_until: Option<Timestamp>,
+    #[serde(default)]
+    pub progenitor_signature: Option<Signature>,
+    #[serde(default)]
+    pub signed_acceptance: Option<SignedAppletFederationAcceptance>,
+}
+
+impl AppletToInvitedGroupTag {
+    pub fn from_tag(tag: LinkTag) -> Self {
+        AppletToInvitedGroupTag::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0)))
+            .unwrap_or_default()
+    }
+
+    pub fn to_tag(self) -> ExternResult<LinkTag> {
+        Ok(LinkTag::new(
+            SerializedBytes::try_from(self)
+                .map_err(|err| wasm_error!(err))?
+                .bytes()
+                .clone(),
+        ))
+    }
+}
+
+/// Returns the applet hash and the DnaHash of the other group for records of either
+/// side of a federation, or None if the record is neither a proposal nor an acceptance
+pub fn federated_group_from_record(record: &Record) -> Option<(EntryHash, DnaHash)> {
+    if let Ok(Some(proposal)) = record.entry().to_app_option::<AppletFederationProposal>() {
+        return Some((proposal.applet_hash, proposal.invited_group_dna_hash));
+    }
+    if let Ok(Some(acceptance)) = record.entry().to_app_option::<AppletFederationAcceptance>() {
+        return Some((acceptance.applet_hash, acceptance.proposing_group_dna_hash));
+    }
+    None
+}
+
+pub fn validate_create_applet_federation_proposal(
+    _action: EntryCreationAction,
+    proposal: AppletFederationProposal,
+) -> ExternResult<ValidateCallbackResult> {
+    let dna_hash = dna_info()?.hash;
+    if proposal.proposing_group_dna_hash.ne(&dna_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletFederationProposals can only be created in the proposing group",
+        )));
+    }
+    if proposal.invited_group_dna_hash.eq(&dna_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "A group cannot federate an Applet with itself",
+        )));
+    }
+    validate_applet_address(
+        proposal.applet_hash.into(),
+        "Applet of an AppletFederationProposal",
+    )
+}
+
+pub fn validate_update_applet_federation_proposal(
+    _action: Update,
+    _proposal: AppletFederationProposal,
+    _original_action: EntryCreationAction,
+    _original_proposal: AppletFederationProposal,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletFederationProposals cannot be updated",
+    )))
+}
+
+pub fn validate_delete_applet_federation_proposal(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_proposal: AppletFederationProposal,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletFederationProposals cannot be deleted",
+    )))
+}
+
+pub fn validate_create_applet_federation_acceptance(
+    _action: EntryCreationAction,
+    acceptance: AppletFederationAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    let dna_hash = dna_info()?.hash;
+    if acceptance.accepting_group_dna_hash.ne(&dna_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletFederationAcceptances can only be created in the accepting group",
+        )));
+    }
+    if acceptance.proposing_group_dna_hash.eq(&dna_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "A group cannot federate an Applet with itself",
+        )));
+    }
+    validate_applet_address(
+        acceptance.applet_hash.into(),
+        "Applet of an AppletFederationAcceptance",
+    )
+}
+
+pub fn validate_update_applet_federation_acceptance(
+    _action: Update,
+    _acceptance: AppletFederationAcceptance,
+    _original_action: EntryCreationAction,
+    _original_acceptance: AppletFederationAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletFederationAcceptances cannot be updated",
+    )))
+}
+
+pub fn validate_delete_applet_federation_acceptance(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_acceptance: AppletFederationAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AppletFederationAcceptances cannot be deleted",
+    )))
+}
+
+pub fn validate_create_link_applet_to_federation_proposal(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AppletToFederationProposal link must be an AppletFederationProposal",
+        )));
+    };
+    let record = must_get_valid_record(action_hash)?;
+    match record.entry().to_app_option::<AppletFederationProposal>() {
+        Ok(Some(proposal)) if base_address.eq(&AnyLinkableHash::from(proposal.applet_hash)) => {
+            Ok(ValidateCallbackResult::Valid)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletToFederationProposal links must point from an Applet to a proposal for that Applet",
+        ))),
+    }
+}
+
+/// AppletToInvitedGroup links mark the completed federation of an Applet. In the
+/// proposing group they point to the AppletFederationProposal and carry the acceptance
+/// of the invited group, signed by its author. In the accepting group they point to the
+/// AppletFederationAcceptance of the author of the link. Only stewards can federate
+/// Applets.
+///
+/// The proposing group cannot read the DHT of the invited group, so it only verifies
+/// the signature over the acceptance. That its author is a steward of the invited
+/// group is validated there, on the AppletToInvitedGroup link to the acceptance. Nothing
+/// binds the signer to the invited group on this side though: the DnaHash of a group
+/// can't be derived from its progenitor inside a zome, so a steward of the proposing
+/// group could sign an acceptance themselves. Federations registered in the proposing
+/// group are therefore unverified claims of its stewards, and shown as such.
+pub fn validate_create_link_applet_to_invited_group(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AppletToInvitedGroup link must be a federation proposal or acceptance",
+        )));
+    };
+    let record = must_get_valid_record(action_hash.clone())?;
+    let Some((applet_hash, _group_dna_hash)) = federated_group_from_record(&record) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AppletToInvitedGroup link must be a federation proposal or acceptance",
+        )));
+    };
+    if base_address.ne(&AnyLinkableHash::from(applet_hash)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AppletToInvitedGroup links must point from an Applet to a federation proposal or acceptance for that Applet",
+        )));
+    }
+
+    match record.entry().to_app_option::<AppletFederationProposal>() {
+        Ok(Some(proposal)) => {
+            let Some(signed_acceptance) =
+                AppletToInvitedGroupTag::from_tag(tag.clone()).signed_acceptance
+            else {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Federations can only be registered with the acceptance of the invited group",
+                )));
+            };
+            let result = validate_signed_applet_federation_acceptance(
+                action_hash,
+                proposal,
+                signed_acceptance,
+            )?;
+            if let ValidateCallbackResult::Invalid(_) = result {
+                return Ok(result);
+            }
+        }
+        _ => {
+            if record.action().author().ne(&action.author) {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Only the author of an AppletFederationAcceptance can link it to the Applet",
+                )));
+            }
+        }
+    }
+
+    validate_steward_permission(
+        &action.author,
+        action.timestamp,
+        StewardPermission::from_tag(tag),
+    )
+}
+
+fn validate_signed_applet_federation_acceptance(
+    proposal_hash: ActionHash,
+    proposal: AppletFederationProposal,
+    signed_acceptance: SignedAppletFederationAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    let acceptance = signed_acceptance.acceptance.clone();
+    if acceptance.proposal_hash.ne(&proposal_hash)
+        || acceptance.applet_hash.ne(&proposal.applet_hash)
+        || acceptance
+            .proposing_group_dna_hash
+            .ne(&proposal.proposing_group_dna_hash)
+        || acceptance
+            .accepting_group_dna_hash
+            .ne(&proposal.invited_group_dna_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The AppletFederationAcceptance does not accept this AppletFederationProposal",
+        )));
+    }
+    if !verify_signature(
+        signed_acceptance.accepting_agent,
+        signed_acceptance.signature,
+        acceptance,
+    )? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Invalid signature of the AppletFederationAcceptance",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
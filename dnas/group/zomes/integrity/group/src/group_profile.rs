@@ -0,0 +1,141 @@
+use hdi::prelude::*;
+
+use crate::{validate_steward_permission, GroupProfile, StewardPermission};
+
+pub const MAX_GROUP_NAME_LENGTH: usize = 100;
+/// Logos are stored inline as data URLs so they need to be capped well below
+/// the maximum entry size
+pub const MAX_GROUP_LOGO_SRC_LENGTH: usize = 1_000_000;
+pub const MAX_GROUP_DESCRIPTION_LENGTH: usize = 5000;
+pub const MAX_GROUP_BANNER_SRC_LENGTH: usize = 1_000_000;
+pub const MAX_GROUP_TAGS: usize = 20;
+pub const MAX_GROUP_TAG_LENGTH: usize = 50;
+
+pub fn validate_group_profile(group_profile: &GroupProfile) -> ExternResult<ValidateCallbackResult> {
+    if group_profile.name.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Group name must not be empty",
+        )));
+    }
+    if group_profile.name.chars().count() > MAX_GROUP_NAME_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Group name must not be longer than {} characters",
+            MAX_GROUP_NAME_LENGTH
+        )));
+    }
+    if group_profile.logo_src.is_empty() && group_profile.logo_blob_hash.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Group logo must not be empty",
+        )));
+    }
+    if group_profile.logo_src.len() > MAX_GROUP_LOGO_SRC_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Group logo must not be larger than {} bytes",
+            MAX_GROUP_LOGO_SRC_LENGTH
+        )));
+    }
+    if group_profile.description.chars().count() > MAX_GROUP_DESCRIPTION_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Group description must not be longer than {} characters",
+            MAX_GROUP_DESCRIPTION_LENGTH
+        )));
+    }
+    if let Some(banner_src) = &group_profile.banner_src {
+        if banner_src.is_empty() {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Group banner must either be omitted or non-empty",
+            )));
+        }
+        if banner_src.len() > MAX_GROUP_BANNER_SRC_LENGTH {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Group banner must not be larger than {} bytes",
+                MAX_GROUP_BANNER_SRC_LENGTH
+            )));
+        }
+    }
+    if group_profile.tags.len() > MAX_GROUP_TAGS {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Groups must not have more than {} tags",
+            MAX_GROUP_TAGS
+        )));
+    }
+    for (i, tag) in group_profile.tags.iter().enumerate() {
+        if tag.trim().is_empty() || tag.chars().count() > MAX_GROUP_TAG_LENGTH {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Group tags must be non-empty and not longer than {} characters",
+                MAX_GROUP_TAG_LENGTH
+            )));
+        }
+        if group_profile.tags[..i].contains(tag) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Group tags must be unique",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_group_profile(
+    _action: EntryCreationAction,
+    group_profile: GroupProfile,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_group_profile(&group_profile)
+}
+
+pub fn validate_update_group_profile(
+    _action: Update,
+    _group_profile: GroupProfile,
+    _original_action: EntryCreationAction,
+    _original_group_profile: GroupProfile,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "GroupProfiles cannot be updated, create a new one instead",
+    )))
+}
+
+pub fn validate_delete_group_profile(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_group_profile: GroupProfile,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "GroupProfiles cannot be deleted",
+    )))
+}
+
+/// Only stewards may change the group profile
+pub fn validate_create_link_anchor_to_group_profile(
+    action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToGroupProfile link must be the action hash of a GroupProfile",
+        )));
+    };
+    let record = must_get_valid_record(action_hash)?;
+    match record.entry().to_app_option::<GroupProfile>() {
+        Ok(Some(_group_profile)) => validate_steward_permission(
+            &action.author,
+            action.timestamp,
+            StewardPermission::from_tag(tag),
+        ),
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToGroupProfile link must be the action hash of a GroupProfile",
+        ))),
+    }
+}
+
+pub fn validate_delete_link_anchor_to_group_profile(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AnchorToGroupProfile links cannot be deleted",
+    )))
+}
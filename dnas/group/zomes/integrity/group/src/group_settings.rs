@@ -0,0 +1,322 @@
+use hdi::prelude::*;
+
+use crate::{
+    entries_created_by, progenitor, validate_steward_permission, StewardPermission,
+    UnitEntryTypes,
+};
+
+/// Who may register Applets in the group, i.e. link them from the group_applets anchor
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum AppletAddPolicy {
+    #[default]
+    Anyone,
+    Stewards,
+    ProgenitorOnly,
+}
+
+/// Who may archive Applets of the group
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum AppletArchivePolicy {
+    /// The agent that registered the Applet, provided they were allowed to register it,
+    /// and the progenitor
+    #[default]
+    RegistrantOrProgenitor,
+    ProgenitorOnly,
+}
+
+/// If set in the GroupSettings, Applets must be proposed and accepted by a quorum of
+/// members before they are added to the group
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AppletProposalRules {
+    /// Number of distinct members that need to approve a proposal
+    pub quorum: u32,
+    /// How long after a proposal has been made members can vote on it
+    pub voting_period_seconds: u64,
+}
+
+/// Group-wide policies. Can only be set by the progenitor, groups without a progenitor
+/// always use the default settings.
+#[hdk_entry_helper]
+#[derive(Clone, Default, PartialEq)]
+pub struct GroupSettings {
+    pub applet_add_policy: AppletAddPolicy,
+    pub applet_archive_policy: AppletArchivePolicy,
+    #[serde(default)]
+    pub applet_proposal_rules: Option<AppletProposalRules>,
+    /// The GroupSettings that these settings replace, None for the first GroupSettings
+    /// of the group. Must be the latest GroupSettings on the chain of the progenitor.
+    #[serde(default)]
+    pub previous_group_settings_hash: Option<ActionHash>,
+}
+
+/// Stored in the tag of AnchorToApplet links. Extends the StewardPermission with the
+/// GroupSettings that were in effect for the author when they created the link, so
+/// that StewardPermission::from_tag keeps working for these tags.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Default)]
+pub struct AnchorToAppletTag {
+    pub permission_hash: Option<ActionHash>,
+    #[serde(default)]
+    pub progenitor_chain_top: Option<ActionHash>,
+    #[serde(default)]
+    pub valid_until: Option<Timestamp>,
+    #[serde(default)]
+    pub progenitor_signature: Option<Signature>,
+    #[serde(default)]
+    pub group_settings_hash: Option<ActionHash>,
+    /// Required if the GroupSettings demand that Applets are proposed and voted on
+    #[serde(default)]
+    pub proposal_acceptance_hash: Option<ActionHash>,
+    /// Set when unarchiving an Applet: the AnchorToApplet link that was deleted when
+    /// the Applet got archived. Only stewards can unarchive Applets.
+    #[serde(default)]
+    pub archived_link_hash: Option<ActionHash>,
+}
+
+impl AnchorToAppletTag {
+    pub fn from_tag(tag: LinkTag) -> Self {
+        AnchorToAppletTag::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0)))
+            .unwrap_or_default()
+    }
+
+    pub fn to_tag(self) -> ExternResult<LinkTag> {
+        Ok(LinkTag::new(
+            SerializedBytes::try_from(self)
+                .map_err(|err| wasm_error!(err))?
+                .bytes()
+                .clone(),
+        ))
+    }
+}
+
+pub fn group_settings_path() -> Path {
+    Path::from("group_settings")
+}
+
+/// Resolves the GroupSettings referenced by a link. No reference means that the author
+/// did not know of any settings, so the default settings apply. Doesn't check whether
+/// the settings had been superseded, see group_settings_in_effect for that.
+pub fn group_settings_at(
+    group_settings_hash: Option<ActionHash>,
+    timestamp: Timestamp,
+) -> ExternResult<Result<GroupSettings, ValidateCallbackResult>> {
+    let Some(group_settings_hash) = group_settings_hash else {
+        return Ok(Ok(GroupSettings::default()));
+    };
+    let record = must_get_valid_record(group_settings_hash)?;
+    if record.action().timestamp() > timestamp {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "Referenced GroupSettings must have been created before the link",
+        ))));
+    }
+    match record.entry().to_app_option::<GroupSettings>() {
+        Ok(Some(group_settings)) => Ok(Ok(group_settings)),
+        _ => Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "Referenced GroupSettings hash must point to a GroupSettings entry",
+        )))),
+    }
+}
+
+/// Resolves the GroupSettings that were in effect when a link was created. The link
+/// must reference the chain top of the progenitor that its author knew of, and the
+/// referenced GroupSettings must not have been superseded on the progenitor's chain
+/// before the link was created. Once the progenitor has set GroupSettings, links that
+/// don't reference any are invalid.
+pub fn group_settings_in_effect(
+    group_settings_hash: Option<ActionHash>,
+    progenitor_chain_top: Option<ActionHash>,
+    timestamp: Timestamp,
+) -> ExternResult<Result<GroupSettings, ValidateCallbackResult>> {
+    let Some(progenitor) = progenitor()? else {
+        return group_settings_at(group_settings_hash, timestamp);
+    };
+    let Some(progenitor_chain_top) = progenitor_chain_top else {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "Links that depend on the GroupSettings must refer to the chain of the progenitor",
+        ))));
+    };
+    let group_settings = match group_settings_at(group_settings_hash.clone(), timestamp)? {
+        Ok(group_settings) => group_settings,
+        Err(invalid) => return Ok(Err(invalid)),
+    };
+    let superseded = entries_created_by(
+        progenitor,
+        progenitor_chain_top,
+        group_settings_hash.clone(),
+        UnitEntryTypes::GroupSettings,
+    )?
+    .into_iter()
+    .any(|(action_hash, created_at)| {
+        Some(action_hash).ne(&group_settings_hash) && created_at <= timestamp
+    });
+    if superseded {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "Referenced GroupSettings had already been superseded when the link was created",
+        ))));
+    }
+    Ok(Ok(group_settings))
+}
+
+/// Checks whether the author of an AnchorToApplet link was allowed to register Applets
+/// under the referenced GroupSettings
+pub fn validate_applet_add_policy(
+    action: &CreateLink,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let tag = AnchorToAppletTag::from_tag(tag);
+    let group_settings = match group_settings_in_effect(
+        tag.group_settings_hash,
+        tag.progenitor_chain_top.clone(),
+        action.timestamp,
+    )? {
+        Ok(group_settings) => group_settings,
+        Err(invalid) => return Ok(invalid),
+    };
+    match group_settings.applet_add_policy {
+        AppletAddPolicy::Anyone => Ok(ValidateCallbackResult::Valid),
+        AppletAddPolicy::Stewards => validate_steward_permission(
+            &action.author,
+            action.timestamp,
+            StewardPermission {
+                permission_hash: tag.permission_hash,
+                progenitor_chain_top: tag.progenitor_chain_top,
+                valid_until: tag.valid_until,
+                progenitor_signature: tag.progenitor_signature,
+            },
+        ),
+        AppletAddPolicy::ProgenitorOnly => match progenitor()? {
+            Some(progenitor) if progenitor.eq(&action.author) => {
+                Ok(ValidateCallbackResult::Valid)
+            }
+            _ => Ok(ValidateCallbackResult::Invalid(String::from(
+                "Only the progenitor can register Applets in this group",
+            ))),
+        },
+    }
+}
+
+pub fn validate_create_group_settings(
+    action: EntryCreationAction,
+    group_settings: GroupSettings,
+) -> ExternResult<ValidateCallbackResult> {
+    match progenitor()? {
+        Some(progenitor) if progenitor.eq(action.author()) => (),
+        Some(_) => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Only the progenitor of the group can set the GroupSettings",
+            )))
+        }
+        None => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Groups without a progenitor cannot have GroupSettings",
+            )))
+        }
+    }
+    // The previous GroupSettings must be the latest ones on the chain of the progenitor
+    if let Some(previous_group_settings_hash) = group_settings.previous_group_settings_hash.clone()
+    {
+        let record = must_get_valid_record(previous_group_settings_hash.clone())?;
+        if record.action().author().ne(action.author()) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Previous GroupSettings must have been created by the progenitor",
+            )));
+        }
+        if !matches!(record.entry().to_app_option::<GroupSettings>(), Ok(Some(_))) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Previous GroupSettings hash must point to a GroupSettings entry",
+            )));
+        }
+    }
+    let skipped_group_settings = entries_created_by(
+        action.author().clone(),
+        action.prev_action().clone(),
+        group_settings.previous_group_settings_hash.clone(),
+        UnitEntryTypes::GroupSettings,
+    )?
+    .into_iter()
+    .any(|(action_hash, _)| Some(action_hash).ne(&group_settings.previous_group_settings_hash));
+    if skipped_group_settings {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "GroupSettings must reference the latest GroupSettings of the progenitor as their predecessor",
+        )));
+    }
+    if let Some(rules) = group_settings.applet_proposal_rules {
+        if rules.quorum == 0 {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The quorum for applet proposals must be at least 1",
+            )));
+        }
+        if rules.voting_period_seconds == 0 {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The voting period for applet proposals must not be empty",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_group_settings(
+    _action: Update,
+    _group_settings: GroupSettings,
+    _original_action: EntryCreationAction,
+    _original_group_settings: GroupSettings,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "GroupSettings cannot be updated, create new ones instead",
+    )))
+}
+
+pub fn validate_delete_group_settings(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_group_settings: GroupSettings,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "GroupSettings cannot be deleted",
+    )))
+}
+
+/// Only the progenitor may change the GroupSettings
+pub fn validate_create_link_anchor_to_group_settings(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(group_settings_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToGroupSettings links must be based on the group_settings anchor",
+        )));
+    }
+    match progenitor()? {
+        Some(progenitor) if progenitor.eq(&action.author) => (),
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Only the progenitor of the group can set the GroupSettings",
+            )))
+        }
+    }
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToGroupSettings link must be the action hash of GroupSettings",
+        )));
+    };
+    let record = must_get_valid_record(action_hash)?;
+    match record.entry().to_app_option::<GroupSettings>() {
+        Ok(Some(_group_settings)) => Ok(ValidateCallbackResult::Valid),
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToGroupSettings link must be the action hash of GroupSettings",
+        ))),
+    }
+}
+
+pub fn validate_delete_link_anchor_to_group_settings(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "AnchorToGroupSettings links cannot be deleted",
+    )))
+}
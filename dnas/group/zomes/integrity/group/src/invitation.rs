@@ -0,0 +1,109 @@
+use hdi::prelude::*;
+
+use crate::{
+    group_dna_properties, progenitor, validate_steward_permission, GroupInvitation,
+    SignedGroupInvitation, StewardPermission,
+};
+
+/// Checks the parts of an invitation that can be checked without network access,
+/// i.e. that it is meant for the joining agent and this DNA and that it has been
+/// signed by the inviter.
+fn check_invitation(
+    agent_pub_key: &AgentPubKey,
+    membrane_proof: &Option<MembraneProof>,
+) -> ExternResult<Result<GroupInvitation, ValidateCallbackResult>> {
+    let Some(membrane_proof) = membrane_proof else {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "This group is invite-only: joining requires an invitation as membrane proof",
+        ))));
+    };
+    let Ok(signed_invitation) = SignedGroupInvitation::try_from((**membrane_proof).clone())
+    else {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "The membrane proof is not a valid group invitation",
+        ))));
+    };
+    let invitation = signed_invitation.invitation;
+
+    if invitation.invitee.ne(agent_pub_key) {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "The group invitation was issued to a different agent",
+        ))));
+    }
+    if invitation.group_dna_hash.ne(&dna_info()?.hash) {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "The group invitation was issued for a different group",
+        ))));
+    }
+    if !verify_signature(
+        invitation.inviter.clone(),
+        signed_invitation.signature,
+        invitation.clone(),
+    )? {
+        return Ok(Err(ValidateCallbackResult::Invalid(String::from(
+            "The signature of the group invitation is invalid",
+        ))));
+    }
+
+    Ok(Ok(invitation))
+}
+
+/// Run by the joining agent before joining, so that they don't join with a membrane
+/// proof that the other members would reject anyway
+#[hdk_extern]
+pub fn genesis_self_check(data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
+    if !group_dna_properties()?.invite_only {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if progenitor()?.eq(&Some(data.agent_key.clone())) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    match check_invitation(&data.agent_key, &data.membrane_proof)? {
+        Ok(_invitation) => Ok(ValidateCallbackResult::Valid),
+        Err(invalid) => Ok(invalid),
+    }
+}
+
+/// In invite-only groups, every agent except the progenitor needs to join with a
+/// SignedGroupInvitation as membrane proof, issued by the progenitor or by a steward
+/// whose StewardGrant the progenitor attested until at least the time of joining
+pub fn validate_agent_joining(
+    agent_pub_key: AgentPubKey,
+    membrane_proof: &Option<MembraneProof>,
+    timestamp: Timestamp,
+) -> ExternResult<ValidateCallbackResult> {
+    if !group_dna_properties()?.invite_only {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(progenitor) = progenitor()? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Invite-only groups need a progenitor to issue invitations",
+        )));
+    };
+    if progenitor.eq(&agent_pub_key) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let invitation = match check_invitation(&agent_pub_key, membrane_proof)? {
+        Ok(invitation) => invitation,
+        Err(invalid) => return Ok(invalid),
+    };
+
+    if let Some(expiry) = invitation.expiry {
+        if expiry <= timestamp {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The group invitation has expired",
+            )));
+        }
+    }
+
+    validate_steward_permission(
+        &invitation.inviter,
+        timestamp,
+        StewardPermission {
+            permission_hash: invitation.steward_grant_hash,
+            progenitor_chain_top: invitation.progenitor_chain_top,
+            valid_until: invitation.valid_until,
+            progenitor_signature: invitation.progenitor_signature,
+        },
+    )
+}
@@ -1,5 +1,29 @@
 use hdi::prelude::*;
-pub use we_types::{Applet, GroupProfile};
+pub use we_types::{
+    Applet, GroupDnaProperties, GroupInvitation, GroupProfile, SignedGroupInvitation,
+    ANCHOR_TO_APPLET_LINK_TYPE, GROUP_APPLETS_ANCHOR,
+};
+
+pub mod applet;
+pub mod applet_proposal;
+pub mod applet_tag;
+pub mod federation;
+pub mod group_profile;
+pub mod group_settings;
+pub mod invitation;
+pub mod membership;
+pub mod related_group;
+pub mod steward;
+pub use applet::*;
+pub use applet_proposal::*;
+pub use applet_tag::*;
+pub use federation::*;
+pub use group_profile::*;
+pub use group_settings::*;
+pub use invitation::*;
+pub use membership::*;
+pub use related_group::*;
+pub use steward::*;
 
 #[hdk_entry_defs]
 #[unit_enum(UnitEntryTypes)]
@@ -9,6 +33,14 @@ pub enum EntryTypes {
     AppletPrivate(Applet),
     RelatedGroup(RelatedGroup),
     GroupProfile(GroupProfile),
+    StewardGrant(StewardGrant),
+    StewardRevocation(StewardRevocation),
+    AppletFederationProposal(AppletFederationProposal),
+    AppletFederationAcceptance(AppletFederationAcceptance),
+    GroupSettings(GroupSettings),
+    AppletProposal(AppletProposal),
+    AppletVote(AppletVote),
+    AppletProposalAcceptance(AppletProposalAcceptance),
 }
 
 #[hdk_link_types]
@@ -16,23 +48,627 @@ pub enum LinkTypes {
     AnchorToApplet,
     AgentToApplet,
     AppletToExternalAgent,
-    AppletToInvitedGroup, // links to "first-order" neighbor groups if an app is being federated
+    AppletToInvitedGroup, // links to the federation records of "first-order" neighbor groups if an app is being federated
     GroupInfoPath,
     AnchorToFederatedApplet,
     AnchorToGroupProfile,
     AnchorToRelatedGroup,
+    AnchorToStewardGrant,
+    AgentToStewardGrant,
+    StewardGrantToRevocation,
+    AppletToJoinedAgent,
+    AppletToFederationProposal,
+    AppletToSuccessor,
+    AppletToPredecessor,
+    AnchorToMember,
+    AnchorToGroupSettings,
+    AnchorToAppletProposal,
+    AppletProposalToVote,
+    AppletProposalToAcceptance,
+    AppletToTag,
+    TagToApplet,
+    AppletToCategory,
+    CategoryToApplet,
 }
 
-#[hdk_entry_helper]
-#[derive(Clone)]
-pub struct RelatedGroup {
-    pub group_profile: GroupProfile,
-    pub network_seed: String,
-    pub group_dna_hash: DnaHash,
-}
+// The custom_views integrity zome recognizes AnchorToApplet links by their index
+const _: () = assert!(LinkTypes::AnchorToApplet as u8 == ANCHOR_TO_APPLET_LINK_TYPE);
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RegisterAppletFederationInput {
     pub applet_hash: EntryHash,
-    pub group_dna_hash: EntryHash,
+    pub group_dna_hash: DnaHash,
+    /// The AppletFederationProposal that the other group has accepted
+    pub proposal_hash: ActionHash,
+    /// The acceptance of the proposal, as returned by `accept_applet_federation` in
+    /// the other group
+    pub signed_acceptance: SignedAppletFederationAcceptance,
+}
+
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op.flattened::<EntryTypes, LinkTypes>()? {
+        FlatOp::StoreEntry(store_entry) => match store_entry {
+            OpEntry::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpEntry::UpdateEntry {
+                app_entry, action, ..
+            } => validate_create_entry(EntryCreationAction::Update(action), app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterUpdate(update_entry) => match update_entry {
+            OpUpdate::Entry {
+                original_action,
+                original_app_entry,
+                app_entry,
+                action,
+            } => validate_update_entry(action, app_entry, original_action, original_app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterDelete(delete_entry) => match delete_entry {
+            OpDelete::Entry {
+                original_action,
+                original_app_entry,
+                action,
+            } => validate_delete_entry(action, original_action, original_app_entry),
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterCreateLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            action,
+        } => validate_create_link(action, link_type, base_address, target_address, tag),
+        FlatOp::RegisterDeleteLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            original_action,
+            action,
+        } => validate_delete_link(
+            action,
+            original_action,
+            link_type,
+            base_address,
+            target_address,
+            tag,
+        ),
+        FlatOp::StoreRecord(store_record) => match store_record {
+            OpRecord::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpRecord::UpdateEntry {
+                original_action_hash,
+                app_entry,
+                action,
+                ..
+            } => {
+                let result = validate_create_entry(
+                    EntryCreationAction::Update(action.clone()),
+                    app_entry.clone(),
+                )?;
+                if let ValidateCallbackResult::Valid = result {
+                    match get_original_app_entry(original_action_hash)? {
+                        Ok(Some((original_action, original_app_entry))) => validate_update_entry(
+                            action,
+                            app_entry,
+                            original_action,
+                            original_app_entry,
+                        ),
+                        Ok(None) => Ok(ValidateCallbackResult::Valid),
+                        Err(invalid) => Ok(invalid),
+                    }
+                } else {
+                    Ok(result)
+                }
+            }
+            OpRecord::DeleteEntry {
+                original_action_hash,
+                action,
+                ..
+            } => match get_original_app_entry(original_action_hash)? {
+                Ok(Some((original_action, original_app_entry))) => {
+                    validate_delete_entry(action, original_action, original_app_entry)
+                }
+                Ok(None) => Ok(ValidateCallbackResult::Valid),
+                Err(invalid) => Ok(invalid),
+            },
+            OpRecord::CreateLink {
+                base_address,
+                target_address,
+                tag,
+                link_type,
+                action,
+            } => validate_create_link(action, link_type, base_address, target_address, tag),
+            OpRecord::DeleteLink {
+                original_action_hash,
+                base_address,
+                action,
+            } => {
+                let record = must_get_valid_record(original_action_hash)?;
+                let create_link = match record.action() {
+                    Action::CreateLink(create_link) => create_link.clone(),
+                    _ => {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "The action that a DeleteLink deletes must be a CreateLink".to_string(),
+                        ));
+                    }
+                };
+                let link_type = match LinkTypes::from_type(
+                    create_link.zome_index.clone(),
+                    create_link.link_type.clone(),
+                )? {
+                    Some(lt) => lt,
+                    None => {
+                        return Ok(ValidateCallbackResult::Valid);
+                    }
+                };
+                validate_delete_link(
+                    action,
+                    create_link.clone(),
+                    link_type,
+                    base_address,
+                    create_link.target_address,
+                    create_link.tag,
+                )
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterAgentActivity(agent_activity) => match agent_activity {
+            OpActivity::CreateAgent { agent, action } => {
+                let previous_action = must_get_action(action.prev_action.clone())?;
+                match previous_action.action() {
+                    Action::AgentValidationPkg(AgentValidationPkg { membrane_proof, .. }) => {
+                        validate_agent_joining(agent, membrane_proof, action.timestamp)
+                    }
+                    _ => Ok(ValidateCallbackResult::Invalid(String::from(
+                        "The previous action for a `CreateAgent` action must be an `AgentValidationPkg`",
+                    ))),
+                }
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+    }
+}
+
+fn validate_create_entry(
+    action: EntryCreationAction,
+    app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match app_entry {
+        EntryTypes::Applet(applet) => validate_create_applet(action, applet),
+        EntryTypes::AppletPrivate(applet) => validate_create_applet(action, applet),
+        EntryTypes::RelatedGroup(related_group) => {
+            validate_create_related_group(action, related_group)
+        }
+        EntryTypes::GroupProfile(group_profile) => {
+            validate_create_group_profile(action, group_profile)
+        }
+        EntryTypes::StewardGrant(steward_grant) => {
+            validate_create_steward_grant(action, steward_grant)
+        }
+        EntryTypes::StewardRevocation(steward_revocation) => {
+            validate_create_steward_revocation(action, steward_revocation)
+        }
+        EntryTypes::AppletFederationProposal(applet_federation_proposal) => {
+            validate_create_applet_federation_proposal(action, applet_federation_proposal)
+        }
+        EntryTypes::AppletFederationAcceptance(applet_federation_acceptance) => {
+            validate_create_applet_federation_acceptance(action, applet_federation_acceptance)
+        }
+        EntryTypes::GroupSettings(group_settings) => {
+            validate_create_group_settings(action, group_settings)
+        }
+        EntryTypes::AppletProposal(applet_proposal) => {
+            validate_create_applet_proposal(action, applet_proposal)
+        }
+        EntryTypes::AppletVote(applet_vote) => validate_create_applet_vote(action, applet_vote),
+        EntryTypes::AppletProposalAcceptance(applet_proposal_acceptance) => {
+            validate_create_applet_proposal_acceptance(action, applet_proposal_acceptance)
+        }
+    }
+}
+
+fn validate_update_entry(
+    action: Update,
+    app_entry: EntryTypes,
+    original_action: EntryCreationAction,
+    original_app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match (app_entry, original_app_entry) {
+        (EntryTypes::Applet(applet), EntryTypes::Applet(original_applet)) => {
+            validate_update_applet(action, applet, original_action, original_applet)
+        }
+        (EntryTypes::AppletPrivate(applet), EntryTypes::AppletPrivate(original_applet)) => {
+            validate_update_applet(action, applet, original_action, original_applet)
+        }
+        (
+            EntryTypes::RelatedGroup(related_group),
+            EntryTypes::RelatedGroup(original_related_group),
+        ) => validate_update_related_group(
+            action,
+            related_group,
+            original_action,
+            original_related_group,
+        ),
+        (
+            EntryTypes::GroupProfile(group_profile),
+            EntryTypes::GroupProfile(original_group_profile),
+        ) => validate_update_group_profile(
+            action,
+            group_profile,
+            original_action,
+            original_group_profile,
+        ),
+        (
+            EntryTypes::StewardGrant(steward_grant),
+            EntryTypes::StewardGrant(original_steward_grant),
+        ) => validate_update_steward_grant(
+            action,
+            steward_grant,
+            original_action,
+            original_steward_grant,
+        ),
+        (
+            EntryTypes::StewardRevocation(steward_revocation),
+            EntryTypes::StewardRevocation(original_steward_revocation),
+        ) => validate_update_steward_revocation(
+            action,
+            steward_revocation,
+            original_action,
+            original_steward_revocation,
+        ),
+        (
+            EntryTypes::AppletFederationProposal(applet_federation_proposal),
+            EntryTypes::AppletFederationProposal(original_applet_federation_proposal),
+        ) => validate_update_applet_federation_proposal(
+            action,
+            applet_federation_proposal,
+            original_action,
+            original_applet_federation_proposal,
+        ),
+        (
+            EntryTypes::AppletFederationAcceptance(applet_federation_acceptance),
+            EntryTypes::AppletFederationAcceptance(original_applet_federation_acceptance),
+        ) => validate_update_applet_federation_acceptance(
+            action,
+            applet_federation_acceptance,
+            original_action,
+            original_applet_federation_acceptance,
+        ),
+        (
+            EntryTypes::GroupSettings(group_settings),
+            EntryTypes::GroupSettings(original_group_settings),
+        ) => validate_update_group_settings(
+            action,
+            group_settings,
+            original_action,
+            original_group_settings,
+        ),
+        (
+            EntryTypes::AppletProposal(applet_proposal),
+            EntryTypes::AppletProposal(original_applet_proposal),
+        ) => validate_update_applet_proposal(
+            action,
+            applet_proposal,
+            original_action,
+            original_applet_proposal,
+        ),
+        (EntryTypes::AppletVote(applet_vote), EntryTypes::AppletVote(original_applet_vote)) => {
+            validate_update_applet_vote(action, applet_vote, original_action, original_applet_vote)
+        }
+        (
+            EntryTypes::AppletProposalAcceptance(applet_proposal_acceptance),
+            EntryTypes::AppletProposalAcceptance(original_applet_proposal_acceptance),
+        ) => validate_update_applet_proposal_acceptance(
+            action,
+            applet_proposal_acceptance,
+            original_action,
+            original_applet_proposal_acceptance,
+        ),
+        _ => Ok(ValidateCallbackResult::Invalid(
+            "Original and updated entry types must be the same".to_string(),
+        )),
+    }
+}
+
+fn validate_delete_entry(
+    action: Delete,
+    original_action: EntryCreationAction,
+    original_app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match original_app_entry {
+        EntryTypes::Applet(applet) => validate_delete_applet(action, original_action, applet),
+        EntryTypes::AppletPrivate(applet) => {
+            validate_delete_applet_private(action, original_action, applet)
+        }
+        EntryTypes::RelatedGroup(related_group) => {
+            validate_delete_related_group(action, original_action, related_group)
+        }
+        EntryTypes::GroupProfile(group_profile) => {
+            validate_delete_group_profile(action, original_action, group_profile)
+        }
+        EntryTypes::StewardGrant(steward_grant) => {
+            validate_delete_steward_grant(action, original_action, steward_grant)
+        }
+        EntryTypes::StewardRevocation(steward_revocation) => {
+            validate_delete_steward_revocation(action, original_action, steward_revocation)
+        }
+        EntryTypes::AppletFederationProposal(applet_federation_proposal) => {
+            validate_delete_applet_federation_proposal(action, original_action, applet_federation_proposal)
+        }
+        EntryTypes::AppletFederationAcceptance(applet_federation_acceptance) => {
+            validate_delete_applet_federation_acceptance(action, original_action, applet_federation_acceptance)
+        }
+        EntryTypes::GroupSettings(group_settings) => {
+            validate_delete_group_settings(action, original_action, group_settings)
+        }
+        EntryTypes::AppletProposal(applet_proposal) => {
+            validate_delete_applet_proposal(action, original_action, applet_proposal)
+        }
+        EntryTypes::AppletVote(applet_vote) => {
+            validate_delete_applet_vote(action, original_action, applet_vote)
+        }
+        EntryTypes::AppletProposalAcceptance(applet_proposal_acceptance) => {
+            validate_delete_applet_proposal_acceptance(
+                action,
+                original_action,
+                applet_proposal_acceptance,
+            )
+        }
+    }
+}
+
+fn validate_create_link(
+    action: CreateLink,
+    link_type: LinkTypes,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::AnchorToApplet => {
+            validate_create_link_anchor_to_applet(action, base_address, target_address, tag)
+        }
+        LinkTypes::AgentToApplet => {
+            validate_create_link_agent_to_applet(action, base_address, target_address, tag)
+        }
+        LinkTypes::AppletToExternalAgent => validate_create_link_applet_to_external_agent(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AppletToInvitedGroup => validate_create_link_applet_to_invited_group(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::GroupInfoPath => Ok(ValidateCallbackResult::Valid),
+        LinkTypes::AnchorToFederatedApplet => validate_create_link_anchor_to_federated_applet(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToGroupProfile => validate_create_link_anchor_to_group_profile(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToRelatedGroup => validate_create_link_anchor_to_related_group(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToStewardGrant => validate_create_link_anchor_to_steward_grant(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AgentToStewardGrant => validate_create_link_agent_to_steward_grant(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::StewardGrantToRevocation => validate_create_link_steward_grant_to_revocation(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AppletToJoinedAgent => validate_create_link_applet_to_joined_agent(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AppletToFederationProposal => {
+            validate_create_link_applet_to_federation_proposal(
+                action,
+                base_address,
+                target_address,
+                tag,
+            )
+        }
+        LinkTypes::AppletToSuccessor => validate_create_link_applet_to_successor(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AppletToPredecessor => validate_create_link_applet_to_predecessor(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToMember => {
+            validate_create_link_anchor_to_member(action, base_address, target_address, tag)
+        }
+        LinkTypes::AnchorToGroupSettings => validate_create_link_anchor_to_group_settings(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToAppletProposal => validate_create_link_anchor_to_applet_proposal(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AppletProposalToVote => validate_create_link_applet_proposal_to_vote(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AppletProposalToAcceptance => {
+            validate_create_link_applet_proposal_to_acceptance(
+                action,
+                base_address,
+                target_address,
+                tag,
+            )
+        }
+        LinkTypes::AppletToTag => {
+            validate_create_link_applet_to_tag(action, base_address, target_address, tag)
+        }
+        LinkTypes::TagToApplet => {
+            validate_create_link_tag_to_applet(action, base_address, target_address, tag)
+        }
+        LinkTypes::AppletToCategory => {
+            validate_create_link_applet_to_category(action, base_address, target_address, tag)
+        }
+        LinkTypes::CategoryToApplet => {
+            validate_create_link_category_to_applet(action, base_address, target_address, tag)
+        }
+    }
+}
+
+fn validate_delete_link(
+    action: DeleteLink,
+    original_action: CreateLink,
+    link_type: LinkTypes,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::AnchorToApplet => validate_delete_link_anchor_to_applet(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToGroupProfile => validate_delete_link_anchor_to_group_profile(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToRelatedGroup => validate_delete_link_anchor_to_related_group(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::AnchorToGroupSettings => validate_delete_link_anchor_to_group_settings(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::GroupInfoPath => Ok(ValidateCallbackResult::Invalid(String::from(
+            "GroupInfoPath links cannot be deleted",
+        ))),
+        LinkTypes::AnchorToStewardGrant
+        | LinkTypes::AgentToStewardGrant
+        | LinkTypes::StewardGrantToRevocation => Ok(ValidateCallbackResult::Invalid(
+            String::from("Steward links cannot be deleted, revoke the StewardGrant instead"),
+        )),
+        LinkTypes::AppletProposalToAcceptance => Ok(ValidateCallbackResult::Invalid(
+            String::from("AppletProposalToAcceptance links cannot be deleted"),
+        )),
+        LinkTypes::AgentToApplet
+        | LinkTypes::AppletToJoinedAgent
+        | LinkTypes::AppletToExternalAgent
+        | LinkTypes::AppletToInvitedGroup
+        | LinkTypes::AppletToFederationProposal
+        | LinkTypes::AppletToSuccessor
+        | LinkTypes::AppletToPredecessor
+        | LinkTypes::AnchorToFederatedApplet
+        | LinkTypes::AnchorToMember
+        | LinkTypes::AnchorToAppletProposal
+        | LinkTypes::AppletProposalToVote
+        | LinkTypes::AppletToTag
+        | LinkTypes::TagToApplet
+        | LinkTypes::AppletToCategory
+        | LinkTypes::CategoryToApplet => {
+            validate_delete_link_only_by_author(action, original_action)
+        }
+    }
+}
+
+/// Fetches the record that an update or delete refers to and deserializes its app entry.
+///
+/// Returns `Ok(None)` if the original entry is private and therefore not available to
+/// the validating agent, and `Err` with an invalid result if the original action
+/// is not one of this zome's entry creation actions.
+fn get_original_app_entry(
+    original_action_hash: ActionHash,
+) -> ExternResult<Result<Option<(EntryCreationAction, EntryTypes)>, ValidateCallbackResult>> {
+    let original_record = must_get_valid_record(original_action_hash)?;
+    let original_action = match original_record.action().clone() {
+        Action::Create(create) => EntryCreationAction::Create(create),
+        Action::Update(update) => EntryCreationAction::Update(update),
+        _ => {
+            return Ok(Err(ValidateCallbackResult::Invalid(
+                "Original action for an update or delete must be a Create or Update action"
+                    .to_string(),
+            )));
+        }
+    };
+    let app_entry_type = match original_action.entry_type() {
+        EntryType::App(app_entry_type) => app_entry_type,
+        _ => {
+            return Ok(Ok(None));
+        }
+    };
+    let entry = match original_record.entry().as_option() {
+        Some(entry) => entry,
+        None => {
+            if original_action.entry_type().visibility().is_public() {
+                return Ok(Err(ValidateCallbackResult::Invalid(
+                    "Original record for an update or delete of a public entry must contain an entry"
+                        .to_string(),
+                )));
+            } else {
+                return Ok(Ok(None));
+            }
+        }
+    };
+    match EntryTypes::deserialize_from_type(
+        app_entry_type.zome_index.clone(),
+        app_entry_type.entry_index.clone(),
+        &entry,
+    )? {
+        Some(original_app_entry) => Ok(Ok(Some((original_action, original_app_entry)))),
+        None => Ok(Err(ValidateCallbackResult::Invalid(
+            "Original app entry must be one of the defined entry types for this zome".to_string(),
+        ))),
+    }
 }
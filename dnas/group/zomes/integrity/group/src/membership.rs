@@ -0,0 +1,100 @@
+use hdi::prelude::*;
+
+use crate::{group_dna_properties, validate_agent_joining, LinkTypes};
+
+pub fn members_path() -> Path {
+    Path::from("members")
+}
+
+/// Stored in the tag of AnchorToMember links
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+pub struct AnchorToMemberTag {
+    /// The AgentValidationPkg with which the member joined the group
+    pub agent_validation_pkg_hash: ActionHash,
+}
+
+impl AnchorToMemberTag {
+    pub fn from_tag(tag: LinkTag) -> Option<Self> {
+        AnchorToMemberTag::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0))).ok()
+    }
+
+    pub fn to_tag(self) -> ExternResult<LinkTag> {
+        Ok(LinkTag::new(
+            SerializedBytes::try_from(self)
+                .map_err(|err| wasm_error!(err))?
+                .bytes()
+                .clone(),
+        ))
+    }
+}
+
+/// Whether the given agent was a member of the group at the given action of their
+/// own chain: they had created an AnchorToMember link up to that action and not
+/// deleted it again
+pub fn was_member_at(agent: AgentPubKey, chain_top: ActionHash) -> ExternResult<bool> {
+    let member_link_type = ScopedLinkType::try_from(LinkTypes::AnchorToMember)?;
+    let members_anchor = AnyLinkableHash::from(members_path().path_entry_hash()?);
+    let mut activity = must_get_agent_activity(agent, ChainFilter::new(chain_top))?;
+    activity.sort_by_key(|activity| activity.action.action().action_seq());
+
+    let mut member_links: Vec<ActionHash> = Vec::new();
+    for activity in activity {
+        match activity.action.action() {
+            Action::CreateLink(create_link)
+                if create_link.zome_index.eq(&member_link_type.zome_index)
+                    && create_link.link_type.eq(&member_link_type.zome_type)
+                    && create_link.base_address.eq(&members_anchor) =>
+            {
+                member_links.push(activity.action.as_hash().clone());
+            }
+            Action::DeleteLink(delete_link) => {
+                member_links.retain(|link_hash| link_hash.ne(&delete_link.link_add_address));
+            }
+            _ => {}
+        }
+    }
+    Ok(!member_links.is_empty())
+}
+
+/// Agents can only add themselves as members of the group. In invite-only groups the
+/// membrane proof that they joined with is checked again, so that agents whose joining
+/// is rejected never show up as members.
+pub fn validate_create_link_anchor_to_member(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(members_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToMember links must be based on the members anchor",
+        )));
+    }
+    if target_address.ne(&AnyLinkableHash::from(action.author.clone())) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only add themselves as members of the group",
+        )));
+    }
+    if !group_dna_properties()?.invite_only {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(tag) = AnchorToMemberTag::from_tag(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an AnchorToMember link must be an AnchorToMemberTag",
+        )));
+    };
+    let agent_validation_pkg = must_get_action(tag.agent_validation_pkg_hash)?;
+    match agent_validation_pkg.action() {
+        Action::AgentValidationPkg(AgentValidationPkg {
+            author,
+            timestamp,
+            membrane_proof,
+            ..
+        }) if author.eq(&action.author) => {
+            validate_agent_joining(author.clone(), membrane_proof, *timestamp)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToMember links must refer to the AgentValidationPkg of the member",
+        ))),
+    }
+}
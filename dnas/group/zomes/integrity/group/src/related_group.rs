@@ -0,0 +1,102 @@
+use hdi::prelude::*;
+
+use crate::{validate_group_profile, validate_steward_permission, GroupProfile, StewardPermission};
+
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct RelatedGroup {
+    pub group_profile: GroupProfile,
+    pub network_seed: String,
+    pub group_dna_hash: DnaHash,
+}
+
+pub fn related_groups_path() -> Path {
+    Path::from("related_groups")
+}
+
+pub fn validate_create_related_group(
+    _action: EntryCreationAction,
+    related_group: RelatedGroup,
+) -> ExternResult<ValidateCallbackResult> {
+    if related_group.network_seed.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The network seed of a related group must not be empty",
+        )));
+    }
+    if related_group.group_dna_hash.eq(&dna_info()?.hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "A group cannot be related to itself",
+        )));
+    }
+    validate_group_profile(&related_group.group_profile)
+}
+
+pub fn validate_update_related_group(
+    _action: Update,
+    _related_group: RelatedGroup,
+    _original_action: EntryCreationAction,
+    _original_related_group: RelatedGroup,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "RelatedGroups cannot be updated",
+    )))
+}
+
+pub fn validate_delete_related_group(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_related_group: RelatedGroup,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "RelatedGroups cannot be deleted",
+    )))
+}
+
+/// Only stewards may manage the related groups
+pub fn validate_create_link_anchor_to_related_group(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(related_groups_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToRelatedGroup links must be based on the related_groups anchor",
+        )));
+    }
+    let Some(entry_hash) = target_address.into_entry_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToRelatedGroup link must be the entry hash of a RelatedGroup",
+        )));
+    };
+    let entry = must_get_entry(entry_hash)?.content;
+    match RelatedGroup::try_from(entry) {
+        Ok(_related_group) => validate_steward_permission(
+            &action.author,
+            action.timestamp,
+            StewardPermission::from_tag(tag),
+        ),
+        Err(_) => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToRelatedGroup link must be the entry hash of a RelatedGroup",
+        ))),
+    }
+}
+
+pub fn validate_delete_link_anchor_to_related_group(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Related groups can only be removed by the agent that added them",
+        )));
+    }
+    validate_steward_permission(
+        &action.author,
+        action.timestamp,
+        StewardPermission::from_tag(tag),
+    )
+}
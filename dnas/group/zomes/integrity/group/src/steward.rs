@@ -0,0 +1,362 @@
+use hdi::prelude::*;
+
+use crate::{GroupDnaProperties, UnitEntryTypes};
+
+/// Grants the steward role to an agent. Can only be created by the progenitor.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct StewardGrant {
+    pub steward: AgentPubKey,
+    pub expiry: Option<Timestamp>,
+}
+
+/// Revokes a previously created StewardGrant. Can only be created by the progenitor.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct StewardRevocation {
+    pub steward_grant_hash: ActionHash,
+}
+
+/// How long the progenitor attests a steward permission for at most. Revoking a
+/// StewardGrant takes effect once the attestations issued before have expired.
+pub const MAX_STEWARD_ATTESTATION_LIFETIME_MICROS: i64 = 24 * 60 * 60 * 1_000_000;
+
+/// Signed by the progenitor on request of a steward: the StewardGrant had not been
+/// revoked up to `progenitor_chain_top`, and the steward may use it until `valid_until`.
+/// Validators can't know the latest action of the progenitor, so they rely on this
+/// signature instead of a chain top that the steward picked themselves.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct StewardAttestation {
+    pub steward: AgentPubKey,
+    pub steward_grant_hash: ActionHash,
+    pub progenitor_chain_top: ActionHash,
+    pub valid_until: Timestamp,
+}
+
+/// Stored in the tag of links that require steward permissions. If `permission_hash`
+/// is `None`, the author of the link claims to be the progenitor. Everyone else needs
+/// the StewardAttestation of the progenitor, made up of the remaining fields.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Default)]
+pub struct StewardPermission {
+    pub permission_hash: Option<ActionHash>,
+    #[serde(default)]
+    pub progenitor_chain_top: Option<ActionHash>,
+    #[serde(default)]
+    pub valid_until: Option<Timestamp>,
+    #[serde(default)]
+    pub progenitor_signature: Option<Signature>,
+}
+
+impl StewardPermission {
+    pub fn from_tag(tag: LinkTag) -> Self {
+        StewardPermission::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0)))
+            .unwrap_or_default()
+    }
+
+    pub fn to_tag(self) -> ExternResult<LinkTag> {
+        Ok(LinkTag::new(
+            SerializedBytes::try_from(self)
+                .map_err(|err| wasm_error!(err))?
+                .bytes()
+                .clone(),
+        ))
+    }
+}
+
+pub fn steward_grants_path() -> Path {
+    Path::from("steward_grants")
+}
+
+/// Reads the GroupDnaProperties of this DNA. Groups that have been created without
+/// properties use the default properties, malformed properties are an error.
+pub fn group_dna_properties() -> ExternResult<GroupDnaProperties> {
+    let properties = dna_info()?.modifiers.properties;
+    let properties: Option<GroupDnaProperties> =
+        decode(properties.bytes()).map_err(|e| wasm_error!(e))?;
+    Ok(properties.unwrap_or_default())
+}
+
+/// Reads the progenitor from the DNA properties. Groups that have been created
+/// without properties don't have a progenitor.
+pub fn progenitor() -> ExternResult<Option<AgentPubKey>> {
+    Ok(group_dna_properties()?.progenitor.map(AgentPubKey::from))
+}
+
+/// Gets the actions of the given agent from `chain_top` back to `until` (or to genesis)
+/// that create entries of the given type, together with their timestamps
+pub fn entries_created_by(
+    agent: AgentPubKey,
+    chain_top: ActionHash,
+    until: Option<ActionHash>,
+    entry_type: UnitEntryTypes,
+) -> ExternResult<Vec<(ActionHash, Timestamp)>> {
+    let scoped_entry_type = ScopedEntryDefIndex::try_from(entry_type)?;
+    let filter = match until {
+        Some(until) => ChainFilter::new(chain_top).until(until),
+        None => ChainFilter::new(chain_top),
+    };
+    let activity = must_get_agent_activity(agent, filter)?;
+    Ok(activity
+        .into_iter()
+        .filter(|activity| match activity.action.action().entry_type() {
+            Some(EntryType::App(app_entry_def)) => {
+                app_entry_def.zome_index.eq(&scoped_entry_type.zome_index)
+                    && app_entry_def.entry_index.eq(&scoped_entry_type.zome_type)
+            }
+            _ => false,
+        })
+        .map(|activity| {
+            (
+                activity.action.as_hash().clone(),
+                activity.action.action().timestamp(),
+            )
+        })
+        .collect())
+}
+
+/// Checks whether the given agent was allowed to act as a steward at the given point in
+/// time, based on the permission they provided. The permission must be attested by the
+/// progenitor and the attestation must not have expired at that point in time.
+pub fn validate_steward_permission(
+    author: &AgentPubKey,
+    timestamp: Timestamp,
+    permission: StewardPermission,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(progenitor) = progenitor()? else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    if author.eq(&progenitor) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(permission_hash) = permission.permission_hash else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only stewards of the group are allowed to perform this action",
+        )));
+    };
+    let record = must_get_valid_record(permission_hash.clone())?;
+    let steward_grant_entry_type: EntryType = UnitEntryTypes::StewardGrant.try_into()?;
+    if record.action().entry_type().ne(&Some(&steward_grant_entry_type)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Steward permission must point to a StewardGrant",
+        )));
+    }
+    let steward_grant = match record.entry().to_app_option::<StewardGrant>() {
+        Ok(Some(steward_grant)) => steward_grant,
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Steward permission must point to a StewardGrant",
+            )))
+        }
+    };
+    if steward_grant.steward.ne(author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Steward permission was granted to a different agent",
+        )));
+    }
+    if let Some(expiry) = steward_grant.expiry {
+        if expiry <= timestamp {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Steward permission has expired",
+            )));
+        }
+    }
+    let (Some(progenitor_chain_top), Some(valid_until), Some(progenitor_signature)) = (
+        permission.progenitor_chain_top,
+        permission.valid_until,
+        permission.progenitor_signature,
+    ) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Steward permission must be attested by the progenitor",
+        )));
+    };
+    if valid_until < timestamp {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The attestation of the steward permission has expired",
+        )));
+    }
+    let attestation = StewardAttestation {
+        steward: author.clone(),
+        steward_grant_hash: permission_hash.clone(),
+        progenitor_chain_top: progenitor_chain_top.clone(),
+        valid_until,
+    };
+    if !verify_signature(progenitor.clone(), progenitor_signature, attestation)? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The attestation of the steward permission must be signed by the progenitor",
+        )));
+    }
+    let revocations = entries_created_by(
+        progenitor,
+        progenitor_chain_top,
+        Some(permission_hash.clone()),
+        UnitEntryTypes::StewardRevocation,
+    )?;
+    for (revocation_hash, revocation_timestamp) in revocations {
+        if revocation_timestamp > timestamp {
+            continue;
+        }
+        let record = must_get_valid_record(revocation_hash)?;
+        if let Ok(Some(steward_revocation)) = record.entry().to_app_option::<StewardRevocation>()
+        {
+            if steward_revocation.steward_grant_hash.eq(&permission_hash) {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Steward permission has been revoked",
+                )));
+            }
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_author_is_progenitor(author: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+    match progenitor()? {
+        Some(progenitor) if progenitor.eq(author) => Ok(ValidateCallbackResult::Valid),
+        Some(_) => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the progenitor of the group can grant or revoke the steward role",
+        ))),
+        None => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Groups without a progenitor have no steward role to grant or revoke",
+        ))),
+    }
+}
+
+pub fn validate_create_steward_grant(
+    action: EntryCreationAction,
+    _steward_grant: StewardGrant,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_author_is_progenitor(action.author())
+}
+
+pub fn validate_update_steward_grant(
+    _action: Update,
+    _steward_grant: StewardGrant,
+    _original_action: EntryCreationAction,
+    _original_steward_grant: StewardGrant,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "StewardGrants cannot be updated, revoke them instead",
+    )))
+}
+
+pub fn validate_delete_steward_grant(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_steward_grant: StewardGrant,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "StewardGrants cannot be deleted, revoke them instead",
+    )))
+}
+
+pub fn validate_create_steward_revocation(
+    action: EntryCreationAction,
+    steward_revocation: StewardRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    let result = validate_author_is_progenitor(action.author())?;
+    if let ValidateCallbackResult::Valid = result {
+        let record = must_get_valid_record(steward_revocation.steward_grant_hash)?;
+        match record.entry().to_app_option::<StewardGrant>() {
+            Ok(Some(_steward_grant)) => Ok(ValidateCallbackResult::Valid),
+            _ => Ok(ValidateCallbackResult::Invalid(String::from(
+                "A StewardRevocation must refer to a StewardGrant",
+            ))),
+        }
+    } else {
+        Ok(result)
+    }
+}
+
+pub fn validate_update_steward_revocation(
+    _action: Update,
+    _steward_revocation: StewardRevocation,
+    _original_action: EntryCreationAction,
+    _original_steward_revocation: StewardRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "StewardRevocations cannot be updated",
+    )))
+}
+
+pub fn validate_delete_steward_revocation(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_steward_revocation: StewardRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "StewardRevocations cannot be deleted",
+    )))
+}
+
+fn must_get_steward_grant(address: AnyLinkableHash) -> ExternResult<Option<StewardGrant>> {
+    let Some(action_hash) = address.into_action_hash() else {
+        return Ok(None);
+    };
+    let record = must_get_valid_record(action_hash)?;
+    Ok(record.entry().to_app_option::<StewardGrant>().ok().flatten())
+}
+
+pub fn validate_create_link_anchor_to_steward_grant(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(steward_grants_path().path_entry_hash()?)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AnchorToStewardGrant links must be based on the steward_grants anchor",
+        )));
+    }
+    if must_get_steward_grant(target_address)?.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AnchorToStewardGrant link must be a StewardGrant",
+        )));
+    }
+    validate_author_is_progenitor(&action.author)
+}
+
+pub fn validate_create_link_agent_to_steward_grant(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(steward_grant) = must_get_steward_grant(target_address)? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of an AgentToStewardGrant link must be a StewardGrant",
+        )));
+    };
+    if base_address.ne(&AnyLinkableHash::from(steward_grant.steward)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "AgentToStewardGrant links must be based on the agent that the StewardGrant is for",
+        )));
+    }
+    validate_author_is_progenitor(&action.author)
+}
+
+pub fn validate_create_link_steward_grant_to_revocation(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(action_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Target of a StewardGrantToRevocation link must be a StewardRevocation",
+        )));
+    };
+    let record = must_get_valid_record(action_hash)?;
+    let steward_revocation = match record.entry().to_app_option::<StewardRevocation>() {
+        Ok(Some(steward_revocation)) => steward_revocation,
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Target of a StewardGrantToRevocation link must be a StewardRevocation",
+            )))
+        }
+    };
+    if base_address.ne(&AnyLinkableHash::from(steward_revocation.steward_grant_hash)) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "StewardGrantToRevocation links must be based on the revoked StewardGrant",
+        )));
+    }
+    validate_author_is_progenitor(&action.author)
+}
@@ -1,17 +1,48 @@
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use futures::lock::Mutex;
-use holochain_client::AdminWebsocket;
+use holochain::prelude::{
+    ActionHash, ActionHashB64, EntryHash, ExternIO, FunctionName, Record, RoleName,
+    SerializedBytes, ZomeName,
+};
+use holochain_client::{AdminWebsocket, AppStatusFilter};
+use holochain_keystore::MetaLairClient;
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Response, Server,
 };
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 
 use crate::{
+    commands::install_applet_bundle::AppAgentWebsocket,
     error::{WeError, WeResult},
     filesystem::{WeFileSystem, UiIdentifier},
+    launch::{AdminPort, AppPort},
 };
 
+/// Custom views are served from hosts starting with this prefix, which gives them an
+/// origin that is separate from the one of every applet
+pub const CUSTOM_VIEW_HOST_PREFIX: &str = "customview-";
+
+/// Every ActionHashB64 starts with this. It is left out of the host of a custom view
+/// to keep the host label within the 63 characters that DNS allows.
+const ACTION_HASH_B64_PREFIX: &str = "uhckk";
+
+/// Mirrors the CustomView of the custom_views zome
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+pub struct CustomView {
+    pub name: String,
+    pub logo: String,
+    pub html: String,
+    pub js: String,
+    pub css: String,
+    #[serde(default)]
+    pub logo_blob_hash: Option<EntryHash>,
+    #[serde(default)]
+    pub applet_dependencies: Vec<EntryHash>,
+}
+
 pub fn pong_iframe() -> String {
     format!("<html><head></head><body><script>window.onload = () => window.parent.postMessage('pong', '*') </script></body></html>")
 }
@@ -68,17 +99,33 @@ pub fn start_applet_uis_server(app_handle: AppHandle, ui_server_port: u16) -> ()
                         let file_name = request.uri().path();
 
                         let fs = app_handle.state::<WeFileSystem>();
-                        let mutex = app_handle.state::<Mutex<AdminWebsocket>>();
-                        let mut admin_ws = mutex.lock().await;
-
-                        let r: WeResult<Response<Body>> = match read_asset(
-                            &fs,
-                            &mut admin_ws,
-                            &lowercase_applet_id,
-                            file_name.to_string(),
-                        )
-                        .await
+
+                        let asset = match lowercase_applet_id.strip_prefix(CUSTOM_VIEW_HOST_PREFIX)
                         {
+                            Some(custom_view_id) => {
+                                read_custom_view_asset(
+                                    &app_handle,
+                                    &custom_view_id.to_string(),
+                                    file_name.to_string(),
+                                )
+                                .await
+                            }
+                            None => {
+                                let mutex = app_handle.state::<Mutex<AdminWebsocket>>();
+                                let mut admin_ws = mutex.lock().await;
+                                let asset = read_asset(
+                                    &fs,
+                                    &mut admin_ws,
+                                    &lowercase_applet_id,
+                                    file_name.to_string(),
+                                )
+                                .await;
+                                admin_ws.close();
+                                asset
+                            }
+                        };
+
+                        let r: WeResult<Response<Body>> = match asset {
                             Ok(Some((asset, mime_type))) => {
                                 let mut response_builder = Response::builder().status(202);
                                 if let Some(mime_type) = mime_type {
@@ -99,7 +146,6 @@ pub fn start_applet_uis_server(app_handle: AppHandle, ui_server_port: u16) -> ()
                                 .body(format!("{:?}", e).into())
                                 .unwrap()),
                         };
-                        admin_ws.close();
                         r
                     }
                 }))
@@ -244,3 +290,187 @@ pub async fn read_asset(
     }
 
 }
+
+/// The id under which a custom view is served, i.e. the part of its host after
+/// CUSTOM_VIEW_HOST_PREFIX
+pub fn custom_view_id(original_custom_view_hash: &ActionHash) -> String {
+    let custom_view_hash_b64 =
+        ActionHashB64::from(original_custom_view_hash.clone()).to_string().to_lowercase();
+    match custom_view_hash_b64.strip_prefix(ACTION_HASH_B64_PREFIX) {
+        Some(id) => id.to_string(),
+        None => custom_view_hash_b64,
+    }
+}
+
+/// The group app and the original create action of every custom view that has been
+/// served, so that requests for its page don't need to search all groups again
+#[derive(Default)]
+pub struct CustomViewLocations(Mutex<HashMap<String, (String, ActionHash)>>);
+
+/// Looks up the custom view with the given id in the running groups we are part of
+/// and returns the latest version of it. Groups whose zome calls fail are skipped.
+async fn get_custom_view_from_lowercase(
+    app_handle: &AppHandle,
+    custom_view_id_lowercase: &String,
+) -> WeResult<Option<CustomView>> {
+    let custom_view_id = custom_view_id_lowercase.to_lowercase();
+    let locations = app_handle.state::<CustomViewLocations>();
+
+    let cached_location = locations.0.lock().await.get(&custom_view_id).cloned();
+    if let Some((group_app_id, original_custom_view_hash)) = cached_location {
+        match get_latest_custom_view(app_handle, &group_app_id, original_custom_view_hash).await
+        {
+            Ok(Some(custom_view)) => return Ok(Some(custom_view)),
+            _ => {
+                locations.0.lock().await.remove(&custom_view_id);
+            }
+        }
+    }
+
+    let group_app_ids: Vec<String> = {
+        let mutex = app_handle.state::<Mutex<AdminWebsocket>>();
+        let mut admin_ws = mutex.lock().await;
+        let apps = admin_ws.list_apps(Some(AppStatusFilter::Running)).await;
+        admin_ws.close();
+        apps?
+            .into_iter()
+            .map(|app| app.installed_app_id)
+            .filter(|installed_app_id| installed_app_id.starts_with("group#"))
+            .collect()
+    };
+
+    for group_app_id in group_app_ids {
+        let original_custom_view_hash =
+            match find_custom_view(app_handle, &group_app_id, &custom_view_id).await {
+                Ok(Some(original_custom_view_hash)) => original_custom_view_hash,
+                Ok(None) => continue,
+                Err(err) => {
+                    if cfg!(debug_assertions) {
+                        println!("Failed to get the custom views of {}: {:?}", group_app_id, err);
+                    }
+                    continue;
+                }
+            };
+        let custom_view =
+            match get_latest_custom_view(app_handle, &group_app_id, original_custom_view_hash.clone())
+                .await
+            {
+                Ok(Some(custom_view)) => custom_view,
+                Ok(None) => continue,
+                Err(err) => {
+                    if cfg!(debug_assertions) {
+                        println!("Failed to get the custom view from {}: {:?}", group_app_id, err);
+                    }
+                    continue;
+                }
+            };
+        locations
+            .0
+            .lock()
+            .await
+            .insert(custom_view_id, (group_app_id, original_custom_view_hash));
+        return Ok(Some(custom_view));
+    }
+    Ok(None)
+}
+
+async fn connect_to_group(
+    app_handle: &AppHandle,
+    group_app_id: &String,
+) -> WeResult<AppAgentWebsocket> {
+    let meta_lair_client = app_handle.state::<Mutex<MetaLairClient>>();
+    let ports = app_handle.state::<(AdminPort, AppPort)>();
+    let group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        group_app_id.clone(),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+    Ok(group_client)
+}
+
+/// The original create action of the custom view with the given id in the given group
+async fn find_custom_view(
+    app_handle: &AppHandle,
+    group_app_id: &String,
+    wanted_custom_view_id: &String,
+) -> WeResult<Option<ActionHash>> {
+    let mut group_client = connect_to_group(app_handle, group_app_id).await?;
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("custom_views"),
+            FunctionName::from("get_all_custom_views"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    let records: Vec<Record> = result.decode()?;
+    Ok(records
+        .into_iter()
+        .map(|record| record.action_address().clone())
+        .find(|action_hash| custom_view_id(action_hash).eq(wanted_custom_view_id)))
+}
+
+async fn get_latest_custom_view(
+    app_handle: &AppHandle,
+    group_app_id: &String,
+    original_custom_view_hash: ActionHash,
+) -> WeResult<Option<CustomView>> {
+    let mut group_client = connect_to_group(app_handle, group_app_id).await?;
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("custom_views"),
+            FunctionName::from("get_custom_view"),
+            ExternIO::encode(original_custom_view_hash)?,
+        )
+        .await?;
+    let maybe_record: Option<Record> = result.decode()?;
+    Ok(maybe_record.and_then(|record| record.entry().to_app_option::<CustomView>().ok().flatten()))
+}
+
+/// Custom views consist of a single page, with their css and js inlined next to the
+/// injected applet-iframe script
+pub async fn read_custom_view_asset(
+    app_handle: &AppHandle,
+    custom_view_id_lowercase: &String,
+    mut asset_name: String,
+) -> WeResult<Option<(Vec<u8>, Option<String>)>> {
+    if cfg!(debug_assertions) {
+        println!("Reading custom view asset. Asset name: {}", asset_name);
+    }
+    if asset_name.starts_with("/") {
+        asset_name = asset_name.strip_prefix("/").unwrap().to_string();
+    }
+    if !(asset_name.is_empty() || asset_name.eq("index.html")) {
+        return Ok(None);
+    }
+
+    let custom_view =
+        match get_custom_view_from_lowercase(app_handle, custom_view_id_lowercase).await? {
+            Some(custom_view) => custom_view,
+            None => return Ok(None),
+        };
+
+    let index_html = format!(
+        r#"<html>
+  <head>
+    <script type="module">{}</script>
+    <style>{}</style>
+  </head>
+  <body>
+    {}
+    <script type="module">{}</script>
+  </body>
+</html>
+"#,
+        include_str!("../../ui/applet-iframe/dist/index.mjs"),
+        custom_view.css,
+        custom_view.html,
+        custom_view.js,
+    );
+    Ok(Some((
+        index_html.as_bytes().to_vec(),
+        Some(String::from("text/html")),
+    )))
+}
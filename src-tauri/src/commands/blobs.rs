@@ -0,0 +1,227 @@
+use std::io::Write;
+
+use base64::{engine::general_purpose::STANDARD, write::EncoderWriter};
+use futures::lock::Mutex;
+use holochain::prelude::{ExternIO, FunctionName, RoleName, ZomeName};
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{EntryHash, EntryHashB64};
+use serde::{Deserialize, Serialize};
+use we_types::MAX_BLOB_CHUNK_SIZE;
+
+use crate::{
+    commands::install_applet_bundle::AppAgentWebsocket,
+    error::{WeError, WeResult},
+    filesystem::WeFileSystem,
+    launch::{AdminPort, AppPort},
+};
+
+/// Mirrors the BlobChunk of the blobs zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlobChunk {
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+/// Mirrors the Blob of the blobs zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Blob {
+    pub mime_type: String,
+    pub size: usize,
+    pub chunk_hashes: Vec<EntryHash>,
+}
+
+pub(crate) async fn connect_to_group(
+    meta_lair_client: &tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: &tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+) -> WeResult<AppAgentWebsocket> {
+    AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(network_seed)),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await
+}
+
+/// Stores the given bytes as a Blob in the group with the given network seed, one chunk
+/// per zome call. Returns the hash by which the Blob can be referenced.
+#[tauri::command]
+pub async fn store_group_blob(
+    window: tauri::Window,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+    mime_type: String,
+    bytes: Vec<u8>,
+) -> WeResult<EntryHashB64> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from("store_group_blob")));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'store_group_blob'.");
+    }
+
+    let mut group_client = connect_to_group(&meta_lair_client, &ports, network_seed).await?;
+    let blob_hash = store_blob(&mut group_client, mime_type, bytes).await?;
+
+    Ok(EntryHashB64::from(blob_hash))
+}
+
+/// Stores the given bytes as a Blob with the given group client, one chunk per zome call
+pub(crate) async fn store_blob(
+    group_client: &mut AppAgentWebsocket,
+    mime_type: String,
+    bytes: Vec<u8>,
+) -> WeResult<EntryHash> {
+    let mut chunk_hashes: Vec<EntryHash> = Vec::new();
+    for chunk in bytes.chunks(MAX_BLOB_CHUNK_SIZE) {
+        let result = group_client
+            .call_zome_fn(
+                RoleName::from("group"),
+                ZomeName::from("blobs"),
+                FunctionName::from("create_blob_chunk"),
+                ExternIO::encode(BlobChunk {
+                    bytes: chunk.to_vec(),
+                })?,
+            )
+            .await?;
+        chunk_hashes.push(result.decode()?);
+    }
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("blobs"),
+            FunctionName::from("create_blob"),
+            ExternIO::encode(Blob {
+                mime_type,
+                size: bytes.len(),
+                chunk_hashes,
+            })?,
+        )
+        .await?;
+    Ok(result.decode()?)
+}
+
+/// Gets the mime type and the bytes of a Blob with the given group client
+pub(crate) async fn fetch_blob(
+    group_client: &mut AppAgentWebsocket,
+    blob_hash: EntryHash,
+) -> WeResult<(String, Vec<u8>)> {
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("blobs"),
+            FunctionName::from("get_blob"),
+            ExternIO::encode(blob_hash)?,
+        )
+        .await?;
+    let maybe_blob: Option<Blob> = result.decode()?;
+    let blob = maybe_blob.ok_or(WeError::CustomError(String::from(
+        "Blob not found in the group.",
+    )))?;
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(blob.size);
+    for chunk_hash in blob.chunk_hashes {
+        let result = group_client
+            .call_zome_fn(
+                RoleName::from("group"),
+                ZomeName::from("blobs"),
+                FunctionName::from("get_blob_chunk"),
+                ExternIO::encode(chunk_hash)?,
+            )
+            .await?;
+        let maybe_chunk: Option<BlobChunk> = result.decode()?;
+        let chunk = maybe_chunk.ok_or(WeError::CustomError(String::from(
+            "Chunk of the blob not found in the group.",
+        )))?;
+        bytes.extend(chunk.bytes);
+    }
+    Ok((blob.mime_type, bytes))
+}
+
+/// Whether the mime type has the `type/subtype` form, so that it can't inject anything
+/// into the data URL it is put in
+fn is_valid_mime_type(mime_type: &str) -> bool {
+    let is_restricted_name = |name: &str| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    match mime_type.split_once('/') {
+        Some((type_name, subtype_name)) => {
+            is_restricted_name(type_name) && is_restricted_name(subtype_name)
+        }
+        None => false,
+    }
+}
+
+/// Gets a Blob of the group with the given network seed as a data URL, e.g. to be used
+/// as the src of a logo. The data URL is stored in the IconStore and served from there
+/// on subsequent calls.
+#[tauri::command]
+pub async fn fetch_group_blob_icon(
+    window: tauri::Window,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    we_fs: tauri::State<'_, WeFileSystem>,
+    network_seed: String,
+    blob_hash: EntryHashB64,
+) -> WeResult<String> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "fetch_group_blob_icon",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'fetch_group_blob_icon'.");
+    }
+
+    let blob_hash = EntryHash::from(blob_hash);
+    let icon_store = we_fs.icon_store();
+
+    if let Some(icon) = icon_store.get_blob_icon(&blob_hash)? {
+        return Ok(icon);
+    }
+
+    let mut group_client = connect_to_group(&meta_lair_client, &ports, network_seed).await?;
+    let (mime_type, bytes) = fetch_blob(&mut group_client, blob_hash.clone()).await?;
+    if !is_valid_mime_type(&mime_type) {
+        return Err(WeError::CustomError(format!(
+            "The blob has an invalid mime type: {}",
+            mime_type
+        )));
+    }
+
+    let mut file = icon_store.create_blob_icon_file(&blob_hash)?;
+    file.write_all(format!("data:{};base64,", mime_type).as_bytes())?;
+    {
+        let mut encoder = EncoderWriter::new(&mut file, &STANDARD);
+        encoder.write_all(bytes.as_slice())?;
+        encoder.finish()?;
+    }
+    file.flush()?;
+    icon_store.commit_blob_icon(&blob_hash)?;
+
+    icon_store
+        .get_blob_icon(&blob_hash)?
+        .ok_or(WeError::FileSystemError(String::from(
+            "Failed to store the blob in the icon store.",
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_type_subtype_mime_types_are_valid() {
+        assert!(is_valid_mime_type("image/png"));
+        assert!(is_valid_mime_type("image/svg+xml"));
+        assert!(!is_valid_mime_type("image"));
+        assert!(!is_valid_mime_type("image/"));
+        assert!(!is_valid_mime_type("image/png;base64,"));
+        assert!(!is_valid_mime_type("text/html\" onload=\"alert(1)"));
+    }
+}
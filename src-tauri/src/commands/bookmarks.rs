@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use futures::lock::Mutex;
+use holochain::{
+    conductor::api::CellInfo,
+    prelude::{ExternIO, FunctionName, RoleName, Timestamp, ZomeName},
+};
+use holochain_client::AdminWebsocket;
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{
+    ActionHash, ActionHashB64, AgentPubKey, AgentPubKeyB64, AnyDhtHashB64, DnaHash, DnaHashB64,
+};
+use serde::{Deserialize, Serialize};
+use we_types::HrlWithContext;
+
+use crate::{
+    commands::install_applet_bundle::AppAgentWebsocket,
+    error::{WeError, WeResult},
+    launch::{AdminPort, AppPort},
+};
+
+/// Mirrors the GetBookmarksInput of the bookmarks zome
+#[derive(Debug, Serialize, Deserialize)]
+struct GetBookmarksInput {
+    offset: usize,
+    limit: Option<usize>,
+}
+
+/// Mirrors the Bookmark of the bookmarks zome
+#[derive(Debug, Serialize, Deserialize)]
+struct Bookmark {
+    hrl_with_context: HrlWithContext,
+    title: String,
+}
+
+/// Mirrors the BookmarkInfo of the bookmarks zome
+#[derive(Debug, Serialize, Deserialize)]
+struct BookmarkInfo {
+    bookmark_hash: ActionHash,
+    bookmark: Bookmark,
+    author: AgentPubKey,
+    pinned_at: Timestamp,
+}
+
+/// Mirrors the BookmarksPage of the bookmarks zome
+#[derive(Debug, Serialize, Deserialize)]
+struct BookmarksPage {
+    bookmarks: Vec<BookmarkInfo>,
+    total: usize,
+}
+
+/// A bookmark of a group together with the applet that its HRL points into
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolvedBookmark {
+    pub bookmark_hash: ActionHashB64,
+    pub title: String,
+    pub dna_hash: DnaHashB64,
+    pub resource_hash: AnyDhtHashB64,
+    pub context: Vec<u8>,
+    pub author: AgentPubKeyB64,
+    pub pinned_at: Timestamp,
+    /// The installed app id of the applet with a cell of the HRL's DNA, None if we
+    /// don't have such an applet installed
+    pub applet_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolvedBookmarksPage {
+    pub bookmarks: Vec<ResolvedBookmark>,
+    pub total: usize,
+}
+
+/// Gets a page of the bookmarks of the group with the given network seed and resolves
+/// the applet that each of them points into
+#[tauri::command]
+pub async fn get_group_bookmarks(
+    window: tauri::Window,
+    admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+    offset: usize,
+    limit: Option<usize>,
+) -> WeResult<ResolvedBookmarksPage> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "get_group_bookmarks",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'get_group_bookmarks'.");
+    }
+
+    let mut group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(network_seed)),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("bookmarks"),
+            FunctionName::from("get_bookmarks"),
+            ExternIO::encode(GetBookmarksInput { offset, limit })?,
+        )
+        .await?;
+    let page: BookmarksPage = result.decode()?;
+
+    let mut admin_ws = admin_ws.lock().await;
+    let mut applet_ids_by_dna: HashMap<DnaHash, String> = HashMap::new();
+    for app_info in admin_ws.list_apps(None).await? {
+        if !app_info.installed_app_id.starts_with("applet#") {
+            continue;
+        }
+        for cells in app_info.cell_info.values() {
+            for cell in cells {
+                let dna_hash = match cell {
+                    CellInfo::Provisioned(provisioned_cell) => {
+                        provisioned_cell.cell_id.dna_hash().clone()
+                    }
+                    CellInfo::Cloned(cloned_cell) => cloned_cell.cell_id.dna_hash().clone(),
+                    _ => continue,
+                };
+                applet_ids_by_dna
+                    .entry(dna_hash)
+                    .or_insert(app_info.installed_app_id.clone());
+            }
+        }
+    }
+    admin_ws.close();
+
+    let bookmarks = page
+        .bookmarks
+        .into_iter()
+        .map(|info| {
+            let hrl = info.bookmark.hrl_with_context.hrl;
+            ResolvedBookmark {
+                bookmark_hash: info.bookmark_hash.into(),
+                title: info.bookmark.title,
+                applet_id: applet_ids_by_dna.get(&hrl.dna_hash).cloned(),
+                dna_hash: hrl.dna_hash.into(),
+                resource_hash: hrl.resource_hash.into(),
+                context: info.bookmark.hrl_with_context.context.bytes().clone(),
+                author: info.author.into(),
+                pinned_at: info.pinned_at,
+            }
+        })
+        .collect();
+
+    Ok(ResolvedBookmarksPage {
+        bookmarks,
+        total: page.total,
+    })
+}
@@ -0,0 +1,262 @@
+use std::io::Write;
+
+use base64::Engine;
+use futures::lock::Mutex;
+use holochain::prelude::{ExternIO, FunctionName, Record, RoleName, ZomeName};
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{ActionHash, ActionHashB64, EntryHash, EntryHashB64};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    applet_iframes::CustomView,
+    commands::blobs::{connect_to_group, fetch_blob, store_blob},
+    error::{WeError, WeResult},
+    launch::{AdminPort, AppPort},
+};
+
+/// Bumped whenever the format of a CustomViewBundle changes
+pub const CUSTOM_VIEW_BUNDLE_VERSION: u32 = 1;
+
+/// A Blob of the blobs zome, carried inside a CustomViewBundle
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BundledBlob {
+    pub mime_type: String,
+    /// Base64 encoded bytes of the Blob
+    pub bytes: String,
+}
+
+/// Everything of a CustomView that is carried over to another group. Blobs don't
+/// exist in the other group yet, so the logo Blob is bundled instead of its hash.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomViewBundleContent {
+    pub name: String,
+    pub logo: String,
+    pub html: String,
+    pub js: String,
+    pub css: String,
+    pub logo_blob: Option<BundledBlob>,
+    pub applet_dependencies: Vec<EntryHashB64>,
+}
+
+impl CustomViewBundleContent {
+    /// Hex encoded sha256 hash of the JSON serialized content
+    pub fn content_hash(&self) -> WeResult<String> {
+        let json = serde_json::to_string(self).map_err(|e| {
+            WeError::CustomError(format!("Failed to serialize custom view: {}", e))
+        })?;
+        Ok(sha256::digest(json))
+    }
+}
+
+/// The contents of a file that a CustomView is exported to
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomViewBundle {
+    pub version: u32,
+    pub content_hash: String,
+    pub content: CustomViewBundleContent,
+}
+
+/// Exports the latest version of a CustomView of the group with the given network seed,
+/// together with the logo Blob that it references, to a single file in the downloads
+/// directory. Existing files are never overwritten. Returns the path of the file.
+#[tauri::command]
+pub async fn export_custom_view(
+    window: tauri::Window,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+    custom_view_hash: ActionHashB64,
+) -> WeResult<String> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "export_custom_view",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'export_custom_view'.");
+    }
+
+    let mut group_client = connect_to_group(&meta_lair_client, &ports, network_seed).await?;
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("custom_views"),
+            FunctionName::from("get_custom_view"),
+            ExternIO::encode(ActionHash::from(custom_view_hash))?,
+        )
+        .await?;
+    let maybe_record: Option<Record> = result.decode()?;
+    let custom_view = maybe_record
+        .and_then(|record| record.entry().to_app_option::<CustomView>().ok().flatten())
+        .ok_or(WeError::CustomError(String::from(
+            "Custom view not found in the group.",
+        )))?;
+
+    let logo_blob = match custom_view.logo_blob_hash {
+        Some(blob_hash) => {
+            let (mime_type, bytes) = fetch_blob(&mut group_client, blob_hash).await?;
+            Some(BundledBlob {
+                mime_type,
+                bytes: base64::engine::general_purpose::STANDARD.encode(bytes),
+            })
+        }
+        None => None,
+    };
+
+    let content = CustomViewBundleContent {
+        name: custom_view.name,
+        logo: custom_view.logo,
+        html: custom_view.html,
+        js: custom_view.js,
+        css: custom_view.css,
+        logo_blob,
+        applet_dependencies: custom_view
+            .applet_dependencies
+            .into_iter()
+            .map(EntryHashB64::from)
+            .collect(),
+    };
+    let bundle = CustomViewBundle {
+        version: CUSTOM_VIEW_BUNDLE_VERSION,
+        content_hash: content.content_hash()?,
+        content,
+    };
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| {
+        WeError::CustomError(format!("Failed to serialize custom view bundle: {}", e))
+    })?;
+
+    let downloads_dir = tauri::api::path::download_dir().ok_or(WeError::FileSystemError(
+        String::from("Failed to find the downloads directory."),
+    ))?;
+    // Views with the same name get distinct files, the same view the same file
+    let mut file_name: String = bundle
+        .content
+        .name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    if file_name.is_empty() {
+        file_name = String::from("custom_view");
+    }
+    let file_path = downloads_dir.join(format!(
+        "{}-{}.customview.json",
+        file_name,
+        &bundle.content_hash[..8]
+    ));
+    let mut file = match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&file_path)
+    {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            return Err(WeError::FileSystemError(format!(
+                "The custom view has already been exported to {}.",
+                file_path.to_string_lossy()
+            )))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    file.write_all(json.as_bytes())?;
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// Imports a CustomView from the contents of a file created by `export_custom_view` into
+/// the group with the given network seed. Fails if the content doesn't match the content
+/// hash of the file, or if any Applet that the view depends on is not part of the group.
+#[tauri::command]
+pub async fn import_custom_view(
+    window: tauri::Window,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+    bundle: String,
+) -> WeResult<ActionHashB64> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "import_custom_view",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'import_custom_view'.");
+    }
+
+    let bundle: CustomViewBundle = serde_json::from_str(&bundle).map_err(|e| {
+        WeError::CustomError(format!("Invalid custom view bundle: {}", e))
+    })?;
+    if bundle.version != CUSTOM_VIEW_BUNDLE_VERSION {
+        return Err(WeError::CustomError(format!(
+            "Unsupported custom view bundle version: {}",
+            bundle.version
+        )));
+    }
+    if bundle.content.content_hash()?.ne(&bundle.content_hash) {
+        return Err(WeError::CustomError(String::from(
+            "The content of the custom view bundle does not match its content hash.",
+        )));
+    }
+
+    let mut group_client = connect_to_group(&meta_lair_client, &ports, network_seed).await?;
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("get_group_applets"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    let group_applets: Vec<EntryHash> = result.decode()?;
+    let applet_dependencies: Vec<EntryHash> = bundle
+        .content
+        .applet_dependencies
+        .into_iter()
+        .map(EntryHash::from)
+        .collect();
+    if let Some(missing_applet) = applet_dependencies
+        .iter()
+        .find(|applet_hash| !group_applets.contains(applet_hash))
+    {
+        return Err(WeError::CustomError(format!(
+            "The custom view depends on the applet {} which is not part of this group.",
+            EntryHashB64::from(missing_applet.clone())
+        )));
+    }
+
+    let logo_blob_hash = match bundle.content.logo_blob {
+        Some(logo_blob) => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(logo_blob.bytes)
+                .map_err(|e| {
+                    WeError::CustomError(format!("Invalid logo in custom view bundle: {}", e))
+                })?;
+            Some(store_blob(&mut group_client, logo_blob.mime_type, bytes).await?)
+        }
+        None => None,
+    };
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("custom_views"),
+            FunctionName::from("create_custom_view"),
+            ExternIO::encode(CustomView {
+                name: bundle.content.name,
+                logo: bundle.content.logo,
+                html: bundle.content.html,
+                js: bundle.content.js,
+                css: bundle.content.css,
+                logo_blob_hash,
+                applet_dependencies,
+            })?,
+        )
+        .await?;
+    let record: Record = result.decode()?;
+
+    Ok(ActionHashB64::from(record.action_address().clone()))
+}
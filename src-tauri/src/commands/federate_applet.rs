@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use futures::lock::Mutex;
+use holochain::prelude::{ActionHash, ExternIO, FunctionName, Record, RoleName, Signature, ZomeName};
+use holochain_client::AdminWebsocket;
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{
+    ActionHashB64, AgentPubKey, DnaHash, DnaHashB64, EntryHash, EntryHashB64,
+};
+use serde::{Deserialize, Serialize};
+use we_types::Applet;
+
+use crate::{
+    commands::install_applet_bundle::{install_applet_bundle_if_necessary, AppAgentWebsocket},
+    error::{WeError, WeResult},
+    filesystem::WeFileSystem,
+    launch::{AdminPort, AppPort},
+};
+
+/// Mirrors the ProposeAppletFederationInput of the group zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProposeAppletFederationInput {
+    pub applet_hash: EntryHash,
+    pub group_dna_hash: DnaHash,
+}
+
+/// Mirrors the AcceptAppletFederationInput of the group zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AcceptAppletFederationInput {
+    pub applet: Applet,
+    pub proposal_hash: ActionHash,
+    pub proposing_group_dna_hash: DnaHash,
+}
+
+/// Mirrors the AppletFederationAcceptance of the group zome
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppletFederationAcceptance {
+    pub applet_hash: EntryHash,
+    pub proposal_hash: ActionHash,
+    pub proposing_group_dna_hash: DnaHash,
+    pub accepting_group_dna_hash: DnaHash,
+}
+
+/// Mirrors the SignedAppletFederationAcceptance of the group zome
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignedAppletFederationAcceptance {
+    pub acceptance: AppletFederationAcceptance,
+    pub accepting_agent: AgentPubKey,
+    pub signature: Signature,
+}
+
+/// Mirrors the RegisterAppletFederationInput of the group zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterAppletFederationInput {
+    pub applet_hash: EntryHash,
+    pub group_dna_hash: DnaHash,
+    pub proposal_hash: ActionHash,
+    pub signed_acceptance: SignedAppletFederationAcceptance,
+}
+
+/// Federates an Applet of one group with another group that the calling agent is a
+/// member of: proposes the federation in the first group, installs the Applet if
+/// necessary and accepts the federation in the second group with the agent of that
+/// group, which adds the Applet there with the same network seed and properties, and
+/// finally registers the signed acceptance in the first group. The calling agent needs
+/// to be a steward in both groups.
+#[tauri::command]
+pub async fn federate_applet(
+    window: tauri::Window,
+    app_handle: tauri::AppHandle,
+    admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    we_fs: tauri::State<'_, WeFileSystem>,
+    applet_hash: EntryHashB64,
+    group_network_seed: String,
+    federated_group_network_seed: String,
+    agent_pub_key: String, // TODO: remove when every applet has a different key
+) -> WeResult<()> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from("federate_applet")));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'federate_applet'.");
+    }
+
+    let applet_hash = EntryHash::from(applet_hash);
+
+    let mut group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(group_network_seed)),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+
+    let group_dna_hash = group_client
+        .get_cell_id_from_role_name(&RoleName::from("group"))?
+        .dna_hash()
+        .clone();
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("get_applet"),
+            ExternIO::encode(applet_hash.clone())?,
+        )
+        .await?;
+    let maybe_record: Option<Record> = result.decode()?;
+    let applet = maybe_record
+        .and_then(|record| record.entry().to_app_option::<Applet>().ok().flatten())
+        .ok_or(WeError::CustomError(String::from(
+            "Applet not found in the group.",
+        )))?;
+
+    let mut federated_group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(federated_group_network_seed)),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+    let federated_group_dna_hash = federated_group_client
+        .get_cell_id_from_role_name(&RoleName::from("group"))?
+        .dna_hash()
+        .clone();
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("propose_applet_federation"),
+            ExternIO::encode(ProposeAppletFederationInput {
+                applet_hash: applet_hash.clone(),
+                group_dna_hash: federated_group_dna_hash.clone(),
+            })?,
+        )
+        .await?;
+    let proposal_hash: ActionHash = result.decode()?;
+
+    let signed_acceptance = install_and_accept_applet_federation(
+        window,
+        app_handle,
+        admin_ws,
+        meta_lair_client,
+        ports,
+        we_fs,
+        &mut federated_group_client,
+        applet,
+        proposal_hash.clone(),
+        group_dna_hash,
+        agent_pub_key,
+    )
+    .await?;
+
+    group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("register_applet_federation"),
+            ExternIO::encode(RegisterAppletFederationInput {
+                applet_hash,
+                group_dna_hash: federated_group_dna_hash,
+                proposal_hash,
+                signed_acceptance,
+            })?,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Accepts an AppletFederationProposal of another group in the group with the given
+/// network seed, installing the Applet if necessary. The returned signed acceptance
+/// needs to be registered in the proposing group by one of its stewards.
+#[tauri::command]
+pub async fn accept_applet_federation(
+    window: tauri::Window,
+    app_handle: tauri::AppHandle,
+    admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    we_fs: tauri::State<'_, WeFileSystem>,
+    group_network_seed: String,
+    applet: Applet,
+    proposal_hash: ActionHashB64,
+    proposing_group_dna_hash: DnaHashB64,
+    agent_pub_key: String, // TODO: remove when every applet has a different key
+) -> WeResult<SignedAppletFederationAcceptance> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "accept_applet_federation",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'accept_applet_federation'.");
+    }
+
+    let mut group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(group_network_seed)),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+
+    install_and_accept_applet_federation(
+        window,
+        app_handle,
+        admin_ws,
+        meta_lair_client,
+        ports,
+        we_fs,
+        &mut group_client,
+        applet,
+        ActionHash::from(proposal_hash),
+        DnaHash::from(proposing_group_dna_hash),
+        agent_pub_key,
+    )
+    .await
+}
+
+/// The shared Applet is installed only once per conductor and is then used from all
+/// groups that it is federated with
+#[allow(clippy::too_many_arguments)]
+async fn install_and_accept_applet_federation(
+    window: tauri::Window,
+    app_handle: tauri::AppHandle,
+    admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    we_fs: tauri::State<'_, WeFileSystem>,
+    group_client: &mut AppAgentWebsocket,
+    applet: Applet,
+    proposal_hash: ActionHash,
+    proposing_group_dna_hash: DnaHash,
+    agent_pub_key: String,
+) -> WeResult<SignedAppletFederationAcceptance> {
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("hash_applet"),
+            ExternIO::encode(applet.clone())?,
+        )
+        .await?;
+    let applet_hash: EntryHash = result.decode()?;
+    let applet_app_id = format!("applet#{}", EntryHashB64::from(applet_hash));
+
+    let is_installed = admin_ws
+        .lock()
+        .await
+        .list_apps(None)
+        .await?
+        .into_iter()
+        .any(|app_info| app_info.installed_app_id == applet_app_id);
+    if !is_installed {
+        install_applet_bundle_if_necessary(
+            window,
+            app_handle,
+            admin_ws,
+            meta_lair_client,
+            ports,
+            we_fs,
+            applet_app_id,
+            applet.network_seed.clone(),
+            HashMap::new(),
+            applet
+                .properties
+                .iter()
+                .map(|(role_name, properties)| (role_name.clone(), properties.bytes().clone()))
+                .collect(),
+            agent_pub_key,
+            DnaHashB64::from(applet.devhub_dna_hash.clone()).to_string(),
+            ActionHashB64::from(applet.devhub_happ_entry_action_hash.clone()).to_string(),
+            ActionHashB64::from(applet.devhub_happ_release_hash.clone()).to_string(),
+        )
+        .await?;
+    }
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("accept_applet_federation"),
+            ExternIO::encode(AcceptAppletFederationInput {
+                applet,
+                proposal_hash,
+                proposing_group_dna_hash,
+            })?,
+        )
+        .await?;
+    let signed_acceptance: SignedAppletFederationAcceptance = result.decode()?;
+
+    Ok(signed_acceptance)
+}
@@ -17,10 +17,11 @@ use holochain::{
     conductor::api::{CellInfo, ClonedCell, ProvisionedCell},
     prelude::{
         kitsune_p2p::dependencies::kitsune_p2p_types::dependencies::lair_keystore_api::LairClient,
-        ActionHash, ActionHashB64, AgentPubKeyB64, AppBundleSource, CellId, CreateCloneCellPayload,
-        DisableCloneCellPayload, DnaHash, DnaHashB64, EnableCloneCellPayload, ExternIO,
-        FunctionName, HumanTimestamp, MembraneProof, RoleName, Serialize, SerializedBytes,
-        Timestamp, UnsafeBytes, ZomeCallUnsigned, ZomeName,
+        ActionHash, ActionHashB64, AgentPubKeyB64, AppBundle, AppBundleSource, AppManifest, CellId,
+        CreateCloneCellPayload, DisableCloneCellPayload, DnaHash, DnaHashB64,
+        EnableCloneCellPayload, ExternIO, FunctionName, HumanTimestamp, MembraneProof, RoleName,
+        Serialize, SerializedBytes, Timestamp, UnsafeBytes, YamlProperties, ZomeCallUnsigned,
+        ZomeName,
     },
 };
 use holochain_client::{
@@ -131,6 +132,7 @@ pub async fn install_applet_bundle_if_necessary(
     app_id: String,
     network_seed: Option<String>,
     membrane_proofs: HashMap<String, Vec<u8>>,
+    properties: HashMap<String, Vec<u8>>, // Segmented by RoleName, as in Applet.properties
     agent_pub_key: String, // TODO: remove when every applet has a different key
     devhub_dna_hash: String,
     happ_entry_action_hash: String,
@@ -282,6 +284,11 @@ pub async fn install_applet_bundle_if_necessary(
         );
     }
 
+    let converted_properties: BTreeMap<String, SerializedBytes> = properties
+        .into_iter()
+        .map(|(role_name, bytes)| (role_name, SerializedBytes::from(UnsafeBytes::from(bytes))))
+        .collect();
+
     window.emit("applet-install-progress", "installing")?;
 
     let happ_option = we_fs
@@ -299,7 +306,10 @@ pub async fn install_applet_bundle_if_necessary(
         Some(happ) => {
             admin_ws
                 .install_app(InstallAppPayload {
-                    source: AppBundleSource::Bundle(happ),
+                    source: AppBundleSource::Bundle(apply_applet_properties(
+                        happ,
+                        &converted_properties,
+                    )?),
                     agent_key: pub_key,
                     installed_app_id: Some(app_id.clone()),
                     network_seed,
@@ -318,6 +328,39 @@ pub async fn install_applet_bundle_if_necessary(
     Ok(app_info)
 }
 
+/// Sets the properties of an Applet, segmented by role name, as the DNA properties of
+/// the corresponding roles in the happ bundle, so that every agent installing the
+/// Applet ends up with the same cells
+pub fn apply_applet_properties(
+    app_bundle: AppBundle,
+    properties: &BTreeMap<String, SerializedBytes>,
+) -> WeResult<AppBundle> {
+    if properties.is_empty() {
+        return Ok(app_bundle);
+    }
+
+    let mut manifest = app_bundle.manifest().to_owned();
+    match &mut manifest {
+        AppManifest::V1(v1) => {
+            for (role_name, role_properties) in properties {
+                let role = v1
+                    .roles
+                    .iter_mut()
+                    .find(|role| role.name.eq(role_name))
+                    .ok_or(WeError::CustomError(format!(
+                        "Applet properties are set for role '{}' which is not part of the happ.",
+                        role_name
+                    )))?;
+                role.dna.modifiers.properties =
+                    Some(YamlProperties::try_from(role_properties.clone())?);
+            }
+        }
+    }
+
+    let bundle = app_bundle.into_inner().update_manifest(manifest)?;
+    Ok(AppBundle::from(bundle))
+}
+
 #[tauri::command]
 pub async fn update_applet_ui(
     window: tauri::Window,
@@ -1241,7 +1284,7 @@ impl AppAgentWebsocket {
         Ok(result)
     }
 
-    fn get_cell_id_from_role_name(&self, role_name: &RoleName) -> WeResult<CellId> {
+    pub fn get_cell_id_from_role_name(&self, role_name: &RoleName) -> WeResult<CellId> {
         if is_clone_id(role_name) {
             let base_role_name = get_base_role_name_from_clone_id(role_name);
 
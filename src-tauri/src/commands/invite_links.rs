@@ -0,0 +1,351 @@
+use std::sync::Arc;
+
+use base64::Engine;
+use futures::lock::Mutex;
+use holochain::prelude::{ExternIO, FunctionName, Record, RoleName, Signature, ZomeName};
+use holochain_keystore::{AgentPubKeyExt, MetaLairClient};
+use holochain_types::prelude::{AgentPubKey, AgentPubKeyB64};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use we_types::{GroupDnaProperties, GroupProfile};
+
+use crate::{
+    commands::install_applet_bundle::AppAgentWebsocket,
+    error::{WeError, WeResult},
+    launch::{AdminPort, AppPort},
+};
+
+/// A snapshot of the GroupProfile at the time the invite link was created. Only the
+/// hash of the logo is included to keep the link short.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GroupProfileSnapshot {
+    pub name: String,
+    /// Hex encoded sha256 hash of the `logo_src` of the GroupProfile
+    pub logo_hash: String,
+}
+
+/// Everything needed to join a group and to preview it before joining
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GroupInvitePayload {
+    pub network_seed: String,
+    pub properties: GroupDnaProperties,
+    pub group_profile: Option<GroupProfileSnapshot>,
+    /// A SignedGroupInvitation for invite-only groups
+    pub membrane_proof: Option<Vec<u8>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InviterSignature {
+    pub inviter: AgentPubKeyB64,
+    /// Signature over the msgpack encoded GroupInvitePayload
+    pub signature: Signature,
+}
+
+/// The content of a `we://group/<network_seed>?invite=<payload>` link, with the network
+/// seed percent-encoded. Plain `we://group/<network_seed>` links are not invite links,
+/// since the DNA hash of the group can't be known without its GroupDnaProperties.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GroupInviteLink {
+    pub payload: GroupInvitePayload,
+    pub inviter_signature: Option<InviterSignature>,
+}
+
+impl GroupInviteLink {
+    pub fn to_url(&self) -> WeResult<String> {
+        let bytes = ExternIO::encode(self.clone())?.0;
+        let invite = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+        Ok(format!(
+            "we://group/{}?invite={}",
+            utf8_percent_encode(&self.payload.network_seed, NON_ALPHANUMERIC),
+            invite
+        ))
+    }
+
+    pub fn from_url(link: &str) -> WeResult<Self> {
+        let url = url::Url::parse(link).map_err(|e| WeError::InvalidInviteLink(e.to_string()))?;
+
+        if url.scheme() != "we" || url.host_str() != Some("group") {
+            return Err(WeError::InvalidInviteLink(String::from(
+                "Not a we://group link",
+            )));
+        }
+
+        let network_seed = percent_decode_str(url.path().trim_start_matches('/'))
+            .decode_utf8()
+            .map_err(|e| WeError::InvalidInviteLink(e.to_string()))?
+            .to_string();
+        if network_seed.is_empty() {
+            return Err(WeError::InvalidInviteLink(String::from(
+                "The link does not contain a network seed",
+            )));
+        }
+
+        let invite = url
+            .query_pairs()
+            .find(|(key, _)| key == "invite")
+            .map(|(_, value)| value.to_string());
+
+        match invite {
+            Some(invite) => {
+                let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                    .decode(invite)
+                    .map_err(|e| WeError::InvalidInviteLink(e.to_string()))?;
+                let invite_link: GroupInviteLink = ExternIO::from(bytes).decode()?;
+                if invite_link.payload.network_seed.ne(&network_seed) {
+                    return Err(WeError::InvalidInviteLink(String::from(
+                        "The network seed of the link does not match its invite",
+                    )));
+                }
+                Ok(invite_link)
+            }
+            None => Err(WeError::InvalidInviteLink(String::from(
+                "The link does not contain an invite",
+            ))),
+        }
+    }
+
+    /// Whether the link is a `we://group` link carrying an invite, as opposed to a
+    /// plain group link or any other deep link
+    pub fn is_invite_link(link: &str) -> bool {
+        url::Url::parse(link)
+            .map(|url| {
+                url.scheme() == "we"
+                    && url.host_str() == Some("group")
+                    && url.query_pairs().any(|(key, _)| key == "invite")
+            })
+            .unwrap_or(false)
+    }
+
+    /// Returns the inviter if the link is signed and the signature is valid, None if
+    /// the link is not signed, and an error if the signature is invalid
+    pub async fn verify(&self) -> WeResult<Option<AgentPubKeyB64>> {
+        let Some(inviter_signature) = &self.inviter_signature else {
+            return Ok(None);
+        };
+        let payload_bytes = ExternIO::encode(self.payload.clone())?.0;
+        let valid = AgentPubKey::from(inviter_signature.inviter.clone())
+            .verify_signature_raw(&inviter_signature.signature, Arc::from(payload_bytes))
+            .await
+            .map_err(|e| WeError::InvalidInviteLink(e.to_string()))?;
+
+        match valid {
+            true => Ok(Some(inviter_signature.inviter.clone())),
+            false => Err(WeError::InvalidInviteLink(String::from(
+                "The signature of the inviter is invalid",
+            ))),
+        }
+    }
+}
+
+/// Sent to the main window as `group-invite-link-received` when an invite link is
+/// opened as a deep link
+#[derive(Serialize, Debug, Clone)]
+pub struct ReceivedGroupInviteLink {
+    pub link: GroupInviteLink,
+    /// The verified inviter, None if the link is not signed
+    pub inviter: Option<AgentPubKeyB64>,
+}
+
+/// Parses an invite link that was opened as a deep link and verifies its signature
+pub async fn receive_group_invite_link(link: &str) -> WeResult<ReceivedGroupInviteLink> {
+    let link = GroupInviteLink::from_url(link)?;
+    let inviter = link.verify().await?;
+    Ok(ReceivedGroupInviteLink { link, inviter })
+}
+
+/// Generates an invite link for the group with the given network seed, including a
+/// snapshot of the current GroupProfile. If `sign` is true, the link is signed by the
+/// calling agent so that the invitee can see who invited them.
+#[tauri::command]
+pub async fn generate_group_invite_link(
+    window: tauri::Window,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+    sign: bool,
+    membrane_proof: Option<Vec<u8>>,
+) -> WeResult<String> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "generate_group_invite_link",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'generate_group_invite_link'.");
+    }
+
+    let group_app_id = format!("group#{}", sha256::digest(network_seed.clone()));
+
+    let meta_lair_client = meta_lair_client.lock().await;
+
+    let mut group_app_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        group_app_id,
+        meta_lair_client.lair_client(),
+    )
+    .await?;
+
+    let result = group_app_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("get_group_dna_properties"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    let properties: GroupDnaProperties = result.decode()?;
+
+    let result = group_app_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("get_group_profile"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    let maybe_record: Option<Record> = result.decode()?;
+    let group_profile = match maybe_record {
+        Some(record) => record
+            .entry()
+            .to_app_option::<GroupProfile>()?
+            .map(|group_profile| GroupProfileSnapshot {
+                name: group_profile.name,
+                logo_hash: sha256::digest(group_profile.logo_src),
+            }),
+        None => None,
+    };
+
+    let payload = GroupInvitePayload {
+        network_seed,
+        properties,
+        group_profile,
+        membrane_proof,
+    };
+
+    let inviter_signature = match sign {
+        true => {
+            let inviter = group_app_client.my_pub_key.clone();
+            let payload_bytes = ExternIO::encode(payload.clone())?.0;
+            let signature = meta_lair_client
+                .sign(inviter.clone(), Arc::from(payload_bytes))
+                .await
+                .map_err(|e| WeError::CustomError(format!("Failed to sign invite link: {}", e)))?;
+            Some(InviterSignature {
+                inviter: inviter.into(),
+                signature,
+            })
+        }
+        false => None,
+    };
+
+    GroupInviteLink {
+        payload,
+        inviter_signature,
+    }
+    .to_url()
+}
+
+#[tauri::command]
+pub fn parse_group_invite_link(window: tauri::Window, link: String) -> WeResult<GroupInviteLink> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "parse_group_invite_link",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'parse_group_invite_link'.");
+    }
+    GroupInviteLink::from_url(link.as_str())
+}
+
+/// Parses the link and verifies the signature of the inviter, if any. Returns the
+/// inviter or None if the link is not signed.
+#[tauri::command]
+pub async fn verify_group_invite_link(
+    window: tauri::Window,
+    link: String,
+) -> WeResult<Option<AgentPubKeyB64>> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "verify_group_invite_link",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'verify_group_invite_link'.");
+    }
+    GroupInviteLink::from_url(link.as_str())?.verify().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsigned_link(network_seed: &str) -> GroupInviteLink {
+        GroupInviteLink {
+            payload: GroupInvitePayload {
+                network_seed: String::from(network_seed),
+                properties: GroupDnaProperties::default(),
+                group_profile: None,
+                membrane_proof: Some(vec![1, 2, 3]),
+            },
+            inviter_signature: None,
+        }
+    }
+
+    async fn signed_link(network_seed: &str) -> GroupInviteLink {
+        let keystore = holochain_keystore::test_keystore::spawn_test_keystore()
+            .await
+            .unwrap();
+        let inviter = keystore.new_sign_keypair_random().await.unwrap();
+        let mut link = unsigned_link(network_seed);
+        let payload_bytes = ExternIO::encode(link.payload.clone()).unwrap().0;
+        let signature = keystore
+            .sign(inviter.clone(), Arc::from(payload_bytes))
+            .await
+            .unwrap();
+        link.inviter_signature = Some(InviterSignature {
+            inviter: inviter.into(),
+            signature,
+        });
+        link
+    }
+
+    #[test]
+    fn network_seed_is_percent_encoded() {
+        let network_seed = "my group/seed?#%&=+ ü";
+        let url = unsigned_link(network_seed).to_url().unwrap();
+        assert!(url.starts_with("we://group/my%20group%2Fseed"));
+
+        let parsed = GroupInviteLink::from_url(&url).unwrap();
+        assert_eq!(parsed.payload.network_seed, network_seed);
+
+        assert!(GroupInviteLink::is_invite_link(&url));
+    }
+
+    #[test]
+    fn plain_group_links_are_not_invite_links() {
+        let url = unsigned_link("seed").to_url().unwrap();
+        let plain_url = url.split('?').next().unwrap();
+        assert!(!GroupInviteLink::is_invite_link(plain_url));
+        assert!(GroupInviteLink::from_url(plain_url).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn network_seed_must_match_the_signed_payload() {
+        let url = signed_link("seed").await.to_url().unwrap();
+        assert!(GroupInviteLink::from_url(&url).is_ok());
+
+        let tampered_url = url.replacen("we://group/seed", "we://group/other-seed", 1);
+        assert!(GroupInviteLink::from_url(&tampered_url).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn membrane_proof_is_covered_by_the_signature() {
+        let link = signed_link("seed").await;
+        assert!(link.verify().await.unwrap().is_some());
+
+        let mut tampered_link = link.clone();
+        tampered_link.payload.membrane_proof = Some(vec![4, 5, 6]);
+        assert!(tampered_link.verify().await.is_err());
+    }
+}
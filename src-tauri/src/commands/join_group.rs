@@ -1,25 +1,88 @@
 use futures::lock::Mutex;
-use holochain::prelude::AppBundle;
+use holochain::prelude::{
+    ActionHash, AppBundle, AppManifest, ExternIO, FunctionName, MembraneProof, RoleName,
+    SerializedBytes, Signature, Timestamp, UnsafeBytes, YamlProperties, ZomeName,
+};
 use holochain_client::{AdminWebsocket, AppInfo, AppStatusFilter, InstallAppPayload};
-use std::collections::HashMap;
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{AgentPubKey, AgentPubKeyB64};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use we_types::{GroupDnaProperties, GroupInvitation, SignedGroupInvitation};
 
-use crate::error::{WeError, WeResult};
+use crate::{
+    commands::install_applet_bundle::AppAgentWebsocket,
+    error::{WeError, WeResult},
+    launch::{AdminPort, AppPort},
+};
 
+/// Joins the group with the given network seed. If we have left the group before, its
+/// app gets enabled again and we are recorded as a member of the group again.
+/// Without a progenitor, the group is an open group with no stewards.
 #[tauri::command]
 pub async fn join_group(
     window: tauri::Window,
     admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
     network_seed: String,
+    progenitor: Option<AgentPubKeyB64>,
+    invite_only: Option<bool>,
+    membrane_proof: Option<Vec<u8>>,
 ) -> WeResult<AppInfo> {
     if window.label() != "main" {
         return Err(WeError::UnauthorizedWindow(String::from("join_group")));
     }
-    inner_join_group(admin_ws, network_seed).await
+    let group_app_id = format!("group#{}", sha256::digest(network_seed.clone()));
+
+    let rejoining = {
+        let mut admin_ws = admin_ws.lock().await;
+        let apps = admin_ws.list_apps(None).await;
+        admin_ws.close();
+        apps?
+            .iter()
+            .any(|app| app.installed_app_id.eq(&group_app_id))
+    };
+
+    let app_info = inner_join_group(
+        admin_ws,
+        network_seed,
+        GroupDnaProperties {
+            progenitor,
+            invite_only: invite_only.unwrap_or(false),
+        },
+        membrane_proof,
+    )
+    .await?;
+
+    if rejoining {
+        let mut group_client = AppAgentWebsocket::connect(
+            format!("ws://127.0.0.1:{}", ports.1),
+            group_app_id,
+            meta_lair_client.lock().await.lair_client(),
+        )
+        .await?;
+        group_client
+            .call_zome_fn(
+                RoleName::from("group"),
+                ZomeName::from("group"),
+                FunctionName::from("rejoin_group"),
+                ExternIO::encode(())?,
+            )
+            .await?;
+    }
+
+    Ok(app_info)
 }
 
+/// Joins the group with the given network seed and DNA properties. If a progenitor is
+/// provided, the group is governed by the progenitor and the stewards they appoint.
+/// Invite-only groups require a SignedGroupInvitation as membrane proof.
 pub async fn inner_join_group(
     admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
     network_seed: String,
+    properties: GroupDnaProperties,
+    membrane_proof: Option<Vec<u8>>,
 ) -> WeResult<AppInfo> {
     if cfg!(debug_assertions) {
         println!("### Called tauri command 'join_group'.");
@@ -43,7 +106,15 @@ pub async fn inner_join_group(
     }
 
     let agent_key = apps[0].agent_pub_key.clone(); // TODO: change when every app has a different public key
-    let we_bundle = AppBundle::decode(include_bytes!("../../../workdir/we.happ"))?;
+    let we_bundle = group_app_bundle(properties)?;
+
+    let mut membrane_proofs: HashMap<String, MembraneProof> = HashMap::new();
+    if let Some(proof) = membrane_proof {
+        membrane_proofs.insert(
+            String::from("group"),
+            Arc::new(SerializedBytes::from(UnsafeBytes::from(proof))),
+        );
+    }
 
     let app_info = admin_ws
         .install_app(InstallAppPayload {
@@ -51,7 +122,7 @@ pub async fn inner_join_group(
             agent_key,
             network_seed: Some(network_seed),
             installed_app_id: Some(group_app_id.clone()),
-            membrane_proofs: HashMap::new(),
+            membrane_proofs,
         })
         .await?;
 
@@ -61,3 +132,116 @@ pub async fn inner_join_group(
 
     Ok(app_info)
 }
+
+/// Returns the we.happ bundle with the given properties set for the group DNA
+fn group_app_bundle(properties: GroupDnaProperties) -> WeResult<AppBundle> {
+    let we_bundle = AppBundle::decode(include_bytes!("../../../workdir/we.happ"))?;
+
+    let properties =
+        serde_yaml::to_value(properties).map_err(|e| WeError::SerdeYamlError(e.to_string()))?;
+
+    let mut manifest = we_bundle.manifest().to_owned();
+    match &mut manifest {
+        AppManifest::V1(v1) => {
+            for role in v1.roles.iter_mut() {
+                if role.name == "group" {
+                    role.dna.modifiers.properties = Some(YamlProperties::new(properties.clone()));
+                }
+            }
+        }
+    }
+
+    let bundle = we_bundle.into_inner().update_manifest(manifest)?;
+    Ok(AppBundle::from(bundle))
+}
+
+/// Mirrors the StewardPermission of the group zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StewardPermission {
+    pub permission_hash: Option<ActionHash>,
+    #[serde(default)]
+    pub progenitor_chain_top: Option<ActionHash>,
+    #[serde(default)]
+    pub valid_until: Option<Timestamp>,
+    #[serde(default)]
+    pub progenitor_signature: Option<Signature>,
+}
+
+/// Creates an invitation for the given agent to join the group with the given network
+/// seed, signed by the calling agent. Returns the membrane proof that the invitee
+/// needs to pass to `join_group`. Only the progenitor and stewards of the group can
+/// create valid invitations. Invitations of stewards need the progenitor to be online
+/// and can only be used for about a day, until the attestation of their permission
+/// by the progenitor expires.
+#[tauri::command]
+pub async fn create_group_invitation(
+    window: tauri::Window,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+    invitee: AgentPubKeyB64,
+    expiry: Option<Timestamp>,
+) -> WeResult<Vec<u8>> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from(
+            "create_group_invitation",
+        )));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'create_group_invitation'.");
+    }
+
+    let group_app_id = format!("group#{}", sha256::digest(network_seed));
+
+    let meta_lair_client = meta_lair_client.lock().await;
+
+    let mut group_app_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        group_app_id,
+        meta_lair_client.lair_client(),
+    )
+    .await?;
+
+    let group_cell_id = group_app_client.get_cell_id_from_role_name(&RoleName::from("group"))?;
+
+    let result = group_app_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("get_my_steward_permission"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    let permission: Option<StewardPermission> = result.decode()?;
+    let Some(permission) = permission else {
+        return Err(WeError::CustomError(String::from(
+            "Only stewards of the group can create invitations.",
+        )));
+    };
+
+    let inviter: AgentPubKey = group_app_client.my_pub_key.clone();
+
+    let invitation = GroupInvitation {
+        group_dna_hash: group_cell_id.dna_hash().clone(),
+        invitee: AgentPubKey::from(invitee),
+        inviter: inviter.clone(),
+        steward_grant_hash: permission.permission_hash,
+        progenitor_chain_top: permission.progenitor_chain_top,
+        valid_until: permission.valid_until,
+        progenitor_signature: permission.progenitor_signature,
+        expiry,
+    };
+
+    let invitation_bytes = SerializedBytes::try_from(invitation.clone())?;
+    let signature = meta_lair_client
+        .sign(inviter, Arc::from(invitation_bytes.bytes().as_slice()))
+        .await
+        .map_err(|e| WeError::CustomError(format!("Failed to sign group invitation: {}", e)))?;
+
+    let membrane_proof = SerializedBytes::try_from(SignedGroupInvitation {
+        invitation,
+        signature,
+    })?;
+
+    Ok(membrane_proof.bytes().clone())
+}
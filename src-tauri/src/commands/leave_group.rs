@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use futures::lock::Mutex;
+use holochain::prelude::{ExternIO, FunctionName, RoleName, ZomeName};
+use holochain_client::{AdminWebsocket, AppStatusFilter};
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{EntryHash, EntryHashB64};
+
+use crate::{
+    commands::install_applet_bundle::AppAgentWebsocket,
+    error::{WeError, WeResult},
+    launch::{AdminPort, AppPort},
+};
+
+async fn get_my_applets(
+    app_port: &AppPort,
+    group_app_id: String,
+    meta_lair_client: &tauri::State<'_, Mutex<MetaLairClient>>,
+) -> WeResult<Vec<EntryHash>> {
+    let mut group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", app_port),
+        group_app_id,
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("get_my_applets"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    Ok(result.decode()?)
+}
+
+/// Leaves the group with the given network seed: removes us from the members of the
+/// group and disables the group app, together with the applets that none of our
+/// other groups use.
+#[tauri::command]
+pub async fn leave_group(
+    window: tauri::Window,
+    admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    network_seed: String,
+) -> WeResult<()> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from("leave_group")));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'leave_group'.");
+    }
+
+    let group_app_id = format!("group#{}", sha256::digest(network_seed));
+
+    let mut group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        group_app_id.clone(),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+    group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("leave_group"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+
+    let group_applets = get_my_applets(&ports.1, group_app_id.clone(), &meta_lair_client).await?;
+
+    let mut admin_ws = admin_ws.lock().await;
+
+    // Applets can be shared with other groups via federation, those need to keep running
+    let other_group_app_ids: Vec<String> = admin_ws
+        .list_apps(Some(AppStatusFilter::Running))
+        .await?
+        .into_iter()
+        .map(|app_info| app_info.installed_app_id)
+        .filter(|app_id| app_id.starts_with("group#") && app_id.ne(&group_app_id))
+        .collect();
+    let mut applets_of_other_groups: HashSet<EntryHash> = HashSet::new();
+    for app_id in other_group_app_ids {
+        let applets = get_my_applets(&ports.1, app_id, &meta_lair_client).await?;
+        applets_of_other_groups.extend(applets);
+    }
+
+    let installed_app_ids: Vec<String> = admin_ws
+        .list_apps(None)
+        .await?
+        .into_iter()
+        .map(|app_info| app_info.installed_app_id)
+        .collect();
+
+    for applet_hash in group_applets {
+        if applets_of_other_groups.contains(&applet_hash) {
+            continue;
+        }
+        let applet_app_id = format!("applet#{}", EntryHashB64::from(applet_hash));
+        if installed_app_ids.contains(&applet_app_id) {
+            admin_ws.disable_app(applet_app_id).await?;
+        }
+    }
+
+    admin_ws.disable_app(group_app_id).await?;
+
+    admin_ws.close();
+
+    Ok(())
+}
@@ -1,8 +1,16 @@
+pub mod blobs;
+pub mod bookmarks;
 pub mod conductor_info;
+pub mod custom_views;
 pub mod devhub;
 pub mod factory_reset;
+pub mod federate_applet;
 pub mod install_applet_bundle;
+pub mod invite_links;
 pub mod join_group;
+pub mod leave_group;
 pub mod notification;
 pub mod password;
-pub mod sign_zome_call;
\ No newline at end of file
+pub mod related_groups;
+pub mod sign_zome_call;
+pub mod upgrade_applet;
\ No newline at end of file
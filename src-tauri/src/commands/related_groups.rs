@@ -0,0 +1,153 @@
+use futures::lock::Mutex;
+use holochain::prelude::{ExternIO, FunctionName, Record, RoleName, ZomeName};
+use holochain_client::{AdminWebsocket, AppInfo};
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{AgentPubKeyB64, DnaHash};
+use serde::{Deserialize, Serialize};
+use we_types::{GroupDnaProperties, GroupProfile};
+
+use crate::{
+    commands::{install_applet_bundle::AppAgentWebsocket, join_group::inner_join_group},
+    error::{WeError, WeResult},
+    launch::{AdminPort, AppPort},
+};
+
+/// Mirrors the RelatedGroup of the group zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedGroup {
+    pub group_profile: GroupProfile,
+    pub network_seed: String,
+    pub group_dna_hash: DnaHash,
+}
+
+/// Creates a new group as a subgroup of the group with the given network seed. The
+/// network seed of the subgroup is derived from the parent's, and both groups are
+/// added as related groups of each other. Only stewards of the parent group can
+/// create subgroups.
+#[tauri::command]
+pub async fn create_subgroup(
+    window: tauri::Window,
+    admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    parent_network_seed: String,
+    group_profile: GroupProfile,
+    progenitor: Option<AgentPubKeyB64>,
+    invite_only: bool,
+) -> WeResult<AppInfo> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from("create_subgroup")));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'create_subgroup'.");
+    }
+
+    // Hashed so that the parent's network seed cannot be derived from the subgroup's.
+    // Members of the subgroup can still join the parent via its RelatedGroup entry.
+    let network_seed = sha256::digest(format!(
+        "{}/subgroup/{}",
+        parent_network_seed,
+        nanoid::nanoid!(13)
+    ));
+
+    let mut parent_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(parent_network_seed.clone())),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+
+    let result = parent_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("am_i_steward"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    let is_steward: bool = result.decode()?;
+    if !is_steward {
+        return Err(WeError::CustomError(String::from(
+            "Only stewards of the parent group can create subgroups.",
+        )));
+    }
+
+    let result = parent_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("get_group_profile"),
+            ExternIO::encode(())?,
+        )
+        .await?;
+    let maybe_record: Option<Record> = result.decode()?;
+    let parent_group_profile: GroupProfile = maybe_record
+        .and_then(|record| record.entry().to_app_option().ok().flatten())
+        .ok_or(WeError::CustomError(String::from(
+            "The parent group has no group profile yet.",
+        )))?;
+
+    let app_info = inner_join_group(
+        admin_ws,
+        network_seed.clone(),
+        GroupDnaProperties {
+            progenitor,
+            invite_only,
+        },
+        None,
+    )
+    .await?;
+
+    let mut child_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(network_seed.clone())),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+
+    let parent_dna_hash = parent_client
+        .get_cell_id_from_role_name(&RoleName::from("group"))?
+        .dna_hash()
+        .clone();
+    let child_dna_hash = child_client
+        .get_cell_id_from_role_name(&RoleName::from("group"))?
+        .dna_hash()
+        .clone();
+
+    child_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("set_group_profile"),
+            ExternIO::encode(group_profile.clone())?,
+        )
+        .await?;
+
+    child_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("add_related_group"),
+            ExternIO::encode(RelatedGroup {
+                group_profile: parent_group_profile,
+                network_seed: parent_network_seed,
+                group_dna_hash: parent_dna_hash,
+            })?,
+        )
+        .await?;
+
+    parent_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("add_related_group"),
+            ExternIO::encode(RelatedGroup {
+                group_profile,
+                network_seed,
+                group_dna_hash: child_dna_hash,
+            })?,
+        )
+        .await?;
+
+    Ok(app_info)
+}
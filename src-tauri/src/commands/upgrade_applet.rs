@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use futures::lock::Mutex;
+use holochain::prelude::{ExternIO, FunctionName, RoleName, ZomeName};
+use holochain_client::{AdminWebsocket, AppInfo};
+use holochain_keystore::MetaLairClient;
+use holochain_types::prelude::{ActionHash, ActionHashB64, DnaHashB64, EntryHash, EntryHashB64};
+use serde::{Deserialize, Serialize};
+use we_types::Applet;
+
+use crate::{
+    commands::install_applet_bundle::{install_applet_bundle_if_necessary, AppAgentWebsocket},
+    error::{WeError, WeResult},
+    filesystem::WeFileSystem,
+    launch::{AdminPort, AppPort},
+};
+
+/// Mirrors the ProposeAppletUpgradeInput of the group zome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProposeAppletUpgradeInput {
+    pub predecessor_hash: EntryHash,
+    pub successor_hash: EntryHash,
+}
+
+/// Installs the successor of an Applet, registers it in the group and proposes it
+/// as the upgrade of the predecessor. The predecessor is left installed and enabled
+/// so that its data stays readable.
+#[tauri::command]
+pub async fn upgrade_applet(
+    window: tauri::Window,
+    app_handle: tauri::AppHandle,
+    admin_ws: tauri::State<'_, Mutex<AdminWebsocket>>,
+    meta_lair_client: tauri::State<'_, Mutex<MetaLairClient>>,
+    ports: tauri::State<'_, (AdminPort, AppPort)>,
+    we_fs: tauri::State<'_, WeFileSystem>,
+    group_network_seed: String,
+    predecessor_hash: EntryHashB64,
+    successor: Applet,
+    agent_pub_key: String, // TODO: remove when every applet has a different key
+) -> WeResult<AppInfo> {
+    if window.label() != "main" {
+        return Err(WeError::UnauthorizedWindow(String::from("upgrade_applet")));
+    }
+    if cfg!(debug_assertions) {
+        println!("### Called tauri command 'upgrade_applet'.");
+    }
+
+    let mut group_client = AppAgentWebsocket::connect(
+        format!("ws://127.0.0.1:{}", ports.1),
+        format!("group#{}", sha256::digest(group_network_seed)),
+        meta_lair_client.lock().await.lair_client(),
+    )
+    .await?;
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("hash_applet"),
+            ExternIO::encode(successor.clone())?,
+        )
+        .await?;
+    let successor_hash: EntryHash = result.decode()?;
+
+    let app_info = install_applet_bundle_if_necessary(
+        window.clone(),
+        app_handle,
+        admin_ws,
+        meta_lair_client,
+        ports,
+        we_fs,
+        format!("applet#{}", EntryHashB64::from(successor_hash.clone())),
+        successor.network_seed.clone(),
+        HashMap::new(),
+        successor
+            .properties
+            .iter()
+            .map(|(role_name, properties)| (role_name.clone(), properties.bytes().clone()))
+            .collect(),
+        agent_pub_key,
+        DnaHashB64::from(successor.devhub_dna_hash.clone()).to_string(),
+        ActionHashB64::from(successor.devhub_happ_entry_action_hash.clone()).to_string(),
+        ActionHashB64::from(successor.devhub_happ_release_hash.clone()).to_string(),
+    )
+    .await?;
+
+    group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("register_applet"),
+            ExternIO::encode(successor)?,
+        )
+        .await?;
+
+    let result = group_client
+        .call_zome_fn(
+            RoleName::from("group"),
+            ZomeName::from("group"),
+            FunctionName::from("propose_applet_upgrade"),
+            ExternIO::encode(ProposeAppletUpgradeInput {
+                predecessor_hash: EntryHash::from(predecessor_hash),
+                successor_hash,
+            })?,
+        )
+        .await?;
+    let _upgrade_link_hash: ActionHash = result.decode()?;
+
+    Ok(app_info)
+}
@@ -79,6 +79,9 @@ pub enum WeError {
     #[error("Tauri API error: `{0}`")]
     TauriApiError(#[from] tauri::api::Error),
 
+    #[error("Invalid invite link: `{0}`")]
+    InvalidInviteLink(String),
+
     #[error("Attempted to call tauri command `{0}` from an unauthorized window")]
     UnauthorizedWindow(String),
 
@@ -3,7 +3,9 @@ use std::{fs, io::Write};
 
 use holochain::prelude::{ActionHash, ActionHashB64};
 use holochain_client::InstalledAppId;
-use holochain_types::prelude::{AnyDhtHash, AnyDhtHashB64, AppBundle, DnaHash, DnaHashB64};
+use holochain_types::prelude::{
+    AnyDhtHash, AnyDhtHashB64, AppBundle, DnaHash, DnaHashB64, EntryHash, EntryHashB64,
+};
 use holochain_types::web_app::WebAppBundle;
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
@@ -547,6 +549,34 @@ impl IconStore {
             return Ok(None);
         }
     }
+
+    fn blob_icon_path(&self, blob_hash: &EntryHash) -> PathBuf {
+        self.path
+            .join(EntryHashB64::from(blob_hash.clone()).to_string())
+    }
+
+    pub fn get_blob_icon(&self, blob_hash: &EntryHash) -> WeResult<Option<String>> {
+        let icon_path = self.blob_icon_path(blob_hash);
+        if icon_path.exists() {
+            let icon = fs::read_to_string(icon_path)?;
+            return Ok(Some(icon));
+        } else {
+            return Ok(None);
+        }
+    }
+
+    /// Creates the temporary file that the icon of a blob gets streamed into. Once
+    /// complete, it needs to be moved into place with `commit_blob_icon`.
+    pub fn create_blob_icon_file(&self, blob_hash: &EntryHash) -> WeResult<fs::File> {
+        let file = fs::File::create(self.blob_icon_path(blob_hash).with_extension("part"))?;
+        Ok(file)
+    }
+
+    pub fn commit_blob_icon(&self, blob_hash: &EntryHash) -> WeResult<()> {
+        let icon_path = self.blob_icon_path(blob_hash);
+        fs::rename(icon_path.with_extension("part"), icon_path)?;
+        Ok(())
+    }
 }
 
 pub fn unzip_file(reader: std::fs::File, outpath: PathBuf) -> WeResult<()> {
@@ -38,21 +38,35 @@ pub mod window;
 pub const APP_NAME: &str = "We";
 
 use crate::{
-    applet_iframes::{pong_iframe, read_asset},
+    applet_iframes::{
+        pong_iframe, read_asset, read_custom_view_asset, CustomViewLocations,
+        CUSTOM_VIEW_HOST_PREFIX,
+    },
     commands::{
+        blobs::{fetch_group_blob_icon, store_group_blob},
+        bookmarks::get_group_bookmarks,
         conductor_info::{get_conductor_info, is_launched},
+        custom_views::{export_custom_view, import_custom_view},
         devhub::{
             disable_dev_mode, enable_dev_mode, is_dev_mode_enabled, open_appstore, open_devhub,
         },
         factory_reset::execute_factory_reset,
+        federate_applet::{accept_applet_federation, federate_applet},
         install_applet_bundle::{
             fetch_available_ui_updates, fetch_icon, install_applet_bundle_if_necessary,
             update_applet_ui,
         },
-        join_group::join_group,
+        invite_links::{
+            generate_group_invite_link, parse_group_invite_link, receive_group_invite_link,
+            verify_group_invite_link, GroupInviteLink,
+        },
+        join_group::{create_group_invitation, join_group},
+        leave_group::leave_group,
         notification::{clear_systray_notification_state, notify_tauri},
         password::{create_password, enter_password, is_keystore_initialized},
+        related_groups::create_subgroup,
         sign_zome_call::sign_zome_call,
+        upgrade_applet::upgrade_applet,
     },
     filesystem::WeFileSystem,
     menu::{build_menu, handle_menu_event},
@@ -80,24 +94,38 @@ fn main() {
         .on_menu_event(|event| handle_menu_event(event.menu_item_id(), event.window()))
         .invoke_handler(tauri::generate_handler![
             clear_systray_notification_state,
+            create_group_invitation,
             create_password,
+            create_subgroup,
             disable_dev_mode,
             enable_dev_mode,
             enter_password,
             execute_factory_reset,
+            export_custom_view,
+            federate_applet,
+            accept_applet_federation,
+            fetch_group_blob_icon,
             fetch_icon,
             fetch_available_ui_updates,
+            generate_group_invite_link,
             get_conductor_info,
+            get_group_bookmarks,
+            import_custom_view,
             install_applet_bundle_if_necessary,
             is_dev_mode_enabled,
             is_keystore_initialized,
             is_launched,
             join_group,
+            leave_group,
             notify_tauri,
             open_appstore,
             open_devhub,
+            parse_group_invite_link,
             sign_zome_call,
+            store_group_blob,
             update_applet_ui,
+            upgrade_applet,
+            verify_group_invite_link,
         ])
         .register_uri_scheme_protocol("applet", |app_handle, request| {
             if request.uri().starts_with("applet://ping") {
@@ -109,8 +137,6 @@ fn main() {
             // prepare our response
             tauri::async_runtime::block_on(async move {
                 let we_fs = app_handle.state::<WeFileSystem>();
-                let mutex = app_handle.state::<Mutex<AdminWebsocket>>();
-                let mut admin_ws = mutex.lock().await;
 
                 let uri_without_protocol = request
                     .uri()
@@ -141,14 +167,24 @@ fn main() {
                     asset_file = asset_file.join(uri_components[i].clone());
                 }
 
-                let r = match read_asset(
-                    &we_fs,
-                    &mut admin_ws,
-                    lowercase_applet_id,
-                    asset_file.as_os_str().to_str().unwrap().to_string(),
-                )
-                .await
-                {
+                let asset_name = asset_file.as_os_str().to_str().unwrap().to_string();
+                let asset = match lowercase_applet_id.strip_prefix(CUSTOM_VIEW_HOST_PREFIX) {
+                    Some(custom_view_id) => {
+                        read_custom_view_asset(&app_handle, &custom_view_id.to_string(), asset_name)
+                            .await
+                    }
+                    None => {
+                        let mutex = app_handle.state::<Mutex<AdminWebsocket>>();
+                        let mut admin_ws = mutex.lock().await;
+                        let asset =
+                            read_asset(&we_fs, &mut admin_ws, lowercase_applet_id, asset_name)
+                                .await;
+                        admin_ws.close();
+                        asset
+                    }
+                };
+
+                let r = match asset {
                     Ok(Some((asset, mime_type))) => {
                         println!("Got asset for applet with id: {}", lowercase_applet_id);
                         let mut response = ResponseBuilder::new().status(StatusCode::ACCEPTED);
@@ -169,7 +205,6 @@ fn main() {
                         .body(format!("{:?}", e).as_bytes().to_vec()),
                 };
 
-                admin_ws.close();
                 r
             })
         })
@@ -227,7 +262,17 @@ fn main() {
                     None => None,
                 };
 
+            let test_applet_properties: Option<PathBuf> =
+                match cli_matches.args.get("test-applet-properties") {
+                    Some(data) => match data.value.clone() {
+                        Value::String(path) => Some(PathBuf::from(path)),
+                        _ => None,
+                    },
+                    None => None,
+                };
+
             let app_handle = app.handle();
+            app.manage(CustomViewLocations::default());
             let ui_server_port = portpicker::pick_unused_port().expect("No ports free");
             start_applet_uis_server(app_handle.clone(), ui_server_port);
 
@@ -241,7 +286,8 @@ fn main() {
 
             if let Some(test_applets) = maybe_test_applets {
                 let _ = tauri::async_runtime::block_on(async move {
-                    launch_test_applets_agent(app.handle(), test_applets).await
+                    launch_test_applets_agent(app.handle(), test_applets, test_applet_properties)
+                        .await
                 })?;
                 return Ok(());
             }
@@ -259,7 +305,18 @@ fn main() {
 
             if !disable_deep_link {
                 if let Err(err) = tauri_plugin_deep_link::register("we", move |request| {
-                    window.emit("deep-link-received", request).unwrap();
+                    if GroupInviteLink::is_invite_link(&request) {
+                        match tauri::async_runtime::block_on(receive_group_invite_link(&request)) {
+                            Ok(invite_link) => window
+                                .emit("group-invite-link-received", invite_link)
+                                .unwrap(),
+                            Err(err) => window
+                                .emit("group-invite-link-invalid", err.to_string())
+                                .unwrap(),
+                        }
+                    } else {
+                        window.emit("deep-link-received", request).unwrap();
+                    }
                     window
                         .request_user_attention(Some(UserAttentionType::Informational))
                         .unwrap();
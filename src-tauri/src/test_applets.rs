@@ -4,7 +4,7 @@ use futures::lock::Mutex;
 use std::collections::{BTreeMap, HashMap};
 use tauri::{AppHandle, Manager};
 use uuid::Uuid;
-use we_types::{Applet, GroupProfile};
+use we_types::{Applet, GroupDnaProperties, GroupProfile};
 
 use fixt::fixt;
 use holochain_client::AdminWebsocket;
@@ -14,7 +14,7 @@ use holochain_types::prelude::{
 };
 use holochain_types::prelude::{
     AppManifest, EntryHash, EntryHashB64, ExternIO, FunctionName, InstallAppPayload, RoleName,
-    ZomeName,
+    SerializedBytes, YamlProperties, ZomeName,
 };
 use holochain_types::web_app::WebAppBundle;
 
@@ -24,7 +24,10 @@ use crate::error::{LaunchHolochainError, WeError};
 use crate::filesystem::{ReleaseInfo, ResourceLocatorB64, UiIdentifier};
 use crate::window::build_main_window;
 use crate::{
-    commands::{install_applet_bundle::AppAgentWebsocket, join_group::join_group},
+    commands::{
+        install_applet_bundle::{apply_applet_properties, AppAgentWebsocket},
+        join_group::join_group,
+    },
     error::WeResult,
     filesystem::{Profile, WeFileSystem},
     launch::launch,
@@ -33,7 +36,15 @@ use crate::{
 pub async fn launch_test_applets_agent(
     app_handle: AppHandle,
     applets_paths: Vec<PathBuf>,
+    applet_properties_path: Option<PathBuf>,
 ) -> WeResult<()> {
+    // DNA properties segmented by role name, applied to every test applet with that role
+    let applet_properties: BTreeMap<String, serde_yaml::Value> = match applet_properties_path {
+        Some(path) => serde_yaml::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| WeError::SerdeYamlError(e.to_string()))?,
+        None => BTreeMap::new(),
+    };
+
     let temp_dir = temp_dir();
     let id = Uuid::new_v4();
     let profile: Profile = id.to_string();
@@ -73,6 +84,8 @@ pub async fn launch_test_applets_agent(
     let app_info = inner_join_group(
         app_handle.state::<Mutex<AdminWebsocket>>(),
         network_seed.clone().unwrap_or(String::from("")),
+        GroupDnaProperties::default(),
+        None,
     )
     .await?;
     println!("Joined group, {:?}", app_info.installed_app_id);
@@ -92,6 +105,7 @@ pub async fn launch_test_applets_agent(
             ExternIO::encode(GroupProfile {
                 name: String::from("Test Applet Group"),
                 logo_src: format!(r#"data:image/svg+xml;utf8,<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><title>account-group</title><path d="M12,5.5A3.5,3.5 0 0,1 15.5,9A3.5,3.5 0 0,1 12,12.5A3.5,3.5 0 0,1 8.5,9A3.5,3.5 0 0,1 12,5.5M5,8C5.56,8 6.08,8.15 6.53,8.42C6.38,9.85 6.8,11.27 7.66,12.38C7.16,13.34 6.16,14 5,14A3,3 0 0,1 2,11A3,3 0 0,1 5,8M19,8A3,3 0 0,1 22,11A3,3 0 0,1 19,14C17.84,14 16.84,13.34 16.34,12.38C17.2,11.27 17.62,9.85 17.47,8.42C17.92,8.15 18.44,8 19,8M5.5,18.25C5.5,16.18 8.41,14.5 12,14.5C15.59,14.5 18.5,16.18 18.5,18.25V20H5.5V18.25M0,20V18.5C0,17.11 1.89,15.94 4.45,15.6C3.86,16.28 3.5,17.22 3.5,18.25V20H0M24,20H20.5V18.25C20.5,17.22 20.14,16.28 19.55,15.6C22.11,15.94 24,17.11 24,18.5V20Z" /></svg>"#),
+                ..Default::default()
             })?,
         )
         .await?;
@@ -120,6 +134,18 @@ pub async fn launch_test_applets_agent(
             AppManifest::V1(v1) => v1.description.clone().unwrap_or(String::from("")),
         };
         let applet_name = app_bundle.manifest().app_name().to_string();
+        let role_names: Vec<String> = match app_bundle.manifest() {
+            AppManifest::V1(v1) => v1.roles.iter().map(|role| role.name.clone()).collect(),
+        };
+        let mut properties: BTreeMap<String, SerializedBytes> = BTreeMap::new();
+        for (role_name, role_properties) in applet_properties.iter() {
+            if role_names.contains(role_name) {
+                properties.insert(
+                    role_name.clone(),
+                    SerializedBytes::try_from(YamlProperties::new(role_properties.clone()))?,
+                );
+            }
+        }
         let applet = Applet {
             // name of the applet as chosen by the person adding it to the group,
             custom_name: applet_name.clone(),
@@ -134,7 +160,7 @@ pub async fn launch_test_applets_agent(
 
             network_seed: network_seed.clone(),
 
-            properties: BTreeMap::new(),
+            properties: properties.clone(),
         };
         println!("Registering applet {:?}", applet_name);
 
@@ -161,7 +187,9 @@ pub async fn launch_test_applets_agent(
         println!("Installing applet {:?}", applet_name);
         admin_ws
             .install_app(InstallAppPayload {
-                source: holochain_types::prelude::AppBundleSource::Bundle(app_bundle),
+                source: holochain_types::prelude::AppBundleSource::Bundle(
+                    apply_applet_properties(app_bundle, &properties)?,
+                ),
                 agent_key,
                 network_seed: network_seed.clone(),
                 installed_app_id: Some(app_id.clone()),
@@ -0,0 +1,39 @@
+import { AppAgentClient, ActionHash } from "@holochain/client";
+import { ZomeClient } from "@holochain-open-dev/utils";
+
+import { BookmarksPage, HrlWithContext } from "./types.js";
+
+export class BookmarksClient extends ZomeClient<never> {
+  constructor(
+    public client: AppAgentClient,
+    public roleName: string,
+    public zomeName = "bookmarks"
+  ) {
+    super(client, roleName, zomeName);
+  }
+
+  /**
+   * Pins the HRL to the group home
+   */
+  pinHrl(hrlWithContext: HrlWithContext, title: string): Promise<ActionHash> {
+    return this.callZome("pin_hrl", {
+      hrl_with_context: hrlWithContext,
+      title,
+    });
+  }
+
+  /**
+   * Only the author of a bookmark can unpin it
+   */
+  unpinBookmark(bookmarkHash: ActionHash): Promise<void> {
+    return this.callZome("unpin_bookmark", bookmarkHash);
+  }
+
+  reorderBookmarks(bookmarkHashes: ActionHash[]): Promise<ActionHash> {
+    return this.callZome("reorder_bookmarks", bookmarkHashes);
+  }
+
+  getBookmarks(offset = 0, limit?: number): Promise<BookmarksPage> {
+    return this.callZome("get_bookmarks", { offset, limit });
+  }
+}
@@ -0,0 +1,57 @@
+import {
+  ActionHash,
+  ActionHashB64,
+  AgentPubKey,
+  AgentPubKeyB64,
+  AnyDhtHash,
+  AnyDhtHashB64,
+  DnaHash,
+  DnaHashB64,
+} from "@holochain/client";
+
+export interface Hrl {
+  dna_hash: DnaHash;
+  resource_hash: AnyDhtHash;
+}
+
+export interface HrlWithContext {
+  hrl: Hrl;
+  context: Uint8Array;
+}
+
+export interface Bookmark {
+  hrl_with_context: HrlWithContext;
+  title: string;
+}
+
+export interface BookmarkInfo {
+  bookmark_hash: ActionHash;
+  bookmark: Bookmark;
+  author: AgentPubKey;
+  pinned_at: number;
+}
+
+export interface BookmarksPage {
+  bookmarks: BookmarkInfo[];
+  total: number;
+}
+
+/**
+ * A bookmark together with the applet that its HRL points into, as returned by the
+ * tauri backend
+ */
+export interface ResolvedBookmark {
+  bookmark_hash: ActionHashB64;
+  title: string;
+  dna_hash: DnaHashB64;
+  resource_hash: AnyDhtHashB64;
+  context: number[];
+  author: AgentPubKeyB64;
+  pinned_at: number;
+  applet_id: string | undefined;
+}
+
+export interface ResolvedBookmarksPage {
+  bookmarks: ResolvedBookmark[];
+  total: number;
+}
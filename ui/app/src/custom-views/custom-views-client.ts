@@ -1,7 +1,17 @@
-import { AppAgentClient, Record, ActionHash } from "@holochain/client";
+import {
+  AppAgentClient,
+  Record,
+  ActionHash,
+  EntryHash,
+} from "@holochain/client";
 import { EntryRecord, ZomeClient } from "@holochain-open-dev/utils";
 
-import { CustomView, CustomViewsSignal } from "./types.js";
+import {
+  BrokenCustomView,
+  CustomView,
+  CustomViewRevision,
+  CustomViewsSignal,
+} from "./types.js";
 
 export class CustomViewsClient extends ZomeClient<CustomViewsSignal> {
   constructor(
@@ -48,10 +58,43 @@ export class CustomViewsClient extends ZomeClient<CustomViewsSignal> {
     return new EntryRecord(record);
   }
 
+  getCustomViewRevisions(
+    originalCustomViewHash: ActionHash
+  ): Promise<Array<CustomViewRevision>> {
+    return this.callZome("get_custom_view_revisions", originalCustomViewHash);
+  }
+
+  async revertCustomView(
+    originalCustomViewHash: ActionHash,
+    revisionHash: ActionHash
+  ): Promise<EntryRecord<CustomView>> {
+    const record: Record = await this.callZome("revert_custom_view", {
+      original_custom_view_hash: originalCustomViewHash,
+      revision_hash: revisionHash,
+    });
+    return new EntryRecord(record);
+  }
+
   /** All Custom Views */
 
   async getAllCustomViews(): Promise<Array<EntryRecord<CustomView>>> {
     const records: Record[] = await this.callZome("get_all_custom_views", null);
     return records.map((r) => new EntryRecord(r));
   }
+
+  /** Applet Dependencies */
+
+  async getCustomViewsForApplet(
+    appletHash: EntryHash
+  ): Promise<Array<EntryRecord<CustomView>>> {
+    const records: Record[] = await this.callZome(
+      "get_custom_views_for_applet",
+      appletHash
+    );
+    return records.map((r) => new EntryRecord(r));
+  }
+
+  getBrokenCustomViews(): Promise<Array<BrokenCustomView>> {
+    return this.callZome("get_broken_custom_views", null);
+  }
 }
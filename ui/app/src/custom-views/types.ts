@@ -5,6 +5,9 @@ import {
   Delete,
   CreateLink,
   DeleteLink,
+  EntryHash,
+  ActionHash,
+  AgentPubKey,
 } from "@holochain/client";
 
 export type CustomViewsSignal =
@@ -47,4 +50,21 @@ export interface CustomView {
   js: string;
 
   css: string;
+
+  logo_blob_hash?: EntryHash | null;
+
+  // Entry hashes of the group applets that the view needs in order to work
+  applet_dependencies?: EntryHash[];
+}
+
+export interface CustomViewRevision {
+  revision_hash: ActionHash;
+  custom_view: CustomView;
+  author: AgentPubKey;
+  timestamp: number;
+}
+
+export interface BrokenCustomView {
+  custom_view_hash: ActionHash;
+  archived_applets: EntryHash[];
 }
@@ -2,7 +2,7 @@ import { html, LitElement } from "lit";
 import { state, query, property, customElement } from "lit/decorators.js";
 
 import { consume } from "@lit-labs/context";
-import { localized, msg } from "@lit/localize";
+import { localized, msg, str } from "@lit/localize";
 import { CellType } from "@holochain/client";
 
 import "@holochain-open-dev/elements/dist/elements/select-avatar.js";
@@ -15,6 +15,7 @@ import "@shoelace-style/shoelace/dist/components/button/button.js";
 import { notifyError, onSubmit } from "@holochain-open-dev/elements";
 
 import { WeStore } from "../we-store.js";
+import { ReceivedGroupInviteLink } from "../tauri.js";
 import { weStoreContext } from "../context.js";
 import { weStyles } from "../shared-styles.js";
 
@@ -37,6 +38,16 @@ export class JoinGroupDialog extends LitElement {
     this._dialog.show();
   }
 
+  /**
+   * Opens the dialog for an invite link, which carries everything needed to join
+   */
+  async openInvite(invite: ReceivedGroupInviteLink) {
+    this.invite = invite;
+    this.networkSeed = invite.link.payload.network_seed;
+    this._joinByPaste = false;
+    this._dialog.show();
+  }
+
   /** Private properties */
   @query("#dialog")
   _dialog!: SlDialog;
@@ -50,6 +61,9 @@ export class JoinGroupDialog extends LitElement {
   @property()
   _joinByPaste = false;
 
+  @state()
+  invite: ReceivedGroupInviteLink | undefined;
+
   @state()
   joining = false;
 
@@ -67,7 +81,15 @@ export class JoinGroupDialog extends LitElement {
     this.joining = true;
 
     try {
-      const groupAppInfo = await this._weStore.joinGroup(networkSeed!);
+      const payload = this.invite?.link.payload;
+      const groupAppInfo = payload
+        ? await this._weStore.joinGroup(
+            payload.network_seed,
+            payload.properties.progenitor ? payload.properties.progenitor : undefined,
+            payload.properties.invite_only,
+            payload.membrane_proof ? new Uint8Array(payload.membrane_proof) : undefined
+          )
+        : await this._weStore.joinGroup(networkSeed!);
 
       this.dispatchEvent(
         new CustomEvent("group-joined", {
@@ -82,6 +104,7 @@ export class JoinGroupDialog extends LitElement {
       );
       this._dialog.hide();
       this.networkSeed = undefined;
+      this.invite = undefined;
       if (this._inviteLinkField) { this._inviteLinkField.value = "" };
     } catch (e) {
       notifyError(msg("Error joining the group."));
@@ -110,6 +133,12 @@ export class JoinGroupDialog extends LitElement {
                 ? html`
                   <sl-input name="link" id="invite-link-field" .label=${msg("Invite Link")} required></sl-input>
                 `
+                : this.invite?.link.payload.group_profile
+                ? html`<span>${
+                    this.invite.inviter
+                      ? msg(str`${this.invite.inviter} invited you to join ${this.invite.link.payload.group_profile.name}.`)
+                      : msg(str`You have been invited to join ${this.invite.link.payload.group_profile.name}.`)
+                  }</span>`
                 : html`<span>${msg("You have been invited to join a group.")}</span>`
 
             }
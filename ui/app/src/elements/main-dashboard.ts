@@ -38,6 +38,7 @@ import { weStyles } from "../shared-styles.js";
 import { weStoreContext } from "../context.js";
 import { WeStore } from "../we-store.js";
 import { JoinGroupDialog } from "./join-group-dialog.js";
+import { ReceivedGroupInviteLink } from "../tauri.js";
 import { weLogoIcon } from "../icons/we-logo-icon.js";
 import { CreateGroupDialog } from "./create-group-dialog.js";
 
@@ -74,13 +75,17 @@ export class MainDashboard extends LitElement {
 
   _unlisten: UnlistenFn | undefined;
 
+  _unlistenInvite: UnlistenFn | undefined;
+
+  _unlistenInvalidInvite: UnlistenFn | undefined;
+
   selectedAppletHash = new StoreSubscriber(
     this,
     () => this._weStore.selectedAppletHash(),
     () => [this._weStore],
   )
 
-  async handleOpenGroup(networkSeed: string) {
+  async handleOpenGroup(networkSeed: string, invite?: ReceivedGroupInviteLink) {
     const groups = await toPromise(
       asyncDeriveStore(this._weStore.groupStores, (groups) =>
         joinAsyncMap(mapValues(groups, (groupStore) => groupStore.networkSeed))
@@ -93,6 +98,8 @@ export class MainDashboard extends LitElement {
 
     if (alreadyJoinedGroup) {
       this.openGroup(alreadyJoinedGroup[0]);
+    } else if (invite) {
+      this.joinGroupDialog.openInvite(invite);
     } else {
       this.joinGroupDialog.open(networkSeed);
     }
@@ -133,6 +140,20 @@ export class MainDashboard extends LitElement {
       }
     });
 
+    this._unlistenInvite = await listen("group-invite-link-received", async (e) => {
+      const invite = e.payload as ReceivedGroupInviteLink;
+      try {
+        await this.handleOpenGroup(invite.link.payload.network_seed, invite);
+      } catch (e) {
+        console.error(e);
+        notifyError(msg("Error opening the link."));
+      }
+    });
+    this._unlistenInvalidInvite = await listen("group-invite-link-invalid", (e) => {
+      console.error(e.payload);
+      notifyError(msg("Invalid invitation link."));
+    });
+
     // add eventlistener for clipboard
     window.addEventListener ("keydown", (zEvent) => {
       if (zEvent.altKey  &&  zEvent.key === "s") {  // case sensitive
@@ -174,6 +195,8 @@ export class MainDashboard extends LitElement {
 
   disconnectedCallback(): void {
     if (this._unlisten) this._unlisten();
+    if (this._unlistenInvite) this._unlistenInvite();
+    if (this._unlistenInvalidInvite) this._unlistenInvalidInvite();
   }
 
   get dynamicLayout() {
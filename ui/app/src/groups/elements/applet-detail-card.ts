@@ -112,7 +112,15 @@ export class AppletDetailCard extends LitElement {
             <span style="flex: 1;"></span>
             ${
               this.federatedGroups.get(this.appletHash) && this.federatedGroups.get(this.appletHash)!.length > 0
-                ? html`<span style="margin-right: 5px; margin-bottom: 5px;">Federated with:</span>`
+                ? html`<sl-tooltip
+                    .content=${msg(
+                      "Federations are claimed by the stewards of this group and can't be verified from here. Check that the other group lists this Applet as well."
+                    )}
+                  >
+                    <span style="margin-right: 5px; margin-bottom: 5px;"
+                      >Federated with (unverified):</span
+                    >
+                  </sl-tooltip>`
                 : html`<div style="height: 30px;"></div>`
             }
           </div>
@@ -37,6 +37,7 @@ import { weStoreContext } from "../../context.js";
 import { WeStore } from "../../we-store.js";
 import { GroupStore } from "../group-store.js";
 import { groupStoreContext } from "../context.js";
+import { federateApplet } from "../../tauri.js";
 
 @localized()
 @customElement("federate-applet-dialog")
@@ -91,29 +92,24 @@ export class FederateAppletDialog extends LitElement {
 
     this.federating = true;
     try {
-      const appletStore = await toPromise(
-        this._weStore.appletStores.get(this.appletHash)
-      );
       const groupStore = await this._weStore.groupStore(groupDnaHash);
 
       if (!groupStore) throw new Error("Failed to federate Applet: GroupStore not found.");
 
-      if (!appletStore) throw new Error("Applet not found");
-
-      const applet = appletStore.applet;
-      await groupStore.addFederatedApplet(applet);
-
-      // Two way link from one group to the other
-      // TODO: what to do when the applet is uninstalled?
-      await this._groupStore.groupClient.registerAppletFederation(
-        this.appletHash,
-        groupDnaHash
+      // Proposes the federation in this group, accepts it in the other group which
+      // adds the applet there, and registers the signed acceptance here
+      await federateApplet(
+        encodeHashToBase64(this.appletHash),
+        await toPromise(this._groupStore.networkSeed),
+        await toPromise(groupStore.networkSeed),
+        encodeHashToBase64(
+          this._weStore.appletBundlesStore.appstoreClient.myPubKey
+        )
       );
-      await groupStore.groupClient.registerAppletFederation(
-        this.appletHash,
-        this._groupStore.groupDnaHash
-      );
-        notify(msg("Applet federated."));
+      await groupStore.allMyApplets.reload();
+      await groupStore.allMyRunningApplets.reload();
+
+      notify(msg("Applet federated."));
       const dialog = this.shadowRoot?.getElementById("dialog") as SlDialog;
       dialog.hide();
     } catch (e) {
@@ -1,4 +1,4 @@
-import { EntryRecord, HashType, retype } from "@holochain-open-dev/utils";
+import { EntryRecord } from "@holochain-open-dev/utils";
 import {
   ActionHash,
   DnaHash,
@@ -7,12 +7,29 @@ import {
   Record,
   AppAgentWebsocket,
   AgentPubKey,
+  AppSignal,
   encodeHashToBase64,
 } from "@holochain/client";
 import { GroupProfile } from "@lightningrodlabs/we-applet";
 
 import { Applet } from "../applets/types.js";
-import { RelatedGroup } from "./types.js";
+import {
+  AppletListing,
+  AppletListingPage,
+  AppletProposalWithTally,
+  AppletTags,
+  AppletUpgrade,
+  GroupEvent,
+  GroupMember,
+  GroupProfileVersion,
+  GroupSettings,
+  GroupSignal,
+  ListAppletsInput,
+  RelatedGroup,
+  RelatedGroupWithStatus,
+  SearchAppletsInput,
+  SignedAppletFederationAcceptance,
+} from "./types.js";
 
 export class GroupClient {
   constructor(
@@ -28,10 +45,80 @@ export class GroupClient {
     return record ? new EntryRecord(record) : undefined;
   }
 
+  /**
+   * Gets all versions of the group profile, oldest first
+   */
+  async getGroupProfileHistory(): Promise<GroupProfileVersion[]> {
+    return this.callZome("get_group_profile_history", null);
+  }
+
+  /**
+   * Makes a previous version of the group profile the current one again
+   */
+  async rollbackGroupProfile(groupProfileHash: ActionHash): Promise<void> {
+    return this.callZome("rollback_group_profile", groupProfileHash);
+  }
+
   async setGroupProfile(groupProfile: GroupProfile): Promise<void> {
     await this.callZome("set_group_profile", groupProfile);
   }
 
+  /** Group Settings */
+
+  async getGroupSettings(): Promise<GroupSettings> {
+    return this.callZome("get_group_settings", null);
+  }
+
+  /**
+   * Only the progenitor of the group can set the group settings
+   */
+  async setGroupSettings(groupSettings: GroupSettings): Promise<ActionHash> {
+    return this.callZome("set_group_settings", groupSettings);
+  }
+
+  /** Applet Proposals */
+
+  /**
+   * Only possible if the group settings require applets to be voted on
+   */
+  async proposeApplet(applet: Applet): Promise<ActionHash> {
+    return this.callZome("propose_applet", applet);
+  }
+
+  /**
+   * Voting again replaces the previous vote
+   */
+  async voteOnAppletProposal(proposalHash: ActionHash, approve: boolean): Promise<ActionHash> {
+    return this.callZome("vote_on_applet_proposal", {
+      proposal_hash: proposalHash,
+      approve,
+    });
+  }
+
+  async getOpenAppletProposals(): Promise<Array<AppletProposalWithTally>> {
+    return this.callZome("get_open_applet_proposals", null);
+  }
+
+  async getAppletProposal(proposalHash: ActionHash): Promise<AppletProposalWithTally | undefined> {
+    return this.callZome("get_applet_proposal", proposalHash);
+  }
+
+  /**
+   * Adds the applet of a proposal that has reached its quorum to the group
+   */
+  async acceptAppletProposal(proposalHash: ActionHash): Promise<EntryHash> {
+    return this.callZome("accept_applet_proposal", proposalHash);
+  }
+
+  /** Members */
+
+  /**
+   * Gets the current members of the group, the earliest to join first
+   */
+  async getMembers(): Promise<GroupMember[]> {
+    return this.callZome("get_members", null);
+  }
+
   /** Related Groups */
 
   async addRelatedGroup(relatedGroup: RelatedGroup): Promise<void> {
@@ -44,6 +131,14 @@ export class GroupClient {
     return records.map((r) => new EntryRecord(r));
   }
 
+  async removeRelatedGroup(groupDnaHash: DnaHash): Promise<void> {
+    return this.callZome("remove_related_group", groupDnaHash);
+  }
+
+  async getRelatedGroupsWithStatus(): Promise<Array<RelatedGroupWithStatus>> {
+    return this.callZome("get_related_groups_with_status", null);
+  }
+
   /** Applets */
 
   async getGroupApplets(): Promise<Array<EntryHash>> {
@@ -71,6 +166,58 @@ export class GroupClient {
     return this.callZome("get_archived_applets", null);
   }
 
+  async listGroupApplets(
+    input: ListAppletsInput = {}
+  ): Promise<AppletListingPage> {
+    return this.callZome("list_group_applets", input);
+  }
+
+  async listUnjoinedApplets(
+    input: ListAppletsInput = {}
+  ): Promise<AppletListingPage> {
+    return this.callZome("list_unjoined_applets", input);
+  }
+
+  async listArchivedApplets(
+    input: ListAppletsInput = {}
+  ): Promise<AppletListingPage> {
+    return this.callZome("list_archived_applets", input);
+  }
+
+  async listFederatedApplets(
+    input: ListAppletsInput = {}
+  ): Promise<AppletListingPage> {
+    return this.callZome("list_federated_applets", input);
+  }
+
+  /** Applet Tags */
+
+  /**
+   * Replaces the tags and categories that we have given the applet, the applet hash stays the same
+   */
+  async setAppletTags(
+    appletHash: EntryHash,
+    tags: string[],
+    categories: string[]
+  ): Promise<void> {
+    return this.callZome("set_applet_tags", {
+      applet_hash: appletHash,
+      tags,
+      categories,
+    });
+  }
+
+  async getAppletTags(appletHash: EntryHash): Promise<AppletTags> {
+    return this.callZome("get_applet_tags", appletHash);
+  }
+
+  /**
+   * Searches the applets of the group that are not archived, all given filters need to match
+   */
+  async searchGroupApplets(input: SearchAppletsInput): Promise<Array<AppletListing>> {
+    return this.callZome("search_group_applets", input);
+  }
+
   async getApplet(appletHash: EntryHash): Promise<Applet | undefined> {
     const record = await this.callZome("get_applet", appletHash);
     if (!record) {
@@ -90,6 +237,20 @@ export class GroupClient {
     return this.callZome("register_applet", applet);
   }
 
+  /**
+   * Gets the agents that have joined the applet, with the timestamp at which they joined
+   * @param appletHash
+   */
+  async getAppletAgents(
+    appletHash: EntryHash
+  ): Promise<Array<[AgentPubKey, number]>> {
+    return this.callZome("get_applet_agents", appletHash);
+  }
+
+  async getMyJoinedAppletsWithTimestamps(): Promise<Array<[EntryHash, number]>> {
+    return this.callZome("get_my_joined_applets_with_timestamps", null);
+  }
+
   async hashApplet(applet: Applet): Promise<EntryHash> {
     return this.callZome("hash_applet", applet);
   }
@@ -102,23 +263,121 @@ export class GroupClient {
     return this.callZome("unarchive_applet", appletHash);
   }
 
-  async registerAppletFederation(
+  /**
+   * Proposes to federate the given applet with the group with the given DnaHash
+   */
+  async proposeAppletFederation(
     appletHash: EntryHash,
     groupDnaHash: DnaHash
+  ): Promise<ActionHash> {
+    return this.callZome("propose_applet_federation", {
+      applet_hash: appletHash,
+      group_dna_hash: groupDnaHash,
+    });
+  }
+
+  async getAppletFederationProposals(appletHash: EntryHash): Promise<Array<Record>> {
+    return this.callZome("get_applet_federation_proposals", appletHash);
+  }
+
+  /**
+   * Accepts the federation proposal made in the group with the given DnaHash,
+   * adding the applet to this group. The returned acceptance needs to be passed
+   * to registerAppletFederation in the proposing group.
+   */
+  async acceptAppletFederation(
+    applet: Applet,
+    proposalHash: ActionHash,
+    proposingGroupDnaHash: DnaHash
+  ): Promise<SignedAppletFederationAcceptance> {
+    return this.callZome("accept_applet_federation", {
+      applet,
+      proposal_hash: proposalHash,
+      proposing_group_dna_hash: proposingGroupDnaHash,
+    });
+  }
+
+  /**
+   * Registers the federation of a proposal that has been accepted by the other group
+   */
+  async registerAppletFederation(
+    appletHash: EntryHash,
+    groupDnaHash: DnaHash,
+    proposalHash: ActionHash,
+    signedAcceptance: SignedAppletFederationAcceptance
   ): Promise<ActionHash> {
     return this.callZome("register_applet_federation", {
       applet_hash: appletHash,
-      group_dna_hash: retype(groupDnaHash, HashType.ENTRY),
+      group_dna_hash: groupDnaHash,
+      proposal_hash: proposalHash,
+      signed_acceptance: signedAcceptance,
     });
   }
 
+  /**
+   * Proposes that the predecessor applet is superseded by the successor applet
+   */
+  async proposeAppletUpgrade(
+    predecessorHash: EntryHash,
+    successorHash: EntryHash
+  ): Promise<ActionHash> {
+    return this.callZome("propose_applet_upgrade", {
+      predecessor_hash: predecessorHash,
+      successor_hash: successorHash,
+    });
+  }
+
+  async getAppletSuccessors(appletHash: EntryHash): Promise<AppletUpgrade[]> {
+    return this.callZome("get_applet_successors", appletHash);
+  }
+
+  async getAppletPredecessors(appletHash: EntryHash): Promise<AppletUpgrade[]> {
+    return this.callZome("get_applet_predecessors", appletHash);
+  }
+
+  async getLatestAppletVersion(appletHash: EntryHash): Promise<EntryHash> {
+    return this.callZome("get_latest_applet_version", appletHash);
+  }
+
   async getFederatedGroups(appletHash: EntryHash): Promise<DnaHash[]> {
-    const groups: EntryHash[] = await this.callZome(
-      "get_federated_groups",
-      appletHash
-    );
+    return this.callZome("get_federated_groups", appletHash);
+  }
+
+  /**
+   * Registers an agent of a federated group as a participant of the applet
+   */
+  async registerExternalAgent(
+    appletHash: EntryHash,
+    agent: AgentPubKey,
+    groupDnaHash: DnaHash
+  ): Promise<ActionHash> {
+    return this.callZome("register_external_agent", {
+      applet_hash: appletHash,
+      agent,
+      group_dna_hash: groupDnaHash,
+    });
+  }
 
-    return groups.map((groupEntryHash) => retype(groupEntryHash, HashType.DNA));
+  async getAppletExternalAgents(
+    appletHash: EntryHash
+  ): Promise<Array<{ agent: AgentPubKey; group_dna_hash: DnaHash }>> {
+    return this.callZome("get_applet_external_agents", appletHash);
+  }
+
+  /** Signals */
+
+  onSignal(listener: (signal: GroupSignal) => void) {
+    return this.appAgentClient.on("signal", (signal: AppSignal) => {
+      if (signal.zome_name !== this.zomeName) return;
+      listener(signal.payload as GroupSignal);
+    });
+  }
+
+  /**
+   * Notifies the given peers of a change to the applets of the group via remote signal
+   */
+  async notifyPeers(event: GroupEvent, peers: AgentPubKey[]): Promise<void> {
+    return this.callZome("notify_peers", { event, peers });
   }
 
   private callZome(fn_name: string, payload: any) {
@@ -16,6 +16,7 @@ import {
   mapAndJoin,
   pipe,
   sliceAndJoin,
+  toPromise,
 } from "@holochain-open-dev/stores";
 import { LazyHoloHashMap, mapValues } from "@holochain-open-dev/utils";
 import {
@@ -34,10 +35,12 @@ import { AppletHash, GroupProfile } from "@lightningrodlabs/we-applet";
 import { GroupClient } from "./group-client.js";
 import { CustomViewsStore } from "../custom-views/custom-views-store.js";
 import { CustomViewsClient } from "../custom-views/custom-views-client.js";
+import { BookmarksClient } from "../bookmarks/bookmarks-client.js";
 import { WeStore } from "../we-store.js";
 import { AppEntry, Entity, HappReleaseEntry } from "../processes/appstore/types.js";
 import { Applet } from "../applets/types.js";
 import { isAppRunning } from "../utils.js";
+import { GroupEvent } from "./types.js";
 
 export const NEW_APPLETS_POLLING_FREQUENCY = 15000;
 
@@ -52,6 +55,8 @@ export class GroupStore {
 
   customViewsStore: CustomViewsStore;
 
+  bookmarksClient: BookmarksClient;
+
   members: AsyncReadable<Array<AgentPubKey>>;
 
   private constructed: boolean;
@@ -72,11 +77,40 @@ export class GroupStore {
     this.customViewsStore = new CustomViewsStore(
       new CustomViewsClient(appAgentWebsocket, "group")
     );
+    this.bookmarksClient = new BookmarksClient(appAgentWebsocket, "group");
     this.members = this.profilesStore.agentsWithProfile;
 
+    this.groupClient.onSignal(async (signal) => {
+      if (signal.type === "LocalEvent") {
+        await this.notifyOnlinePeers(signal.event);
+      }
+      if (signal.type === "LocalEvent" || signal.type === "RemoteEvent") {
+        await this.allMyApplets.reload();
+        await this.allMyRunningApplets.reload();
+        await this.allAdvertisedApplets.reload();
+      }
+    });
+
     this.constructed = true;
   }
 
+  // Passes changes to the applets of the group on to the members that are currently online
+  private async notifyOnlinePeers(event: GroupEvent) {
+    try {
+      const members = await toPromise(this.members);
+      const onlineMembers: AgentPubKey[] = [];
+      for (const member of members) {
+        const status = await toPromise(this.peerStatusStore.agentsStatus.get(member));
+        if (status === "online") onlineMembers.push(member);
+      }
+      if (onlineMembers.length > 0) {
+        await this.groupClient.notifyPeers(event, onlineMembers);
+      }
+    } catch (e) {
+      console.warn(`Failed to notify online peers of group event: ${e}`);
+    }
+  }
+
   public async addRelatedGroup(
     groupDnaHash: DnaHash,
     groupProfile: GroupProfile
@@ -1,8 +1,167 @@
-import { DnaHash } from "@holochain/client";
+import {
+  ActionHash,
+  AgentPubKey,
+  Create,
+  CreateLink,
+  Delete,
+  DeleteLink,
+  DnaHash,
+  EntryHash,
+  SignedActionHashed,
+  Record,
+  Update,
+} from "@holochain/client";
 import { GroupProfile } from "@lightningrodlabs/we-applet";
 
+import { Applet } from "../applets/types.js";
+
 export interface RelatedGroup {
   group_profile: GroupProfile;
   network_seed: string;
   group_dna_hash: DnaHash;
 }
+
+export type RelatedGroupStatus = "Mutual" | "OneSided" | "Unknown";
+
+export interface RelatedGroupWithStatus {
+  related_group_hash: EntryHash;
+  related_group: RelatedGroup;
+  added_by: AgentPubKey;
+  status: RelatedGroupStatus;
+}
+
+export interface AppletUpgrade {
+  predecessor_hash: EntryHash;
+  successor_hash: EntryHash;
+  proposed_by: AgentPubKey;
+  timestamp: number;
+}
+
+export interface AppletFederationAcceptance {
+  applet_hash: EntryHash;
+  proposal_hash: ActionHash;
+  proposing_group_dna_hash: DnaHash;
+  accepting_group_dna_hash: DnaHash;
+}
+
+export interface SignedAppletFederationAcceptance {
+  acceptance: AppletFederationAcceptance;
+  accepting_agent: AgentPubKey;
+  signature: Uint8Array;
+}
+
+export type AppletAddPolicy = "Anyone" | "Stewards" | "ProgenitorOnly";
+
+export type AppletArchivePolicy = "RegistrantOrProgenitor" | "ProgenitorOnly";
+
+export interface AppletProposalRules {
+  quorum: number;
+  voting_period_seconds: number;
+}
+
+export interface GroupSettings {
+  applet_add_policy: AppletAddPolicy;
+  applet_archive_policy: AppletArchivePolicy;
+  applet_proposal_rules?: AppletProposalRules;
+  previous_group_settings_hash?: ActionHash;
+}
+
+export interface AppletProposal {
+  applet_hash: EntryHash;
+  group_settings_hash: ActionHash | undefined;
+}
+
+export type AppletProposalStatus = "Open" | "Accepted" | "Rejected";
+
+export interface AppletProposalWithTally {
+  proposal_hash: ActionHash;
+  proposal: AppletProposal;
+  applet: Applet;
+  proposed_by: AgentPubKey;
+  proposed_at: number;
+  voting_closes_at: number;
+  quorum: number;
+  approvals: number;
+  rejections: number;
+  my_vote: boolean | undefined;
+  status: AppletProposalStatus;
+}
+
+export interface GroupMember {
+  agent: AgentPubKey;
+  joined_at: number;
+}
+
+export interface GroupProfileVersion {
+  group_profile_hash: ActionHash;
+  group_profile: GroupProfile;
+  author: AgentPubKey;
+  timestamp: number;
+}
+
+export type GroupEvent =
+  | { type: "AppletRegistered"; applet_hash: EntryHash }
+  | { type: "AppletArchived"; applet_hash: EntryHash }
+  | { type: "AppletFederated"; applet_hash: EntryHash };
+
+export type GroupSignal =
+  | {
+      type: "EntryCreated";
+      action: SignedActionHashed<Create>;
+      app_entry: any;
+    }
+  | {
+      type: "EntryUpdated";
+      action: SignedActionHashed<Update>;
+      app_entry: any;
+      original_app_entry: any;
+    }
+  | {
+      type: "EntryDeleted";
+      action: SignedActionHashed<Delete>;
+      original_app_entry: any;
+    }
+  | {
+      type: "LinkCreated";
+      action: SignedActionHashed<CreateLink>;
+      link_type: string;
+    }
+  | {
+      type: "LinkDeleted";
+      action: SignedActionHashed<DeleteLink>;
+      link_type: string;
+    }
+  | { type: "LocalEvent"; event: GroupEvent }
+  | { type: "RemoteEvent"; from_agent: AgentPubKey; event: GroupEvent };
+
+export interface ListAppletsInput {
+  sort_by?: "RegisteredAt" | "Name";
+  descending?: boolean;
+  offset?: number;
+  limit?: number;
+}
+
+export interface AppletListing {
+  applet_hash: EntryHash;
+  record: Record;
+  applet: Applet;
+  registered_by: AgentPubKey;
+  registered_at: number;
+  archived: boolean;
+}
+
+export interface AppletListingPage {
+  applets: AppletListing[];
+  total: number;
+}
+
+export interface AppletTags {
+  tags: string[];
+  categories: string[];
+}
+
+export interface SearchAppletsInput {
+  tag?: string;
+  category?: string;
+  query?: string;
+}
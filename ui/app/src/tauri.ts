@@ -1,9 +1,12 @@
 import {
   ActionHashB64,
   AgentPubKey,
+  AgentPubKeyB64,
   AppInfo,
   CallZomeRequestUnsigned,
   CellType,
+  DnaHashB64,
+  EntryHashB64,
   InstalledAppId,
   encodeHashToBase64,
   randomNonce,
@@ -13,10 +16,13 @@ import {
 } from "@holochain/client";
 import { encode } from "@msgpack/msgpack";
 import { invoke } from "@tauri-apps/api/tauri";
-import { WeNotification } from "@lightningrodlabs/we-applet";
+import { GroupProfile, WeNotification } from "@lightningrodlabs/we-applet";
 
 import { isWindows } from "./utils.js";
 import { ResourceLocatorB64 } from "./processes/appstore/get-happ-releases.js";
+import { Applet } from "./applets/types.js";
+import { ResolvedBookmarksPage } from "./bookmarks/types.js";
+import { SignedAppletFederationAcceptance } from "./groups/types.js";
 
 export async function isKeystoreInitialized(): Promise<boolean> {
   return invoke("is_keystore_initialized");
@@ -72,11 +78,51 @@ async function fetchPing(origin: string) {
   });
 }
 
-export async function joinGroup(networkSeed: string): Promise<AppInfo> {
+export async function joinGroup(
+  networkSeed: string,
+  progenitor?: AgentPubKeyB64,
+  inviteOnly: boolean = false,
+  membraneProof?: Uint8Array
+): Promise<AppInfo> {
   const appInfo: AppInfo = await invoke("join_group", {
     networkSeed,
+    progenitor: progenitor ? progenitor : null,
+    inviteOnly,
+    membraneProof: membraneProof ? Array.from(membraneProof) : null,
   });
 
+  return withUint8ArrayCellIds(appInfo);
+}
+
+/**
+ * Creates a subgroup of the group with the given network seed. The new group and
+ * the parent group are added as related groups of each other. Only stewards of the
+ * parent group can create subgroups.
+ */
+export async function createSubgroup(
+  parentNetworkSeed: string,
+  groupProfile: GroupProfile,
+  progenitor?: AgentPubKeyB64,
+  inviteOnly: boolean = false
+): Promise<AppInfo> {
+  const appInfo: AppInfo = await invoke("create_subgroup", {
+    parentNetworkSeed,
+    groupProfile,
+    progenitor: progenitor ? progenitor : null,
+    inviteOnly,
+  });
+
+  return withUint8ArrayCellIds(appInfo);
+}
+
+/**
+ * Leaves the group and disables it together with the applets that no other group uses
+ */
+export async function leaveGroup(networkSeed: string): Promise<void> {
+  return invoke("leave_group", { networkSeed });
+}
+
+function withUint8ArrayCellIds(appInfo: AppInfo): AppInfo {
   for (const [role, cells] of Object.entries(appInfo.cell_info)) {
     for (const cell of cells) {
       if (CellType.Provisioned in cell) {
@@ -103,6 +149,217 @@ export async function joinGroup(networkSeed: string): Promise<AppInfo> {
   return appInfo;
 }
 
+export interface GroupProfileSnapshot {
+  name: string;
+  logo_hash: string;
+}
+
+export interface GroupInvitePayload {
+  network_seed: string;
+  properties: {
+    progenitor: AgentPubKeyB64 | null;
+    invite_only: boolean;
+  };
+  group_profile: GroupProfileSnapshot | null;
+  membrane_proof: number[] | null;
+}
+
+export interface GroupInviteLink {
+  payload: GroupInvitePayload;
+  inviter_signature: {
+    inviter: AgentPubKeyB64;
+    signature: number[];
+  } | null;
+}
+
+/**
+ * Payload of the "group-invite-link-received" event, emitted when an invite link
+ * is opened as a deep link. The inviter has already been verified.
+ */
+export interface ReceivedGroupInviteLink {
+  link: GroupInviteLink;
+  inviter: AgentPubKeyB64 | null;
+}
+
+export async function generateGroupInviteLink(
+  networkSeed: string,
+  sign: boolean,
+  membraneProof?: Uint8Array
+): Promise<string> {
+  return invoke("generate_group_invite_link", {
+    networkSeed,
+    sign,
+    membraneProof: membraneProof ? Array.from(membraneProof) : null,
+  });
+}
+
+export async function parseGroupInviteLink(
+  link: string
+): Promise<GroupInviteLink> {
+  return invoke("parse_group_invite_link", { link });
+}
+
+/**
+ * Returns the inviter if the link is signed, or undefined if it is not signed.
+ * Throws if the signature is invalid.
+ */
+export async function verifyGroupInviteLink(
+  link: string
+): Promise<AgentPubKeyB64 | undefined> {
+  const inviter: AgentPubKeyB64 | null = await invoke(
+    "verify_group_invite_link",
+    { link }
+  );
+  return inviter ? inviter : undefined;
+}
+
+/**
+ * Creates an invitation for the given agent to join the invite-only group with the
+ * given network seed. Returns the membrane proof the invitee needs to join with.
+ */
+export async function createGroupInvitation(
+  networkSeed: string,
+  invitee: AgentPubKeyB64,
+  expiry?: number
+): Promise<Uint8Array> {
+  const membraneProof: number[] = await invoke("create_group_invitation", {
+    networkSeed,
+    invitee,
+    expiry: expiry ? expiry : null,
+  });
+  return new Uint8Array(membraneProof);
+}
+
+/**
+ * Federates the applet with another group the agent is a member of: proposes the
+ * federation in the applet's group, installs the applet if necessary and accepts it
+ * in the other group, and registers the signed acceptance in the applet's group
+ */
+export async function federateApplet(
+  appletHash: EntryHashB64,
+  groupNetworkSeed: string,
+  federatedGroupNetworkSeed: string,
+  agentPubKey: AgentPubKeyB64
+): Promise<void> {
+  return invoke("federate_applet", {
+    appletHash,
+    groupNetworkSeed,
+    federatedGroupNetworkSeed,
+    agentPubKey,
+  });
+}
+
+/**
+ * Accepts the federation proposal of another group in the group with the given
+ * network seed, installing the applet if necessary. The returned acceptance needs to
+ * be registered in the proposing group by one of its stewards.
+ */
+export async function acceptAppletFederation(
+  groupNetworkSeed: string,
+  applet: Applet,
+  proposalHash: ActionHashB64,
+  proposingGroupDnaHash: DnaHashB64,
+  agentPubKey: AgentPubKeyB64
+): Promise<SignedAppletFederationAcceptance> {
+  return invoke("accept_applet_federation", {
+    groupNetworkSeed,
+    applet,
+    proposalHash,
+    proposingGroupDnaHash,
+    agentPubKey,
+  });
+}
+
+/**
+ * Installs the successor of an applet and proposes it as the upgrade of the
+ * predecessor in the group. The predecessor stays installed and readable.
+ */
+export async function upgradeApplet(
+  groupNetworkSeed: string,
+  predecessorHash: EntryHashB64,
+  successor: Applet,
+  agentPubKey: AgentPubKeyB64
+): Promise<AppInfo> {
+  return invoke("upgrade_applet", {
+    groupNetworkSeed,
+    predecessorHash,
+    successor,
+    agentPubKey,
+  });
+}
+
+/**
+ * Stores the bytes as a chunked blob in the group and returns its hash
+ */
+export async function storeGroupBlob(
+  networkSeed: string,
+  mimeType: string,
+  bytes: Uint8Array
+): Promise<EntryHashB64> {
+  return invoke("store_group_blob", {
+    networkSeed,
+    mimeType,
+    bytes: Array.from(bytes),
+  });
+}
+
+/**
+ * Gets a blob of the group as a data URL, cached in the icon store
+ */
+export async function fetchGroupBlobIcon(
+  networkSeed: string,
+  blobHash: EntryHashB64
+): Promise<string> {
+  return invoke("fetch_group_blob_icon", {
+    networkSeed,
+    blobHash,
+  });
+}
+
+/**
+ * Gets a page of the bookmarks of the group, each with the id of the installed applet
+ * that it points into
+ */
+export async function getGroupBookmarks(
+  networkSeed: string,
+  offset = 0,
+  limit?: number
+): Promise<ResolvedBookmarksPage> {
+  return invoke("get_group_bookmarks", {
+    networkSeed,
+    offset,
+    limit,
+  });
+}
+
+/**
+ * Exports a custom view of the group, including its logo blob, to a file in the
+ * downloads directory and returns the path of that file
+ */
+export async function exportCustomView(
+  networkSeed: string,
+  customViewHash: ActionHashB64
+): Promise<string> {
+  return invoke("export_custom_view", {
+    networkSeed,
+    customViewHash,
+  });
+}
+
+/**
+ * Imports a custom view into the group from the contents of a file created by
+ * exportCustomView
+ */
+export async function importCustomView(
+  networkSeed: string,
+  bundle: string
+): Promise<ActionHashB64> {
+  return invoke("import_custom_view", {
+    networkSeed,
+    bundle,
+  });
+}
+
 export async function getConductorInfo(): Promise<ConductorInfo> {
   const conductor_info: any = await invoke("get_conductor_info");
 
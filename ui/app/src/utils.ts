@@ -1,4 +1,5 @@
 import {
+  ActionHash,
   EntryHash,
   CellId,
   CellInfo,
@@ -57,6 +58,29 @@ export function appletOrigin(
   }
 }
 
+/**
+ * Custom views are served under the hash of their create action, without the "uhCkk"
+ * prefix that all action hashes share so that the subdomain stays short enough
+ */
+export function customViewOrigin(
+  conductorInfo: ConductorInfo,
+  customViewHash: ActionHash
+): string {
+  const customViewId = `customview-${encodeHashToBase64(customViewHash)
+    .slice(5)
+    .toLowerCase()}`;
+  if (conductorInfo.applet_iframe_protocol === AppletIframeProtocol.Assets) {
+    return `applet://${customViewId}`;
+  } else if (
+    conductorInfo.applet_iframe_protocol ===
+    AppletIframeProtocol.LocalhostSubdomain
+  ) {
+    return `http://${customViewId}.127.0.0.1:${conductorInfo.applets_ui_port}`;
+  } else {
+    return `http://${customViewId}.localtest.me:${conductorInfo.applets_ui_port}`;
+  }
+}
+
 export function findAppForDnaHash(
   apps: ListAppsResponse,
   dnaHash: DnaHash
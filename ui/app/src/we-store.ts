@@ -33,7 +33,7 @@ import {
   decodeHashFromBase64,
 } from "@holochain/client";
 import { encodeHashToBase64 } from "@holochain/client";
-import { EntryHashB64 } from "@holochain/client";
+import { AgentPubKeyB64, EntryHashB64 } from "@holochain/client";
 import {
   ActionHash,
   AdminWebsocket,
@@ -149,9 +149,19 @@ export class WeStore {
     return appInfo;
   }
 
-  public async joinGroup(networkSeed: string): Promise<AppInfo> {
+  public async joinGroup(
+    networkSeed: string,
+    progenitor?: AgentPubKeyB64,
+    inviteOnly: boolean = false,
+    membraneProof?: Uint8Array
+  ): Promise<AppInfo> {
     try {
-      const appInfo = await joinGroup(networkSeed);
+      const appInfo = await joinGroup(
+        networkSeed,
+        progenitor,
+        inviteOnly,
+        membraneProof
+      );
       await this.reloadManualStores();
       return appInfo;
     } catch (e) {
@@ -557,6 +567,13 @@ export class WeStore {
         appId,
         networkSeed: applet.network_seed,
         membraneProofs: {},
+        // Uint8Arrays need to be converted to plain arrays to be deserialized as Vec<u8>
+        properties: Object.fromEntries(
+          Object.entries(applet.properties).map(([roleName, bytes]) => [
+            roleName,
+            Array.from(bytes),
+          ])
+        ),
         agentPubKey: encodeHashToBase64(
           this.appletBundlesStore.appstoreClient.myPubKey
         ),
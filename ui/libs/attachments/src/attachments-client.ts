@@ -1,6 +1,6 @@
 import { AnyDhtHash, AppAgentClient, DnaHash } from "@holochain/client";
 import { decode, encode } from "@msgpack/msgpack";
-import { HrlWithContext } from "@lightningrodlabs/we-applet";
+import { Hrl, HrlWithContext } from "@lightningrodlabs/we-applet";
 import { ZomeClient, getCellIdFromRoleName } from "@holochain-open-dev/utils";
 
 export class AttachmentsClient extends ZomeClient<{}> {
@@ -44,6 +44,16 @@ export class AttachmentsClient extends ZomeClient<{}> {
     }));
   }
 
+  /**
+   * Gets the hashes that the given HRL has been attached to
+   */
+  getAttachedTo(hrl: Hrl): Promise<Array<AnyDhtHash>> {
+    return this.callZome("get_attached_to", {
+      dna_hash: hrl[0],
+      resource_hash: hrl[1],
+    });
+  }
+
   removeAttachment(
     hash: AnyDhtHash,
     hrlWithContext: HrlWithContext
@@ -19,4 +19,21 @@ export class AttachmentsZomeMock extends ZomeMock implements AppAgentClient {
   async get_attachments(hash: AnyDhtHash): Promise<Array<HrlWithContext>> {
     return this.attachments.get(hash);
   }
+
+  async get_attached_to(hrl): Promise<Array<AnyDhtHash>> {
+    const attachedTo: Array<AnyDhtHash> = [];
+    for (const [hash, attachments] of this.attachments.entries()) {
+      if (
+        attachments.some(
+          (attachment: any) =>
+            attachment.hrl.dna_hash.toString() === hrl.dna_hash.toString() &&
+            attachment.hrl.resource_hash.toString() ===
+              hrl.resource_hash.toString()
+        )
+      ) {
+        attachedTo.push(hash);
+      }
+    }
+    return attachedTo;
+  }
 }
@@ -39,6 +39,11 @@ export interface EntryInfo {
 export interface GroupProfile {
   name: string;
   logo_src: string;
+  // Missing in group profiles created by older versions of We
+  description?: string;
+  banner_src?: string | null;
+  tags?: string[];
+  logo_blob_hash?: EntryHash | null;
 }
 
 export interface AttachmentType {